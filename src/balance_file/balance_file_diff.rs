@@ -0,0 +1,87 @@
+use crate::balance_file::balance_file_format::BalanceFile;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// Differences between two balance files, sorted by client id
+#[derive(Debug, Default, PartialEq)]
+pub struct BalanceFileDiff {
+    // (client_id, balance) pairs only in the new file
+    pub added: Vec<(i32, Decimal)>,
+    // (client_id, balance) pairs only in the old file
+    pub removed: Vec<(i32, Decimal)>,
+    // (client_id, old balance, new balance) of the clients whose balance changed
+    pub changed: Vec<(i32, Decimal, Decimal)>,
+}
+
+/// Balance file diff logic
+impl BalanceFileDiff {
+    /// True when both files have the same balances
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare [BalanceFile] old_file with [BalanceFile] new_file
+pub fn diff_balance_files(old_file: &BalanceFile, new_file: &BalanceFile) -> BalanceFileDiff {
+    let old_balances: BTreeMap<i32, Decimal> = old_file.balances.iter().copied().collect();
+    let new_balances: BTreeMap<i32, Decimal> = new_file.balances.iter().copied().collect();
+    let mut diff = BalanceFileDiff::default();
+
+    for (client_id, new_balance) in &new_balances {
+        match old_balances.get(client_id) {
+            None => diff.added.push((*client_id, *new_balance)),
+            Some(old_balance) if old_balance != new_balance => {
+                diff.changed.push((*client_id, *old_balance, *new_balance))
+            }
+            Some(_) => {}
+        }
+    }
+    for (client_id, old_balance) in &old_balances {
+        if !new_balances.contains_key(client_id) {
+            diff.removed.push((*client_id, *old_balance));
+        }
+    }
+    diff
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scenario:
+    /// Execute diff_balance_files with added, removed, changed and equal clients
+    /// Expectation:
+    /// Only the added, removed and changed clients should be returned
+    #[test]
+    fn when_diff_balance_files_should_return_added_removed_and_changed_clients() {
+        let old_file = BalanceFile {
+            generated_at: None,
+            balances: vec![
+                (1, Decimal::new(1000, 2)),
+                (2, Decimal::new(500, 2)),
+                (3, Decimal::ONE),
+            ],
+        };
+        let new_file = BalanceFile {
+            generated_at: None,
+            balances: vec![
+                (1, Decimal::new(1000, 2)),
+                (2, Decimal::new(750, 2)),
+                (4, Decimal::TWO),
+            ],
+        };
+
+        let expected_result = diff_balance_files(&old_file, &new_file);
+
+        assert_eq!(
+            BalanceFileDiff {
+                added: vec![(4, Decimal::TWO)],
+                removed: vec![(3, Decimal::ONE)],
+                changed: vec![(2, Decimal::new(500, 2), Decimal::new(750, 2))],
+            },
+            expected_result
+        );
+        assert!(diff_balance_files(&old_file, &old_file).is_empty());
+    }
+}
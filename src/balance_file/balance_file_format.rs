@@ -0,0 +1,264 @@
+use crate::errors::common_error::CommonError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use log::error;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Header record prefix, followed by the generation timestamp
+pub const HEADER_RECORD: &str = "H";
+/// Trailer record prefix, followed by the record count, the sum of balances and the SHA-256 of the body lines
+pub const TRAILER_RECORD: &str = "T";
+
+/// Layout of the balance files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BalanceFileFormat {
+    // only `ID BALANCE` lines
+    Legacy,
+    // `ID BALANCE` lines between a header and a trailer with control totals
+    #[default]
+    Control,
+}
+
+/// Balance file format from its name, `legacy` or `control`
+impl std::str::FromStr for BalanceFileFormat {
+    type Err = CommonError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "legacy" => Ok(BalanceFileFormat::Legacy),
+            "control" => Ok(BalanceFileFormat::Control),
+            _ => Err(CommonError::InvalidField {
+                field: "balance_file_format".to_string(),
+                reason: "must be legacy or control".to_string(),
+            }),
+        }
+    }
+}
+
+/// Read a [BalanceFileFormat] from a config value
+impl TryFrom<String> for BalanceFileFormat {
+    type Error = CommonError;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        format.parse()
+    }
+}
+
+/// Content of a balance file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceFile {
+    // header timestamp, None on legacy files
+    pub generated_at: Option<DateTime<Utc>>,
+    // (client_id, balance) pairs in file order
+    pub balances: Vec<(i32, Decimal)>,
+}
+
+/// Balance file logic
+impl BalanceFile {
+    /// Sum of the balances
+    pub fn total(&self) -> Decimal {
+        self.balances.iter().map(|(_, balance)| *balance).sum()
+    }
+
+    /// Balance of [i32] client_id, None if the client is not in the file
+    pub fn balance_of(&self, client_id: i32) -> Option<Decimal> {
+        self.balances
+            .iter()
+            .find(|(file_client_id, _)| *file_client_id == client_id)
+            .map(|(_, balance)| *balance)
+    }
+}
+
+/// Render (client_id, balance) pairs of [Decimal] balances as a balance file in [BalanceFileFormat] format
+pub fn render_balance_file(
+    balances: &[(i32, Decimal)],
+    format: BalanceFileFormat,
+    generated_at: DateTime<Utc>,
+) -> String {
+    let body: String = balances
+        .iter()
+        .map(|(client_id, balance)| format!("{:02} {:.2}\n", client_id, balance))
+        .collect();
+
+    match format {
+        BalanceFileFormat::Legacy => body,
+        BalanceFileFormat::Control => {
+            let total: Decimal = balances.iter().map(|(_, balance)| *balance).sum();
+            format!(
+                "{HEADER_RECORD} {}\n{body}{TRAILER_RECORD} {} {:.2} {}\n",
+                generated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                balances.len(),
+                total,
+                body_digest(&body)
+            )
+        }
+    }
+}
+
+/// Parse [str] content of a balance file in any [BalanceFileFormat], checking the trailer when there is a header
+/// Returns a [CommonError] if any line is invalid or the control totals do not match the body
+pub fn parse_balance_file(content: &str) -> Result<BalanceFile, CommonError> {
+    let lines: Vec<&str> = content.lines().collect();
+
+    let Some(header) = lines
+        .first()
+        .and_then(|line| line.strip_prefix(&format!("{HEADER_RECORD} ")))
+    else {
+        return Ok(BalanceFile {
+            generated_at: None,
+            balances: parse_body(&lines)?,
+        });
+    };
+
+    let generated_at = DateTime::parse_from_rfc3339(header)
+        .map_err(|_| invalid_balance_file("invalid header timestamp"))?
+        .with_timezone(&Utc);
+    let trailer = lines
+        .last()
+        .filter(|_| lines.len() > 1)
+        .and_then(|line| line.strip_prefix(&format!("{TRAILER_RECORD} ")))
+        .ok_or_else(|| invalid_balance_file("missing trailer"))?;
+    let body_lines = &lines[1..lines.len() - 1];
+    let balances = parse_body(body_lines)?;
+
+    let mut trailer_fields = trailer.split(' ');
+    let (Some(count), Some(total), Some(digest), None) = (
+        trailer_fields.next(),
+        trailer_fields.next(),
+        trailer_fields.next(),
+        trailer_fields.next(),
+    ) else {
+        return Err(invalid_balance_file("invalid trailer"));
+    };
+    let body: String = body_lines.iter().map(|line| format!("{line}\n")).collect();
+    let body_total: Decimal = balances.iter().map(|(_, balance)| *balance).sum();
+
+    if count.parse::<usize>().ok() != Some(balances.len()) {
+        return Err(invalid_balance_file("record count does not match"));
+    }
+    if total.parse::<Decimal>().ok() != Some(body_total) {
+        return Err(invalid_balance_file("sum of balances does not match"));
+    }
+    if digest != body_digest(&body) {
+        return Err(invalid_balance_file("checksum does not match"));
+    }
+
+    Ok(BalanceFile {
+        generated_at: Some(generated_at),
+        balances,
+    })
+}
+
+/// Parse `ID BALANCE` [str] lines
+/// Returns a [CommonError] if any line is invalid or a client id is repeated
+fn parse_body(lines: &[&str]) -> Result<Vec<(i32, Decimal)>, CommonError> {
+    let balances: Vec<(i32, Decimal)> = lines
+        .iter()
+        .map(|line| {
+            line.split_once(' ')
+                .and_then(|(client_id, balance)| {
+                    Some((client_id.parse().ok()?, balance.parse().ok()?))
+                })
+                .ok_or_else(|| invalid_balance_file("invalid balance line"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut client_ids = std::collections::HashSet::new();
+    if balances
+        .iter()
+        .any(|(client_id, _)| !client_ids.insert(*client_id))
+    {
+        return Err(invalid_balance_file("repeated client id"));
+    }
+    Ok(balances)
+}
+
+/// SHA-256 hex digest of [str] body
+fn body_digest(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+/// Build the [CommonError] of an invalid balance file
+fn invalid_balance_file(reason: &str) -> CommonError {
+    error!("parse_balance_file - error - {reason}");
+    CommonError::InvalidBalanceFile(reason.to_string())
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Balances of two clients
+    fn create_balances() -> Vec<(i32, Decimal)> {
+        vec![(1, Decimal::new(1050, 2)), (2, Decimal::new(-250, 2))]
+    }
+
+    /// Scenario:
+    /// Execute render_balance_file with [BalanceFileFormat::Legacy]
+    /// Expectation:
+    /// Only the `ID BALANCE` lines should be rendered
+    #[test]
+    fn when_render_balance_file_with_legacy_format_should_return_only_balance_lines() {
+        let expected_result =
+            render_balance_file(&create_balances(), BalanceFileFormat::Legacy, Utc::now());
+
+        assert_eq!("01 10.50\n02 -2.50\n", expected_result);
+    }
+
+    /// Scenario:
+    /// Execute render_balance_file with [BalanceFileFormat::Control] and parse the result
+    /// Expectation:
+    /// Header and trailer should be rendered and the same balances parsed back
+    #[test]
+    fn when_render_balance_file_with_control_format_should_add_header_and_trailer() {
+        let generated_at = DateTime::parse_from_rfc3339("2025-01-31T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let content =
+            render_balance_file(&create_balances(), BalanceFileFormat::Control, generated_at);
+        let expected_result = parse_balance_file(&content).unwrap();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!("H 2025-01-31T10:00:00Z", lines[0]);
+        assert!(lines[3].starts_with("T 2 8.00 "));
+        assert_eq!(64, lines[3].rsplit(' ').next().unwrap().len());
+        assert_eq!(Some(generated_at), expected_result.generated_at);
+        assert_eq!(create_balances(), expected_result.balances);
+    }
+
+    /// Scenario:
+    /// Execute parse_balance_file when a balance line was modified
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[test]
+    fn when_parse_balance_file_with_tampered_body_should_return_common_error() {
+        let content =
+            render_balance_file(&create_balances(), BalanceFileFormat::Control, Utc::now())
+                .replace("01 10.50\n02 -2.50", "01 12.50\n02 -4.50");
+
+        let expected_result = parse_balance_file(&content);
+
+        assert_eq!(
+            CommonError::InvalidBalanceFile("checksum does not match".to_string()),
+            expected_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute parse_balance_file when a client id is repeated
+    /// Expectation:
+    /// A [CommonError::InvalidBalanceFile] should be returned
+    #[test]
+    fn when_parse_balance_file_with_repeated_client_id_should_return_common_error() {
+        let expected_result = parse_balance_file("01 1.00\n01 2.00\n");
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidBalanceFile(reason)) if reason == "repeated client id"
+        ));
+    }
+}
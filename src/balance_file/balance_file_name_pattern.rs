@@ -0,0 +1,196 @@
+use crate::constants::constants::TEMPORARY_FILE_SUFFIX;
+use crate::errors::common_error::CommonError;
+use chrono::NaiveDate;
+
+/// Format of the date placeholder
+pub const DATE_FORMAT: &str = "%d%m%Y";
+
+/// Placeholder replaced by the generation date, formatted as `DDMMYYYY`
+pub const DATE_PLACEHOLDER: &str = "{date}";
+/// Placeholder replaced by the number of the file generated on the same date, starting at 1
+pub const COUNTER_PLACEHOLDER: &str = "{counter}";
+
+/// Name of the balance files, e.g. `{date}_{counter}.DAT`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceFileNamePattern {
+    // pattern with both placeholders
+    pattern: String,
+}
+
+/// Balance file name pattern logic
+impl BalanceFileNamePattern {
+    /// Validate [str] pattern, it must have each placeholder once and be a plain file name
+    /// Returns a [CommonError] if the pattern is invalid
+    pub fn new(pattern: &str) -> Result<Self, CommonError> {
+        let invalid_pattern = |reason: &str| CommonError::InvalidField {
+            field: "file_name_pattern".to_string(),
+            reason: reason.to_string(),
+        };
+
+        if pattern.matches(DATE_PLACEHOLDER).count() != 1
+            || pattern.matches(COUNTER_PLACEHOLDER).count() != 1
+        {
+            return Err(invalid_pattern(
+                "must contain {date} and {counter} exactly once",
+            ));
+        }
+        if pattern.contains('/') || pattern.contains('\\') {
+            return Err(invalid_pattern("must be a file name, not a path"));
+        }
+        if pattern.ends_with(TEMPORARY_FILE_SUFFIX) {
+            return Err(invalid_pattern(
+                "must not end with the temporary file suffix",
+            ));
+        }
+
+        Ok(BalanceFileNamePattern {
+            pattern: pattern.to_string(),
+        })
+    }
+
+    /// Pattern with both placeholders
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// File name of the [u32] counter file generated on [str] date
+    pub fn file_name(&self, date: &str, counter: u32) -> String {
+        self.pattern
+            .replace(DATE_PLACEHOLDER, date)
+            .replace(COUNTER_PLACEHOLDER, &counter.to_string())
+    }
+
+    /// Date and counter of [str] file_name if it was generated with this pattern
+    pub fn parse_file_name(&self, file_name: &str) -> Option<(NaiveDate, u32)> {
+        let date_position = self.pattern.find(DATE_PLACEHOLDER)?;
+        let counter_position = self.pattern.find(COUNTER_PLACEHOLDER)?;
+        let date_first = date_position < counter_position;
+        let (first, second) = if date_first {
+            (DATE_PLACEHOLDER, COUNTER_PLACEHOLDER)
+        } else {
+            (COUNTER_PLACEHOLDER, DATE_PLACEHOLDER)
+        };
+        let (prefix, rest) = self.pattern.split_once(first)?;
+        let (separator, suffix) = rest.split_once(second)?;
+
+        let placeholders = file_name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        let (first_value, second_value) =
+            self.split_placeholders(placeholders, separator, date_first)?;
+        let (date, counter) = if date_first {
+            (first_value, second_value)
+        } else {
+            (second_value, first_value)
+        };
+
+        let date = NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+        Some((date, counter.parse().ok()?))
+    }
+
+    /// Counter of [str] file_name if it was generated with this pattern on [str] date
+    pub fn counter(&self, file_name: &str, date: &str) -> Option<u32> {
+        let (prefix, suffix) = self
+            .pattern
+            .replace(DATE_PLACEHOLDER, date)
+            .split_once(COUNTER_PLACEHOLDER)
+            .map(|(prefix, suffix)| (prefix.to_string(), suffix.to_string()))?;
+
+        file_name
+            .strip_prefix(&prefix)?
+            .strip_suffix(&suffix)?
+            .parse()
+            .ok()
+    }
+}
+
+/// Balance file name pattern "private" logic
+impl BalanceFileNamePattern {
+    /// Split [str] value, with the part matching the first placeholder in front, by [str] separator
+    /// The date always has 8 digits, so it is cut by length when it comes first
+    fn split_placeholders<'a>(
+        &self,
+        value: &'a str,
+        separator: &str,
+        date_first: bool,
+    ) -> Option<(&'a str, &'a str)> {
+        if date_first {
+            let (date, rest) = value.split_at_checked(8)?;
+            Some((date, rest.strip_prefix(separator)?))
+        } else {
+            let (rest, date) = value.split_at_checked(value.len().checked_sub(8)?)?;
+            Some((rest.strip_suffix(separator)?, date))
+        }
+    }
+}
+
+/// Pattern used when it is not configured, `DDMMYYYY_N.DAT`
+impl Default for BalanceFileNamePattern {
+    fn default() -> Self {
+        BalanceFileNamePattern {
+            pattern: format!("{DATE_PLACEHOLDER}_{COUNTER_PLACEHOLDER}.DAT"),
+        }
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATE: &str = "31012025";
+
+    /// Scenario:
+    /// Execute file_name and counter with the default pattern
+    /// Expectation:
+    /// `DDMMYYYY_N.DAT` names should be generated and their counter read back
+    #[test]
+    fn when_default_pattern_should_generate_and_read_dat_file_names() {
+        let pattern = BalanceFileNamePattern::default();
+
+        let file_name = pattern.file_name(DATE, 10);
+
+        assert_eq!("31012025_10.DAT", file_name);
+        assert_eq!(Some(10), pattern.counter(&file_name, DATE));
+        assert_eq!(None, pattern.counter(&file_name, "01022025"));
+        assert_eq!(None, pattern.counter("31012025_10.DAT.tmp", DATE));
+    }
+
+    /// Scenario:
+    /// Execute parse_file_name with the default pattern and with a pattern having the counter first
+    /// Expectation:
+    /// The date and counter should be returned only for names generated with the pattern
+    #[test]
+    fn when_parse_file_name_should_return_date_and_counter() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let default_pattern = BalanceFileNamePattern::default();
+        let counter_first = BalanceFileNamePattern::new("balances-{counter}{date}.txt").unwrap();
+
+        assert_eq!(
+            Some((date, 10)),
+            default_pattern.parse_file_name("31012025_10.DAT")
+        );
+        assert_eq!(
+            Some((date, 3)),
+            counter_first.parse_file_name("balances-331012025.txt")
+        );
+        assert_eq!(None, default_pattern.parse_file_name("31132025_1.DAT"));
+        assert_eq!(None, default_pattern.parse_file_name("31012025_1.DAT.tmp"));
+        assert_eq!(None, default_pattern.parse_file_name("events.jsonl"));
+    }
+
+    /// Scenario:
+    /// Execute new with patterns missing a placeholder or with a folder
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[test]
+    fn when_new_with_invalid_pattern_should_return_common_error() {
+        let custom_pattern = BalanceFileNamePattern::new("balances-{counter}-{date}.txt").unwrap();
+
+        assert_eq!("balances-2-31012025.txt", custom_pattern.file_name(DATE, 2));
+        assert!(BalanceFileNamePattern::new("{date}.DAT").is_err());
+        assert!(BalanceFileNamePattern::new("{date}_{counter}_{counter}.DAT").is_err());
+        assert!(matches!(
+            BalanceFileNamePattern::new("out/{date}_{counter}.DAT"),
+            Err(CommonError::InvalidField { field, .. }) if field == "file_name_pattern"
+        ));
+    }
+}
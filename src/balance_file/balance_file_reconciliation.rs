@@ -0,0 +1,89 @@
+use crate::balance_file::balance_file_format::BalanceFile;
+use crate::dto::reconciliation_report_dto::BalanceMismatch;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Compare the balances of [BalanceFile] balance_file with the (client_id, balance) pairs of [Decimal] expected_balances,
+/// recomputed from the movements recorded before the file was written
+/// A client missing from either side is compared against zero
+/// Returns how many clients were checked and the ones whose balance does not match, sorted by client id
+pub fn reconcile_balance_file(
+    balance_file: &BalanceFile,
+    expected_balances: &BTreeMap<i32, Decimal>,
+) -> (usize, Vec<BalanceMismatch>) {
+    let file_balances: BTreeMap<i32, Decimal> = balance_file.balances.iter().copied().collect();
+    let client_ids: BTreeSet<i32> = file_balances
+        .keys()
+        .chain(expected_balances.keys())
+        .copied()
+        .collect();
+
+    let mismatches = client_ids
+        .iter()
+        .filter_map(|client_id| {
+            let file_balance = file_balances.get(client_id).copied();
+            let expected_balance = expected_balances
+                .get(client_id)
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            let difference = file_balance.unwrap_or(Decimal::ZERO) - expected_balance;
+            (!difference.is_zero()).then_some(BalanceMismatch {
+                client_id: *client_id,
+                file_balance,
+                expected_balance,
+                difference,
+            })
+        })
+        .collect();
+    (client_ids.len(), mismatches)
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scenario:
+    /// Execute reconcile_balance_file with a matching client, a changed one, one missing from the file
+    /// and one with zero balance and no movements
+    /// Expectation:
+    /// Only the changed client and the client missing from the file should be reported
+    #[test]
+    fn when_reconcile_balance_file_should_return_clients_not_matching_their_movements() {
+        let balance_file = BalanceFile {
+            generated_at: None,
+            balances: vec![
+                (1, Decimal::new(1000, 2)),
+                (2, Decimal::new(500, 2)),
+                (4, Decimal::ZERO),
+            ],
+        };
+        let expected_balances = BTreeMap::from([
+            (1, Decimal::new(1000, 2)),
+            (2, Decimal::new(750, 2)),
+            (3, Decimal::ONE),
+        ]);
+
+        let (checked_clients, mismatches) =
+            reconcile_balance_file(&balance_file, &expected_balances);
+
+        assert_eq!(4, checked_clients);
+        assert_eq!(
+            vec![
+                BalanceMismatch {
+                    client_id: 2,
+                    file_balance: Some(Decimal::new(500, 2)),
+                    expected_balance: Decimal::new(750, 2),
+                    difference: Decimal::new(-250, 2),
+                },
+                BalanceMismatch {
+                    client_id: 3,
+                    file_balance: None,
+                    expected_balance: Decimal::ONE,
+                    difference: -Decimal::ONE,
+                },
+            ],
+            mismatches
+        );
+    }
+}
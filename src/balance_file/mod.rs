@@ -0,0 +1,4 @@
+pub mod balance_file_diff;
+pub mod balance_file_format;
+pub mod balance_file_name_pattern;
+pub mod balance_file_reconciliation;
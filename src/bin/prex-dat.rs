@@ -0,0 +1,115 @@
+use clap::{Parser, Subcommand};
+use prex_core_challenge::balance_file::balance_file_diff::diff_balance_files;
+use prex_core_challenge::balance_file::balance_file_format::{parse_balance_file, BalanceFile};
+use prex_core_challenge::errors::common_error::CommonError;
+use std::process::ExitCode;
+
+/// Offline tool for the balance files written by the service
+#[derive(Parser)]
+#[command(
+    name = "prex-dat",
+    about = "Parse, validate and diff balance .DAT files"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// prex-dat commands
+#[derive(Subcommand)]
+enum Command {
+    /// Check the syntax and the control totals of every file
+    Validate { files: Vec<String> },
+    /// Show the generation date, record count and sum of balances of a file
+    Totals { file: String },
+    /// Show the balance of one client
+    Balance { file: String, client_id: i32 },
+    /// Show the clients added, removed and with a different balance in new_file
+    Diff { old_file: String, new_file: String },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("prex-dat - {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run [Command] command, printing its result
+/// Returns a [CommonError] if a file cannot be read or is invalid
+fn run(command: Command) -> Result<ExitCode, CommonError> {
+    match command {
+        Command::Validate { files } => {
+            let mut exit_code = ExitCode::SUCCESS;
+            for file in files {
+                match read_balance_file(&file) {
+                    Ok(balance_file) => println!(
+                        "{file}: ok - records: {} - total: {:.2}",
+                        balance_file.balances.len(),
+                        balance_file.total()
+                    ),
+                    Err(error) => {
+                        println!("{file}: invalid - {error}");
+                        exit_code = ExitCode::FAILURE;
+                    }
+                }
+            }
+            Ok(exit_code)
+        }
+        Command::Totals { file } => {
+            let balance_file = read_balance_file(&file)?;
+            match balance_file.generated_at {
+                Some(generated_at) => println!("generated at: {}", generated_at.to_rfc3339()),
+                None => println!("generated at: unknown (legacy format)"),
+            }
+            println!("records: {}", balance_file.balances.len());
+            println!("total: {:.2}", balance_file.total());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Balance { file, client_id } => {
+            match read_balance_file(&file)?.balance_of(client_id) {
+                Some(balance) => {
+                    println!("{:02} {:.2}", client_id, balance);
+                    Ok(ExitCode::SUCCESS)
+                }
+                None => Err(CommonError::ClientNotFound(client_id)),
+            }
+        }
+        Command::Diff { old_file, new_file } => {
+            let diff = diff_balance_files(
+                &read_balance_file(&old_file)?,
+                &read_balance_file(&new_file)?,
+            );
+            for (client_id, balance) in &diff.added {
+                println!("+ {:02} {:.2}", client_id, balance);
+            }
+            for (client_id, balance) in &diff.removed {
+                println!("- {:02} {:.2}", client_id, balance);
+            }
+            for (client_id, old_balance, new_balance) in &diff.changed {
+                println!(
+                    "~ {:02} {:.2} -> {:.2} ({:+.2})",
+                    client_id,
+                    old_balance,
+                    new_balance,
+                    new_balance - old_balance
+                );
+            }
+            if diff.is_empty() {
+                println!("no differences");
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Read and parse [str] file
+/// Returns a [CommonError] if the file cannot be read or is invalid
+fn read_balance_file(file: &str) -> Result<BalanceFile, CommonError> {
+    let content =
+        std::fs::read_to_string(file).map_err(|_| CommonError::FileReadFailed(file.to_string()))?;
+    parse_balance_file(&content)
+}
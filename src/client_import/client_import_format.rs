@@ -0,0 +1,249 @@
+use crate::dto::new_client_dto::NewClient;
+use crate::errors::common_error::CommonError;
+use chrono::NaiveDate;
+use csv::{Position, ReaderBuilder, Trim};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Date format of the birth dates of an import file
+const BIRTH_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Layout of the client import files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ClientImportFormat {
+    // header line with the field names, then one client per line
+    #[default]
+    Csv,
+    // one JSON object per line, also known as JSON Lines
+    Ndjson,
+}
+
+/// Implementation Client import format
+impl ClientImportFormat {
+    /// Format of [str] file_name from its extension, None if it is unknown
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        Path::new(file_name)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.parse().ok())
+    }
+}
+
+/// Client import format from its name, `csv`, `ndjson` or `jsonl`
+impl std::str::FromStr for ClientImportFormat {
+    type Err = CommonError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ClientImportFormat::Csv),
+            "ndjson" | "jsonl" => Ok(ClientImportFormat::Ndjson),
+            _ => Err(CommonError::InvalidField {
+                field: "format".to_string(),
+                reason: "must be csv or ndjson".to_string(),
+            }),
+        }
+    }
+}
+
+/// Read a [ClientImportFormat] from a query param
+impl TryFrom<String> for ClientImportFormat {
+    type Error = CommonError;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        format.parse()
+    }
+}
+
+/// Row of an import file
+pub struct ClientImportRow {
+    // line of the row in the file, starting at 1
+    pub line: usize,
+    // client of the row, or why it cannot be read
+    pub new_client: Result<NewClient, CommonError>,
+}
+
+/// Fields of a row as written in the file, so a bad birth date is reported naming its field
+#[derive(Deserialize)]
+struct ClientImportRecord {
+    client_name: String,
+    birth_date: String,
+    document_number: String,
+    country: String,
+}
+
+/// Parse every row of [str] content written in [ClientImportFormat] format, blank lines are skipped
+/// A row that cannot be read is kept with its [CommonError], so the rest of the file can still be imported
+pub fn parse_client_import(content: &str, format: ClientImportFormat) -> Vec<ClientImportRow> {
+    match format {
+        ClientImportFormat::Csv => parse_csv(content),
+        ClientImportFormat::Ndjson => parse_ndjson(content),
+    }
+}
+
+/// Parse the rows of a CSV file, its header names the fields in any order
+fn parse_csv(content: &str) -> Vec<ClientImportRow> {
+    let mut reader = ReaderBuilder::new()
+        .trim(Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(error) => {
+            return vec![ClientImportRow {
+                line: 1,
+                new_client: Err(invalid_row(error.to_string())),
+            }]
+        }
+    };
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| match record {
+            Ok(record) => ClientImportRow {
+                line: line_of(content, record.position(), index),
+                new_client: record
+                    .deserialize::<ClientImportRecord>(Some(&headers))
+                    .map_err(|error| match error.kind() {
+                        // without the position, it is already reported by the line
+                        csv::ErrorKind::Deserialize { err, .. } => invalid_row(err.to_string()),
+                        _ => invalid_row(error.to_string()),
+                    })
+                    .and_then(map_new_client),
+            },
+            Err(error) => ClientImportRow {
+                line: line_of(content, error.position(), index),
+                new_client: Err(invalid_row(error.to_string())),
+            },
+        })
+        .collect()
+}
+
+/// Line of the record at [Position] position of content, the line after the header for the [usize] index record if unknown
+/// Counted from the byte offset, the line reported by the reader does not count the blank lines
+fn line_of(content: &str, position: Option<&Position>, index: usize) -> usize {
+    let Some(rest) = position.and_then(|position| content.get(position.byte() as usize..)) else {
+        return index + 2;
+    };
+    // the record position is right after the previous record, before the blank lines
+    let start = content.len() - rest.trim_start_matches(['\r', '\n']).len();
+    content[..start].matches('\n').count() + 1
+}
+
+/// Parse the rows of a JSON Lines file
+fn parse_ndjson(content: &str) -> Vec<ClientImportRow> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ClientImportRow {
+            line: index + 1,
+            new_client: serde_json::from_str::<ClientImportRecord>(line)
+                .map_err(|error| invalid_row(error.to_string()))
+                .and_then(map_new_client),
+        })
+        .collect()
+}
+
+/// Maps a [NewClient] from [ClientImportRecord] record
+/// Returns a [CommonError] if the birth date is not in `YYYY-MM-DD` format
+fn map_new_client(record: ClientImportRecord) -> Result<NewClient, CommonError> {
+    let birth_date = NaiveDate::parse_from_str(record.birth_date.trim(), BIRTH_DATE_FORMAT)
+        .map_err(|_| CommonError::InvalidField {
+            field: "birth_date".to_string(),
+            reason: format!(
+                "must be a date in format YYYY-MM-DD, got {}",
+                record.birth_date
+            ),
+        })?;
+    Ok(NewClient {
+        client_name: record.client_name,
+        birth_date,
+        document_number: record.document_number,
+        country: record.country,
+    })
+}
+
+/// Build the [CommonError] of a row that cannot be read
+fn invalid_row(reason: String) -> CommonError {
+    CommonError::InvalidField {
+        field: "row".to_string(),
+        reason,
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scenario:
+    /// Parse a CSV file with the columns in another order, a blank line, a bad birth date and a missing column
+    /// Expectation:
+    /// Every row should be returned with its file line, the valid one mapped to a [NewClient]
+    #[test]
+    fn when_parse_client_import_csv_should_keep_every_row_with_its_line() {
+        let content = "document_number,client_name,birth_date,country\n\
+                       123, Jane Doe ,1990-05-01,AR\n\
+                       \n\
+                       456,John Doe,01/05/1990,AR\n\
+                       789,Jim Doe,1990-05-01\n";
+
+        let rows = parse_client_import(content, ClientImportFormat::Csv);
+
+        assert_eq!(3, rows.len());
+        assert_eq!(2, rows[0].line);
+        let new_client = rows[0].new_client.as_ref().unwrap();
+        assert_eq!("Jane Doe", new_client.client_name);
+        assert_eq!("123", new_client.document_number);
+        assert_eq!(
+            NaiveDate::from_ymd_opt(1990, 5, 1).unwrap(),
+            new_client.birth_date
+        );
+        assert_eq!(4, rows[1].line);
+        assert!(matches!(
+            rows[1].new_client,
+            Err(CommonError::InvalidField { ref field, .. }) if field == "birth_date"
+        ));
+        assert_eq!(5, rows[2].line);
+        assert!(matches!(
+            rows[2].new_client,
+            Err(CommonError::InvalidField { ref field, .. }) if field == "row"
+        ));
+    }
+
+    /// Scenario:
+    /// Parse a JSON Lines file with a valid row, a blank line and a row that is not JSON
+    /// Expectation:
+    /// Both rows should be returned with their file line
+    #[test]
+    fn when_parse_client_import_ndjson_should_keep_every_row_with_its_line() {
+        let content = "{\"client_name\":\"Jane Doe\",\"birth_date\":\"1990-05-01\",\"document_number\":\"123\",\"country\":\"AR\"}\n\
+                       \n\
+                       not json\n";
+
+        let rows = parse_client_import(content, ClientImportFormat::Ndjson);
+
+        assert_eq!(2, rows.len());
+        assert_eq!(1, rows[0].line);
+        assert!(rows[0].new_client.is_ok());
+        assert_eq!(3, rows[1].line);
+        assert!(rows[1].new_client.is_err());
+    }
+
+    /// Scenario:
+    /// Read the import format from names and file names
+    /// Expectation:
+    /// csv, ndjson and jsonl should be accepted ignoring case, anything else rejected
+    #[test]
+    fn when_parse_client_import_format_should_accept_csv_and_ndjson() {
+        assert_eq!(Ok(ClientImportFormat::Csv), "CSV".parse());
+        assert_eq!(Ok(ClientImportFormat::Ndjson), "jsonl".parse());
+        assert!("xml".parse::<ClientImportFormat>().is_err());
+        assert_eq!(
+            Some(ClientImportFormat::Ndjson),
+            ClientImportFormat::from_file_name("./clients.ndjson")
+        );
+        assert_eq!(None, ClientImportFormat::from_file_name("./clients"));
+    }
+}
@@ -0,0 +1,644 @@
+use crate::balance_file::balance_file_format::BalanceFileFormat;
+use crate::balance_file::balance_file_name_pattern::BalanceFileNamePattern;
+use crate::constants::constants::{
+    BALANCE_FILE_FORMAT_ENV, BALANCE_FOLDER_ENV, CLIENT_BALANCE_FOLDER, CONFIG_FILE_ENV,
+    DEFAULT_CONFIG_FILE, DEFAULT_HOST, DEFAULT_PORT, EVENT_LOG_PATH, EVENT_LOG_PATH_ENV,
+    FILE_NAME_PATTERN_ENV, HOST_ENV, LOG_LEVEL_ENV, MAX_TRANSACTION_AMOUNT_ENV,
+    OVERDRAFT_LIMIT_ENV, PORT_ENV, RESTORE_FROM_BALANCE_FILE_ENV, WORKERS_ENV,
+};
+use crate::errors::common_error::CommonError;
+use crate::model::overdraft_model::{OverdraftLimit, OverdraftLimits};
+use crate::validator::transaction_validator::TransactionLimits;
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Command line flags, they override the environment variables and the config file
+#[derive(Debug, Default, Parser)]
+#[command(name = "prex-core-challenge", about = "Mini payment processor")]
+pub struct CliArgs {
+    /// TOML config file
+    #[arg(long)]
+    pub config: Option<String>,
+    /// Address the server binds to
+    #[arg(long)]
+    pub host: Option<String>,
+    /// Port the server listens on
+    #[arg(long)]
+    pub port: Option<u16>,
+    /// Number of HTTP workers
+    #[arg(long)]
+    pub workers: Option<usize>,
+    /// Log level: off, error, warn, info, debug or trace
+    #[arg(long)]
+    pub log_level: Option<String>,
+    /// Folder for save the client's balances
+    #[arg(long)]
+    pub balance_folder: Option<String>,
+    /// Name of the balance files, with {date} and {counter}
+    #[arg(long)]
+    pub file_name_pattern: Option<String>,
+    /// Layout of the balance files: control or legacy
+    #[arg(long)]
+    pub balance_file_format: Option<String>,
+    /// Event log file
+    #[arg(long)]
+    pub event_log_path: Option<String>,
+    /// Load the most recent balance file as opening balances on startup
+    #[arg(long)]
+    pub restore_from_balance_file: bool,
+    /// Default overdraft limit: unlimited or the max negative balance
+    #[arg(long)]
+    pub overdraft_limit: Option<String>,
+    /// Max amount of a single transaction
+    #[arg(long)]
+    pub max_transaction_amount: Option<Decimal>,
+    /// Command run instead of starting the server
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Commands run on the restored state instead of starting the server
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the clients of a CSV or JSON Lines file, with the server stopped, and write the import report
+    ImportClients {
+        /// File with client_name, birth_date (YYYY-MM-DD), document_number and country
+        file: String,
+        /// csv or ndjson, read from the file extension when not informed
+        #[arg(long)]
+        format: Option<String>,
+        /// File the JSON report is written to, printed when not informed
+        #[arg(long)]
+        report: Option<String>,
+    },
+    /// Check a balance file against the movements recorded before it was written, with the server stopped,
+    /// and write the mismatch report; exits with an error if any balance does not match
+    ReconcileBalanceFile {
+        /// Balance file written by store_balance
+        file: String,
+        /// File the JSON report is written to, printed when not informed
+        #[arg(long)]
+        report: Option<String>,
+    },
+}
+
+/// Server configuration: config file, overridden by environment variables, overridden by command line flags
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    // address the server binds to
+    pub host: String,
+    // port the server listens on
+    pub port: u16,
+    // number of HTTP workers
+    pub workers: usize,
+    // log level
+    pub log_level: String,
+    // folder for save the client's balances
+    pub balance_folder: String,
+    // name of the balance files
+    pub file_name_pattern: String,
+    // layout of the balance files
+    pub balance_file_format: BalanceFileFormat,
+    // event log file
+    pub event_log_path: String,
+    // load the most recent balance file as opening balances on startup
+    pub restore_from_balance_file: bool,
+    // overdraft limit of the clients without their own limit
+    pub overdraft_limit: OverdraftLimit,
+    // overdraft limit of each client, by client id
+    pub client_overdraft_limits: HashMap<String, OverdraftLimit>,
+    // max amount of a single transaction
+    pub max_transaction_amount: Decimal,
+}
+
+/// Values used when nothing is configured
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            workers: std::thread::available_parallelism().map_or(1, |workers| workers.get()),
+            log_level: LevelFilter::Debug.to_string().to_lowercase(),
+            balance_folder: CLIENT_BALANCE_FOLDER.to_string(),
+            file_name_pattern: BalanceFileNamePattern::default().as_str().to_string(),
+            balance_file_format: BalanceFileFormat::default(),
+            event_log_path: EVENT_LOG_PATH.to_string(),
+            restore_from_balance_file: false,
+            overdraft_limit: OverdraftLimit::default(),
+            client_overdraft_limits: HashMap::new(),
+            max_transaction_amount: TransactionLimits::default().max_amount,
+        }
+    }
+}
+
+/// Configuration loading logic
+impl AppConfig {
+    /// Load the configuration from the process environment and [CliArgs] cli_args
+    /// The config file is `--config`, then `PREX_CONFIG`, then `./prex.toml` if it exists
+    /// Returns a [CommonError] if the config file cannot be read or any setting is invalid
+    pub fn load(cli_args: CliArgs) -> Result<Self, CommonError> {
+        Self::load_from(cli_args, |name| std::env::var(name).ok())
+    }
+
+    /// Load the configuration from [Fn] env_var and [CliArgs] cli_args
+    /// Returns a [CommonError] if the config file cannot be read or any setting is invalid
+    pub fn load_from(
+        cli_args: CliArgs,
+        env_var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, CommonError> {
+        let config_file = cli_args.config.clone().or_else(|| env_var(CONFIG_FILE_ENV));
+        let mut app_config = match config_file {
+            Some(config_file) => Self::from_file(&config_file)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(DEFAULT_CONFIG_FILE)?
+            }
+            None => AppConfig::default(),
+        };
+
+        app_config.apply_env(env_var)?;
+        app_config.apply_cli_args(cli_args)?;
+        app_config.validate()?;
+        Ok(app_config)
+    }
+
+    /// Read the TOML [str] config_file, missing settings keep their default value
+    /// Returns a [CommonError] if the file cannot be read or is not valid TOML
+    pub fn from_file(config_file: &str) -> Result<Self, CommonError> {
+        let content = std::fs::read_to_string(config_file)
+            .map_err(|_| CommonError::FileReadFailed(config_file.to_string()))?;
+        toml::from_str(&content).map_err(|error| CommonError::InvalidField {
+            field: "config".to_string(),
+            reason: format!("{config_file}: {}", error.message()),
+        })
+    }
+
+    /// Check every setting
+    /// Returns a [CommonError] naming the first invalid setting
+    pub fn validate(&self) -> Result<(), CommonError> {
+        if self.host.trim().is_empty() {
+            return Err(invalid_setting("host", "must not be empty"));
+        }
+        if self.port == 0 {
+            return Err(invalid_setting("port", "must be between 1 and 65535"));
+        }
+        if self.workers == 0 {
+            return Err(invalid_setting("workers", "must be greater than zero"));
+        }
+        self.level_filter()?;
+        if self.balance_folder.trim().is_empty() {
+            return Err(invalid_setting("balance_folder", "must not be empty"));
+        }
+        if Path::new(&self.balance_folder).is_file() {
+            return Err(invalid_setting("balance_folder", "is a file, not a folder"));
+        }
+        self.balance_file_name_pattern()?;
+        if self.event_log_path.trim().is_empty() {
+            return Err(invalid_setting("event_log_path", "must not be empty"));
+        }
+        self.overdraft_limits()?;
+        if self.max_transaction_amount <= Decimal::ZERO {
+            return Err(invalid_setting(
+                "max_transaction_amount",
+                "must be a positive amount",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Log level as [LevelFilter]
+    /// Returns a [CommonError] if the log level is unknown
+    pub fn level_filter(&self) -> Result<LevelFilter, CommonError> {
+        LevelFilter::from_str(&self.log_level).map_err(|_| {
+            invalid_setting(
+                "log_level",
+                "must be off, error, warn, info, debug or trace",
+            )
+        })
+    }
+
+    /// File name pattern as [BalanceFileNamePattern]
+    /// Returns a [CommonError] if the pattern is invalid
+    pub fn balance_file_name_pattern(&self) -> Result<BalanceFileNamePattern, CommonError> {
+        BalanceFileNamePattern::new(&self.file_name_pattern)
+    }
+
+    /// Default and per client overdraft limits as [OverdraftLimits]
+    /// Returns a [CommonError] if a client id is not a number
+    pub fn overdraft_limits(&self) -> Result<OverdraftLimits, CommonError> {
+        let client_limits = self
+            .client_overdraft_limits
+            .iter()
+            .map(|(client_id, limit)| {
+                client_id
+                    .parse::<i32>()
+                    .map(|client_id| (client_id, *limit))
+                    .map_err(|_| {
+                        invalid_setting("client_overdraft_limits", "keys must be client ids")
+                    })
+            })
+            .collect::<Result<HashMap<i32, OverdraftLimit>, CommonError>>()?;
+        Ok(OverdraftLimits {
+            default_limit: self.overdraft_limit,
+            client_limits,
+        })
+    }
+
+    /// Limits applied to every transaction amount as [TransactionLimits]
+    pub fn transaction_limits(&self) -> TransactionLimits {
+        TransactionLimits {
+            max_amount: self.max_transaction_amount,
+            ..Default::default()
+        }
+    }
+
+    /// Address the server binds to, (host, port)
+    pub fn bind_address(&self) -> (String, u16) {
+        (self.host.clone(), self.port)
+    }
+}
+
+/// Configuration loading "private" logic
+impl AppConfig {
+    /// Override the settings with the environment variables returned by [Fn] env_var
+    /// Returns a [CommonError] if a numeric variable is not a number or the balance file format is unknown
+    fn apply_env(&mut self, env_var: impl Fn(&str) -> Option<String>) -> Result<(), CommonError> {
+        if let Some(host) = env_var(HOST_ENV) {
+            self.host = host;
+        }
+        if let Some(port) = env_var(PORT_ENV) {
+            self.port = port
+                .parse()
+                .map_err(|_| invalid_setting(PORT_ENV, "must be between 1 and 65535"))?;
+        }
+        if let Some(workers) = env_var(WORKERS_ENV) {
+            self.workers = workers
+                .parse()
+                .map_err(|_| invalid_setting(WORKERS_ENV, "must be a positive number"))?;
+        }
+        if let Some(log_level) = env_var(LOG_LEVEL_ENV) {
+            self.log_level = log_level;
+        }
+        if let Some(balance_folder) = env_var(BALANCE_FOLDER_ENV) {
+            self.balance_folder = balance_folder;
+        }
+        if let Some(file_name_pattern) = env_var(FILE_NAME_PATTERN_ENV) {
+            self.file_name_pattern = file_name_pattern;
+        }
+        if let Some(balance_file_format) = env_var(BALANCE_FILE_FORMAT_ENV) {
+            self.balance_file_format = balance_file_format.parse().map_err(|_| {
+                invalid_setting(BALANCE_FILE_FORMAT_ENV, "must be legacy or control")
+            })?;
+        }
+        if let Some(event_log_path) = env_var(EVENT_LOG_PATH_ENV) {
+            self.event_log_path = event_log_path;
+        }
+        if let Some(restore_from_balance_file) = env_var(RESTORE_FROM_BALANCE_FILE_ENV) {
+            self.restore_from_balance_file = restore_from_balance_file.parse().map_err(|_| {
+                invalid_setting(RESTORE_FROM_BALANCE_FILE_ENV, "must be true or false")
+            })?;
+        }
+        if let Some(overdraft_limit) = env_var(OVERDRAFT_LIMIT_ENV) {
+            self.overdraft_limit = overdraft_limit.parse().map_err(|_| {
+                invalid_setting(
+                    OVERDRAFT_LIMIT_ENV,
+                    "must be unlimited or a non negative amount",
+                )
+            })?;
+        }
+        if let Some(max_transaction_amount) = env_var(MAX_TRANSACTION_AMOUNT_ENV) {
+            self.max_transaction_amount = max_transaction_amount.parse().map_err(|_| {
+                invalid_setting(MAX_TRANSACTION_AMOUNT_ENV, "must be a positive amount")
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Override the settings with the informed [CliArgs] cli_args
+    /// Returns a [CommonError] if the balance file format or the overdraft limit is invalid
+    fn apply_cli_args(&mut self, cli_args: CliArgs) -> Result<(), CommonError> {
+        if let Some(host) = cli_args.host {
+            self.host = host;
+        }
+        if let Some(port) = cli_args.port {
+            self.port = port;
+        }
+        if let Some(workers) = cli_args.workers {
+            self.workers = workers;
+        }
+        if let Some(log_level) = cli_args.log_level {
+            self.log_level = log_level;
+        }
+        if let Some(balance_folder) = cli_args.balance_folder {
+            self.balance_folder = balance_folder;
+        }
+        if let Some(file_name_pattern) = cli_args.file_name_pattern {
+            self.file_name_pattern = file_name_pattern;
+        }
+        if let Some(balance_file_format) = cli_args.balance_file_format {
+            self.balance_file_format = balance_file_format.parse()?;
+        }
+        if let Some(event_log_path) = cli_args.event_log_path {
+            self.event_log_path = event_log_path;
+        }
+        if cli_args.restore_from_balance_file {
+            self.restore_from_balance_file = true;
+        }
+        if let Some(overdraft_limit) = cli_args.overdraft_limit {
+            self.overdraft_limit = overdraft_limit.parse()?;
+        }
+        if let Some(max_transaction_amount) = cli_args.max_transaction_amount {
+            self.max_transaction_amount = max_transaction_amount;
+        }
+        Ok(())
+    }
+}
+
+/// Build the [CommonError] of an invalid [str] setting
+fn invalid_setting(setting: &str, reason: &str) -> CommonError {
+    CommonError::InvalidField {
+        field: setting.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Write [str] content in a temporary TOML file
+    fn create_config_file(content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("prex-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+        path.display().to_string()
+    }
+
+    /// Scenario:
+    /// Execute load_from with a config file, environment variables and command line flags
+    /// Expectation:
+    /// Flags should win over variables, variables over the file and the file over the defaults
+    #[test]
+    fn when_load_from_with_every_source_should_apply_them_in_order() {
+        let config_file = create_config_file(
+            "host = \"0.0.0.0\"\nport = 9000\nworkers = 2\nbalance_folder = \"/shared/balances\"\n",
+        );
+        let env_vars = HashMap::from([
+            (CONFIG_FILE_ENV, config_file.clone()),
+            (PORT_ENV, "9100".to_string()),
+            (LOG_LEVEL_ENV, "info".to_string()),
+        ]);
+        let cli_args = CliArgs::parse_from(["prex-core-challenge", "--port", "9200"]);
+
+        let expected_result = AppConfig::load_from(cli_args, |name| env_vars.get(name).cloned());
+        let _ = std::fs::remove_file(&config_file);
+
+        let app_config = expected_result.unwrap();
+        assert_eq!("0.0.0.0", app_config.host);
+        assert_eq!(9200, app_config.port);
+        assert_eq!(2, app_config.workers);
+        assert_eq!("info", app_config.log_level);
+        assert_eq!("/shared/balances", app_config.balance_folder);
+        assert_eq!("{date}_{counter}.DAT", app_config.file_name_pattern);
+        assert!(!app_config.restore_from_balance_file);
+    }
+
+    /// Scenario:
+    /// Execute load_from with invalid settings
+    /// Expectation:
+    /// A [CommonError] naming the invalid setting should be returned
+    #[test]
+    fn when_load_from_with_invalid_settings_should_return_common_error() {
+        let no_env = |_: &str| None;
+
+        let invalid_port = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--port", "0"]),
+            no_env,
+        );
+        let invalid_log_level = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--log-level", "verbose"]),
+            no_env,
+        );
+        let invalid_workers = AppConfig::load_from(CliArgs::default(), |name| {
+            (name == WORKERS_ENV).then(|| "many".to_string())
+        });
+
+        assert!(
+            matches!(invalid_port, Err(CommonError::InvalidField { field, .. }) if field == "port")
+        );
+        assert!(
+            matches!(invalid_log_level, Err(CommonError::InvalidField { field, .. }) if field == "log_level")
+        );
+        assert!(
+            matches!(invalid_workers, Err(CommonError::InvalidField { field, .. }) if field == WORKERS_ENV)
+        );
+    }
+
+    /// Scenario:
+    /// Execute from_file with an unknown setting
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[test]
+    fn when_from_file_with_unknown_setting_should_return_common_error() {
+        let config_file = create_config_file("prot = 9000\n");
+
+        let expected_result = AppConfig::from_file(&config_file);
+        let _ = std::fs::remove_file(&config_file);
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, reason }) if field == "config" && reason.contains("prot")
+        ));
+    }
+
+    /// Scenario:
+    /// Execute load_from with a default and per client overdraft limits in the config file and the flags
+    /// Expectation:
+    /// The flag should override the default limit and the client limits should be read by client id
+    #[test]
+    fn when_load_from_with_overdraft_limits_should_read_default_and_client_limits() {
+        let config_file = create_config_file(
+            "overdraft_limit = \"0\"\n[client_overdraft_limits]\n7 = \"unlimited\"\n8 = \"25.50\"\n",
+        );
+        let cli_args = CliArgs::parse_from([
+            "prex-core-challenge",
+            "--config",
+            &config_file,
+            "--overdraft-limit",
+            "10",
+        ]);
+
+        let expected_result = AppConfig::load_from(cli_args, |_| None);
+        let _ = std::fs::remove_file(&config_file);
+
+        let overdraft_limits = expected_result.unwrap().overdraft_limits().unwrap();
+        assert_eq!(
+            OverdraftLimit::Limited(rust_decimal::Decimal::TEN),
+            overdraft_limits.limit_of(1)
+        );
+        assert_eq!(OverdraftLimit::Unlimited, overdraft_limits.limit_of(7));
+        assert_eq!(
+            OverdraftLimit::Limited(rust_decimal::Decimal::new(2550, 2)),
+            overdraft_limits.limit_of(8)
+        );
+    }
+
+    /// Scenario:
+    /// Execute load_from with a negative overdraft limit and with a client limit not keyed by client id
+    /// Expectation:
+    /// A [CommonError] naming the invalid setting should be returned
+    #[test]
+    fn when_load_from_with_invalid_overdraft_limits_should_return_common_error() {
+        let config_file = create_config_file("[client_overdraft_limits]\nvip = \"unlimited\"\n");
+
+        let invalid_client_limit = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--config", &config_file]),
+            |_| None,
+        );
+        let _ = std::fs::remove_file(&config_file);
+        let invalid_default_limit = AppConfig::load_from(CliArgs::default(), |name| {
+            (name == OVERDRAFT_LIMIT_ENV).then(|| "-5".to_string())
+        });
+
+        assert!(
+            matches!(invalid_client_limit, Err(CommonError::InvalidField { field, .. }) if field == "client_overdraft_limits")
+        );
+        assert!(
+            matches!(invalid_default_limit, Err(CommonError::InvalidField { field, .. }) if field == OVERDRAFT_LIMIT_ENV)
+        );
+    }
+
+    /// Scenario:
+    /// Execute load_from with the balance file format and the max transaction amount in every source
+    /// Expectation:
+    /// The flags should override the variables and the variables the config file
+    #[test]
+    fn when_load_from_with_balance_file_format_and_max_transaction_amount_should_apply_them() {
+        let config_file = create_config_file(
+            "balance_file_format = \"legacy\"\nmax_transaction_amount = \"500\"\n",
+        );
+        let from_file = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--config", &config_file]),
+            |_| None,
+        );
+        let from_env = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--config", &config_file]),
+            |name| match name {
+                BALANCE_FILE_FORMAT_ENV => Some("control".to_string()),
+                MAX_TRANSACTION_AMOUNT_ENV => Some("750.50".to_string()),
+                _ => None,
+            },
+        );
+        let from_flags = AppConfig::load_from(
+            CliArgs::parse_from([
+                "prex-core-challenge",
+                "--config",
+                &config_file,
+                "--balance-file-format",
+                "control",
+                "--max-transaction-amount",
+                "1000",
+            ]),
+            |_| None,
+        );
+        let _ = std::fs::remove_file(&config_file);
+
+        let from_file = from_file.unwrap();
+        assert_eq!(BalanceFileFormat::Legacy, from_file.balance_file_format);
+        assert_eq!(
+            Decimal::new(500, 0),
+            from_file.transaction_limits().max_amount
+        );
+        let from_env = from_env.unwrap();
+        assert_eq!(BalanceFileFormat::Control, from_env.balance_file_format);
+        assert_eq!(Decimal::new(75050, 2), from_env.max_transaction_amount);
+        let from_flags = from_flags.unwrap();
+        assert_eq!(BalanceFileFormat::Control, from_flags.balance_file_format);
+        assert_eq!(Decimal::new(1000, 0), from_flags.max_transaction_amount);
+    }
+
+    /// Scenario:
+    /// Execute load_from with an unknown balance file format and with a max transaction amount that is not positive
+    /// Expectation:
+    /// A [CommonError] naming the invalid setting should be returned
+    #[test]
+    fn when_load_from_with_invalid_balance_file_format_or_max_transaction_amount_should_return_common_error(
+    ) {
+        let invalid_format_env = AppConfig::load_from(CliArgs::default(), |name| {
+            (name == BALANCE_FILE_FORMAT_ENV).then(|| "csv".to_string())
+        });
+        let invalid_format_flag = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--balance-file-format", "csv"]),
+            |_| None,
+        );
+        let invalid_amount_env = AppConfig::load_from(CliArgs::default(), |name| {
+            (name == MAX_TRANSACTION_AMOUNT_ENV).then(|| "a lot".to_string())
+        });
+        let invalid_amount_flag = AppConfig::load_from(
+            CliArgs::parse_from(["prex-core-challenge", "--max-transaction-amount", "0"]),
+            |_| None,
+        );
+
+        assert!(
+            matches!(invalid_format_env, Err(CommonError::InvalidField { field, .. }) if field == BALANCE_FILE_FORMAT_ENV)
+        );
+        assert!(
+            matches!(invalid_format_flag, Err(CommonError::InvalidField { field, .. }) if field == "balance_file_format")
+        );
+        assert!(
+            matches!(invalid_amount_env, Err(CommonError::InvalidField { field, .. }) if field == MAX_TRANSACTION_AMOUNT_ENV)
+        );
+        assert!(
+            matches!(invalid_amount_flag, Err(CommonError::InvalidField { field, .. }) if field == "max_transaction_amount")
+        );
+    }
+
+    /// Scenario:
+    /// Parse the command line of the import-clients command
+    /// Expectation:
+    /// The file, the format and the report should be read and the flags still apply to the configuration
+    #[test]
+    fn when_parse_import_clients_command_should_read_its_arguments() {
+        let cli_args = CliArgs::parse_from([
+            "prex-core-challenge",
+            "--event-log-path",
+            "./events.jsonl",
+            "import-clients",
+            "./clients.csv",
+            "--report",
+            "./report.json",
+        ]);
+
+        assert_eq!(Some("./events.jsonl".to_string()), cli_args.event_log_path);
+        assert!(matches!(
+            cli_args.command,
+            Some(Command::ImportClients { ref file, format: None, report: Some(ref report) })
+                if file == "./clients.csv" && report == "./report.json"
+        ));
+    }
+
+    /// Scenario:
+    /// Parse the command line of the reconcile-balance-file command
+    /// Expectation:
+    /// The balance file should be read and the report left to be printed
+    #[test]
+    fn when_parse_reconcile_balance_file_command_should_read_its_arguments() {
+        let cli_args = CliArgs::parse_from([
+            "prex-core-challenge",
+            "reconcile-balance-file",
+            "./store_balances/01122023_1.DAT",
+        ]);
+
+        assert!(matches!(
+            cli_args.command,
+            Some(Command::ReconcileBalanceFile { ref file, report: None })
+                if file == "./store_balances/01122023_1.DAT"
+        ));
+    }
+}
@@ -1,22 +1,126 @@
 /// Main Path
 pub const MAIN_PATH: &str = "/api/v1/client";
+/// Client Listing Path
+pub const CLIENTS_PATH: &str = "/api/v1/clients";
 
 /// New Client Path
 pub const NEW_CLIENT_PATH: &str = "/new_client";
+/// Client Import Path
+pub const CLIENT_IMPORT_PATH: &str = "/import_clients";
 /// New Debit Path
 pub const NEW_DEBIT_TRANSACTION_PATH: &str = "/new_debit_transaction";
 /// New Credit Path
 pub const NEW_CREDIT_TRANSACTION_PATH: &str = "/new_credit_transaction";
+/// New Transaction Batch Path
+pub const NEW_TRANSACTION_BATCH_PATH: &str = "/new_transaction_batch";
+/// New Hold Path
+pub const NEW_HOLD_PATH: &str = "/new_hold";
+/// Capture Hold Path
+pub const CAPTURE_HOLD_PATH: &str = "/hold/{hold_id}/capture";
+/// Release Hold Path
+pub const RELEASE_HOLD_PATH: &str = "/hold/{hold_id}/release";
+/// Reverse Transaction Path
+pub const TRANSACTION_REVERSAL_PATH: &str = "/transaction/{transaction_id}/reversal";
+/// New Transfer Path
+pub const TRANSFER_PATH: &str = "/transfer";
 /// Store Balance Path
 pub const STORE_BALANCE_PATH: &str = "/store_balance";
+/// Reconcile Balance File Path
+pub const BALANCE_FILE_RECONCILIATION_PATH: &str = "/balance_file/{file_name}/reconciliation";
 /// Get Client Balance
 pub const CLIENT_BALANCE_PATH: &str = "/client_balance/{id}";
+/// Get Client Transactions
+pub const CLIENT_TRANSACTIONS_PATH: &str = "/{id}/transactions";
+/// Get Client Statement
+pub const CLIENT_STATEMENT_PATH: &str = "/{id}/statement";
+/// Get Client By Document Number
+pub const CLIENT_BY_DOCUMENT_PATH: &str = "/by_document/{document_number}";
+/// Block Client Account
+pub const BLOCK_CLIENT_PATH: &str = "/{id}/block";
+/// Unblock Client Account
+pub const UNBLOCK_CLIENT_PATH: &str = "/{id}/unblock";
+/// Close Client Account
+pub const CLOSE_CLIENT_PATH: &str = "/{id}/close";
+/// Update Client Profile
+pub const CLIENT_PATH: &str = "/{id}";
 
 /// Folder for save the client's balances
 pub const CLIENT_BALANCE_FOLDER: &str = "./store_balances";
 
 /// Balance file prefix
 pub const PREFIX_FILE: &str = ".DAT";
+/// Environment variable with the balance file format, `control` (default) or `legacy`
+pub const BALANCE_FILE_FORMAT_ENV: &str = "PREX_BALANCE_FILE_FORMAT";
+/// Suffix of a balance file while it is being written
+pub const TEMPORARY_FILE_SUFFIX: &str = ".tmp";
+
+/// Transactions per page when the page size is not informed
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+/// Max transactions per page
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Max decimal places of a transaction amount
+pub const MAX_AMOUNT_SCALE: u32 = 2;
+/// Max amount of a single transaction
+pub const MAX_TRANSACTION_AMOUNT: i64 = 1_000_000;
+/// Environment variable overriding the max amount of a single transaction
+pub const MAX_TRANSACTION_AMOUNT_ENV: &str = "PREX_MAX_TRANSACTION_AMOUNT";
+
+/// Max credits and debits of a single batch
+pub const MAX_BATCH_ITEMS: usize = 10_000;
+
+/// Max size of a client import file
+pub const MAX_IMPORT_FILE_BYTES: usize = 16 * 1024 * 1024;
+/// Name of the downloaded client import report
+pub const CLIENT_IMPORT_REPORT_FILE_NAME: &str = "client_import_report.json";
+
+/// Header used to deduplicate retried transactions
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Seconds an idempotency key is remembered
+pub const IDEMPOTENCY_KEY_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+/// Seconds until a hold is released when its expiry is not informed
+pub const DEFAULT_HOLD_EXPIRY_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Max seconds until a hold is released
+pub const MAX_HOLD_EXPIRY_SECONDS: i64 = 30 * 24 * 60 * 60;
+/// Seconds between two runs of the task releasing the expired holds
+pub const HOLD_EXPIRY_INTERVAL_SECONDS: u64 = 60;
+
+/// Profile version of a new client
+pub const INITIAL_CLIENT_VERSION: u64 = 1;
+
+/// Event log with every change applied by the client service
+pub const EVENT_LOG_PATH: &str = "./event_log/events.jsonl";
+
+/// Config file read when `--config` and [CONFIG_FILE_ENV] are not informed
+pub const DEFAULT_CONFIG_FILE: &str = "./prex.toml";
+/// Address the server binds to by default
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+/// Port the server listens on by default
+pub const DEFAULT_PORT: u16 = 8080;
+/// Environment variable with the config file
+pub const CONFIG_FILE_ENV: &str = "PREX_CONFIG";
+/// Environment variable overriding the host
+pub const HOST_ENV: &str = "PREX_HOST";
+/// Environment variable overriding the port
+pub const PORT_ENV: &str = "PREX_PORT";
+/// Environment variable overriding the number of HTTP workers
+pub const WORKERS_ENV: &str = "PREX_WORKERS";
+/// Environment variable overriding the log level
+pub const LOG_LEVEL_ENV: &str = "PREX_LOG_LEVEL";
+/// Environment variable overriding the balance folder
+pub const BALANCE_FOLDER_ENV: &str = "PREX_BALANCE_FOLDER";
+/// Environment variable overriding the balance file name pattern
+pub const FILE_NAME_PATTERN_ENV: &str = "PREX_FILE_NAME_PATTERN";
+/// Environment variable enabling the restore of the most recent balance file on startup
+pub const RESTORE_FROM_BALANCE_FILE_ENV: &str = "PREX_RESTORE_FROM_BALANCE_FILE";
+/// Environment variable overriding the event log file
+pub const EVENT_LOG_PATH_ENV: &str = "PREX_EVENT_LOG_PATH";
+/// Environment variable overriding the default overdraft limit, `unlimited` or an amount
+pub const OVERDRAFT_LIMIT_ENV: &str = "PREX_OVERDRAFT_LIMIT";
+
+/// Environment variable with the SQLite database path, only read with the `sqlite` feature
+pub const SQLITE_PATH_ENV: &str = "PREX_SQLITE_PATH";
 
 /// Unit tests cases
 #[cfg(test)]
@@ -28,12 +132,68 @@ mod tests {
     #[test]
     fn test_constants() {
         assert_eq!("/api/v1/client", MAIN_PATH);
+        assert_eq!("/api/v1/clients", CLIENTS_PATH);
         assert_eq!("/new_client", NEW_CLIENT_PATH);
         assert_eq!("/new_debit_transaction", NEW_DEBIT_TRANSACTION_PATH);
         assert_eq!("/new_credit_transaction", NEW_CREDIT_TRANSACTION_PATH);
+        assert_eq!("/new_transaction_batch", NEW_TRANSACTION_BATCH_PATH);
+        assert_eq!("/import_clients", CLIENT_IMPORT_PATH);
+        assert_eq!("/new_hold", NEW_HOLD_PATH);
+        assert_eq!("/hold/{hold_id}/capture", CAPTURE_HOLD_PATH);
+        assert_eq!("/hold/{hold_id}/release", RELEASE_HOLD_PATH);
+        assert_eq!(
+            "/transaction/{transaction_id}/reversal",
+            TRANSACTION_REVERSAL_PATH
+        );
+        assert_eq!("/transfer", TRANSFER_PATH);
         assert_eq!("/store_balance", STORE_BALANCE_PATH);
+        assert_eq!(
+            "/balance_file/{file_name}/reconciliation",
+            BALANCE_FILE_RECONCILIATION_PATH
+        );
         assert_eq!("/client_balance/{id}", CLIENT_BALANCE_PATH);
+        assert_eq!("/{id}/transactions", CLIENT_TRANSACTIONS_PATH);
+        assert_eq!("/{id}/statement", CLIENT_STATEMENT_PATH);
+        assert_eq!("/by_document/{document_number}", CLIENT_BY_DOCUMENT_PATH);
+        assert_eq!("/{id}/block", BLOCK_CLIENT_PATH);
+        assert_eq!("/{id}/unblock", UNBLOCK_CLIENT_PATH);
+        assert_eq!("/{id}/close", CLOSE_CLIENT_PATH);
+        assert_eq!("/{id}", CLIENT_PATH);
         assert_eq!("./store_balances", CLIENT_BALANCE_FOLDER);
         assert_eq!(".DAT", PREFIX_FILE);
+        assert_eq!(".tmp", TEMPORARY_FILE_SUFFIX);
+        assert_eq!("PREX_BALANCE_FILE_FORMAT", BALANCE_FILE_FORMAT_ENV);
+        assert_eq!(20, DEFAULT_PAGE_SIZE);
+        assert_eq!(100, MAX_PAGE_SIZE);
+        assert_eq!(2, MAX_AMOUNT_SCALE);
+        assert_eq!(1_000_000, MAX_TRANSACTION_AMOUNT);
+        assert_eq!("PREX_MAX_TRANSACTION_AMOUNT", MAX_TRANSACTION_AMOUNT_ENV);
+        assert_eq!(10_000, MAX_BATCH_ITEMS);
+        assert_eq!(16_777_216, MAX_IMPORT_FILE_BYTES);
+        assert_eq!("client_import_report.json", CLIENT_IMPORT_REPORT_FILE_NAME);
+        assert_eq!("Idempotency-Key", IDEMPOTENCY_KEY_HEADER);
+        assert_eq!(86400, IDEMPOTENCY_KEY_TTL_SECONDS);
+        assert_eq!(604800, DEFAULT_HOLD_EXPIRY_SECONDS);
+        assert_eq!(2592000, MAX_HOLD_EXPIRY_SECONDS);
+        assert_eq!(60, HOLD_EXPIRY_INTERVAL_SECONDS);
+        assert_eq!(1, INITIAL_CLIENT_VERSION);
+        assert_eq!("./event_log/events.jsonl", EVENT_LOG_PATH);
+        assert_eq!("./prex.toml", DEFAULT_CONFIG_FILE);
+        assert_eq!("127.0.0.1", DEFAULT_HOST);
+        assert_eq!(8080, DEFAULT_PORT);
+        assert_eq!("PREX_CONFIG", CONFIG_FILE_ENV);
+        assert_eq!("PREX_HOST", HOST_ENV);
+        assert_eq!("PREX_PORT", PORT_ENV);
+        assert_eq!("PREX_WORKERS", WORKERS_ENV);
+        assert_eq!("PREX_LOG_LEVEL", LOG_LEVEL_ENV);
+        assert_eq!("PREX_BALANCE_FOLDER", BALANCE_FOLDER_ENV);
+        assert_eq!("PREX_FILE_NAME_PATTERN", FILE_NAME_PATTERN_ENV);
+        assert_eq!("PREX_EVENT_LOG_PATH", EVENT_LOG_PATH_ENV);
+        assert_eq!(
+            "PREX_RESTORE_FROM_BALANCE_FILE",
+            RESTORE_FROM_BALANCE_FILE_ENV
+        );
+        assert_eq!("PREX_OVERDRAFT_LIMIT", OVERDRAFT_LIMIT_ENV);
+        assert_eq!("PREX_SQLITE_PATH", SQLITE_PATH_ENV);
     }
 }
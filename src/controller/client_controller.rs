@@ -1,18 +1,37 @@
 use crate::constants::constants::{
-    CLIENT_BALANCE_PATH, MAIN_PATH, NEW_CLIENT_PATH, NEW_CREDIT_TRANSACTION_PATH,
-    NEW_DEBIT_TRANSACTION_PATH, STORE_BALANCE_PATH,
+    BALANCE_FILE_RECONCILIATION_PATH, BLOCK_CLIENT_PATH, CAPTURE_HOLD_PATH, CLIENTS_PATH,
+    CLIENT_BALANCE_PATH, CLIENT_BY_DOCUMENT_PATH, CLIENT_IMPORT_PATH,
+    CLIENT_IMPORT_REPORT_FILE_NAME, CLIENT_PATH, CLIENT_STATEMENT_PATH, CLIENT_TRANSACTIONS_PATH,
+    CLOSE_CLIENT_PATH, IDEMPOTENCY_KEY_HEADER, MAIN_PATH, MAX_IMPORT_FILE_BYTES, NEW_CLIENT_PATH,
+    NEW_CREDIT_TRANSACTION_PATH, NEW_DEBIT_TRANSACTION_PATH, NEW_HOLD_PATH,
+    NEW_TRANSACTION_BATCH_PATH, RELEASE_HOLD_PATH, STORE_BALANCE_PATH, TRANSACTION_REVERSAL_PATH,
+    TRANSFER_PATH, UNBLOCK_CLIENT_PATH,
 };
+use crate::dto::capture_hold_dto::CaptureHold;
+use crate::dto::client_filter_dto::ClientFilter;
+use crate::dto::client_import_options_dto::ClientImportOptions;
+use crate::dto::close_client_dto::CloseClient;
 use crate::dto::new_client_dto::NewClient;
 use crate::dto::new_credit_transaction::NewCreditTransaction;
 use crate::dto::new_debit_transaction::NewDebitTransaction;
+use crate::dto::new_hold_dto::NewHold;
+use crate::dto::new_transfer_dto::NewTransfer;
+use crate::dto::reverse_transaction_dto::ReverseTransaction;
+use crate::dto::statement_filter_dto::StatementFilter;
+use crate::dto::transaction_batch_dto::NewTransactionBatch;
+use crate::dto::transaction_filter_dto::TransactionFilter;
+use crate::dto::update_client_dto::UpdateClient;
 use crate::errors::common_error::CommonError;
 use crate::service::client_service::DynClientService;
+use crate::statement::statement_format::{render_statement_csv, StatementFormat};
 
-use actix_web::{self, web, HttpResponse, Responder, Scope};
+use actix_web::http::header;
+use actix_web::{self, web, HttpRequest, HttpResponse, Scope};
 
 /// Client controller
 #[derive(Clone)]
 pub struct ClientController {
+    #[allow(dead_code)]
     client_service: DynClientService,
 }
 
@@ -26,7 +45,16 @@ impl ClientController {
     /// Configure declared endpoints for this controller
     pub fn create_routes(&self) -> Scope {
         web::scope(MAIN_PATH)
+            .app_data(json_config())
+            .app_data(path_config())
+            .app_data(query_config())
             .route(NEW_CLIENT_PATH, web::post().to(map_create_new_client))
+            // import files are bigger than the default payload limit
+            .service(
+                web::resource(CLIENT_IMPORT_PATH)
+                    .app_data(web::PayloadConfig::new(MAX_IMPORT_FILE_BYTES))
+                    .route(web::post().to(map_import_clients)),
+            )
             .route(
                 NEW_CREDIT_TRANSACTION_PATH,
                 web::post().to(map_create_new_credit_transaction),
@@ -35,119 +63,429 @@ impl ClientController {
                 NEW_DEBIT_TRANSACTION_PATH,
                 web::post().to(map_create_new_debit_transaction),
             )
+            .route(
+                NEW_TRANSACTION_BATCH_PATH,
+                web::post().to(map_create_transaction_batch),
+            )
+            .route(
+                TRANSACTION_REVERSAL_PATH,
+                web::post().to(map_reverse_transaction),
+            )
+            .route(NEW_HOLD_PATH, web::post().to(map_create_hold))
+            .route(CAPTURE_HOLD_PATH, web::post().to(map_capture_hold))
+            .route(RELEASE_HOLD_PATH, web::post().to(map_release_hold))
+            .route(TRANSFER_PATH, web::post().to(map_create_transfer))
             .route(STORE_BALANCE_PATH, web::post().to(map_create_balance_files))
+            .route(
+                BALANCE_FILE_RECONCILIATION_PATH,
+                web::get().to(map_reconcile_balance_file),
+            )
             .route(CLIENT_BALANCE_PATH, web::get().to(map_get_client_balance))
+            // before the transactions path, `/by_document/transactions` would match `/{id}/transactions`
+            .route(
+                CLIENT_BY_DOCUMENT_PATH,
+                web::get().to(map_get_client_by_document),
+            )
+            .route(
+                CLIENT_TRANSACTIONS_PATH,
+                web::get().to(map_get_client_transactions),
+            )
+            .route(
+                CLIENT_STATEMENT_PATH,
+                web::get().to(map_get_client_statement),
+            )
+            .route(BLOCK_CLIENT_PATH, web::post().to(map_block_client))
+            .route(UNBLOCK_CLIENT_PATH, web::post().to(map_unblock_client))
+            .route(CLOSE_CLIENT_PATH, web::post().to(map_close_client))
+            // last, `/{id}` would also match the paths above
+            .route(CLIENT_PATH, web::patch().to(map_update_client))
+    }
+    /// Configure the client listing endpoint, outside of the [MAIN_PATH] scope
+    pub fn create_clients_routes(&self) -> Scope {
+        web::scope(CLIENTS_PATH)
+            .app_data(query_config())
+            .route("", web::get().to(map_list_clients))
     }
 }
 
+/// Invalid JSON bodies are answered with the same error envelope as [CommonError]
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|error, _request| {
+        CommonError::InvalidField {
+            field: "body".to_string(),
+            reason: error.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Invalid path params are answered with the same error envelope as [CommonError]
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|error, _request| {
+        CommonError::InvalidField {
+            field: "path".to_string(),
+            reason: error.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Invalid query params are answered with the same error envelope as [CommonError]
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|error, _request| {
+        CommonError::InvalidField {
+            field: "query".to_string(),
+            reason: error.to_string(),
+        }
+        .into()
+    })
+}
+
 /// Maps new client end-point
 pub async fn map_create_new_client(
     service: web::Data<DynClientService>,
     new_client: web::Json<NewClient>,
-) -> impl Responder {
-    match service.create_new_client(new_client.into_inner()).await {
-        Ok(id) => HttpResponse::Ok().json(id),
-        Err(error) => match error {
-            CommonError::Forbiden => {
-                HttpResponse::Forbidden().body("The document number already exists")
-            }
-            _ => HttpResponse::InternalServerError()
-                .body("Error creating new client. Try again later."),
-        },
-    }
+) -> Result<HttpResponse, CommonError> {
+    let client_id = service.create_new_client(new_client.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(client_id))
+}
+
+/// Maps client import end-point, the report is sent as a downloadable file
+pub async fn map_import_clients(
+    service: web::Data<DynClientService>,
+    options: web::Query<ClientImportOptions>,
+    content: web::Bytes,
+) -> Result<HttpResponse, CommonError> {
+    let content =
+        String::from_utf8(content.to_vec()).map_err(|error| CommonError::InvalidField {
+            field: "body".to_string(),
+            reason: error.to_string(),
+        })?;
+    let report = service
+        .import_clients(content, options.format.unwrap_or_default())
+        .await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{CLIENT_IMPORT_REPORT_FILE_NAME}\""),
+        ))
+        .json(report))
 }
 
 /// Maps new credit transaction end-point
 pub async fn map_create_new_credit_transaction(
     service: web::Data<DynClientService>,
+    request: HttpRequest,
     new_credit: web::Json<NewCreditTransaction>,
-) -> impl Responder {
-    match service
-        .create_new_credit_transaction(new_credit.into_inner())
-        .await
-    {
-        Ok(balance) => HttpResponse::Ok().json(balance),
-        Err(error) => match error {
-            CommonError::NotFound => HttpResponse::NotFound().body("Client not found"),
-            _ => HttpResponse::InternalServerError()
-                .body("Error creating new client. Try again later."),
-        },
+) -> Result<HttpResponse, CommonError> {
+    let mut new_credit = new_credit.into_inner();
+    if let Some(idempotency_key) = read_idempotency_key(&request) {
+        new_credit.idempotency_key = Some(idempotency_key);
     }
+
+    let balance = service.create_new_credit_transaction(new_credit).await?;
+    Ok(HttpResponse::Ok().json(balance))
 }
 /// Maps new debit transaction end-point
 pub async fn map_create_new_debit_transaction(
     service: web::Data<DynClientService>,
+    request: HttpRequest,
     new_debit: web::Json<NewDebitTransaction>,
-) -> impl Responder {
-    match service
-        .create_new_debit_transaction(new_debit.into_inner())
-        .await
-    {
-        Ok(balance) => HttpResponse::Ok().json(balance),
-        Err(error) => match error {
-            CommonError::NotFound => HttpResponse::NotFound().body("Client not found"),
-            _ => HttpResponse::InternalServerError()
-                .body("Error creating new client. Try again later."),
-        },
+) -> Result<HttpResponse, CommonError> {
+    let mut new_debit = new_debit.into_inner();
+    if let Some(idempotency_key) = read_idempotency_key(&request) {
+        new_debit.idempotency_key = Some(idempotency_key);
     }
+
+    let balance = service.create_new_debit_transaction(new_debit).await?;
+    Ok(HttpResponse::Ok().json(balance))
+}
+/// Maps new transfer end-point
+pub async fn map_create_transfer(
+    service: web::Data<DynClientService>,
+    new_transfer: web::Json<NewTransfer>,
+) -> Result<HttpResponse, CommonError> {
+    let transfer_info = service.create_transfer(new_transfer.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(transfer_info))
 }
 /// Maps create balance files
-pub async fn map_create_balance_files(service: web::Data<DynClientService>) -> impl Responder {
-    match service.generate_file_with_all_clients_balances().await {
-        Ok(_) => HttpResponse::Ok().body("File created successfully"),
-        Err(error) => {
-            let message = match error {
-                CommonError::FolderCreationFailed => "Error when creating folder",
-                CommonError::FolderReadFailed => "Error when reading folder",
-                CommonError::LockReadFailed => "Error when reading app_state",
-                CommonError::LockWriteFailed => "Error when writing app_state",
-                CommonError::FileCreationFailed => "Error when creating file",
-                CommonError::FileWriteFailed => "Error when writing to the file",
-                _ => "An unexpected error occurred",
-            };
-            HttpResponse::InternalServerError().body(message)
-        }
-    }
+pub async fn map_create_balance_files(
+    service: web::Data<DynClientService>,
+) -> Result<HttpResponse, CommonError> {
+    service.generate_file_with_all_clients_balances().await?;
+    Ok(HttpResponse::Ok().body("File created successfully"))
+}
+/// Maps balance file reconciliation end-point
+pub async fn map_reconcile_balance_file(
+    service: web::Data<DynClientService>,
+    file_name: web::Path<String>,
+) -> Result<HttpResponse, CommonError> {
+    let report = service
+        .reconcile_balance_file(file_name.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(report))
 }
 /// Maps get client balance end-point
 pub async fn map_get_client_balance(
     service: web::Data<DynClientService>,
     client_id: web::Path<i32>,
-) -> impl Responder {
-    match service.get_client_balance(client_id.into_inner()).await {
-        Ok(client_info) => HttpResponse::Ok().json(client_info),
-        Err(error) => match error {
-            CommonError::NotFound => HttpResponse::NotFound().body("Client not found"),
-            _ => HttpResponse::InternalServerError().body("An unexpected error occurred"),
-        },
+) -> Result<HttpResponse, CommonError> {
+    let client_info = service.get_client_balance(client_id.into_inner()).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, entity_tag(client_info.version)))
+        .json(client_info))
+}
+
+/// Maps get client by document number end-point
+pub async fn map_get_client_by_document(
+    service: web::Data<DynClientService>,
+    document_number: web::Path<String>,
+) -> Result<HttpResponse, CommonError> {
+    let client_info = service
+        .get_client_by_document(document_number.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, entity_tag(client_info.version)))
+        .json(client_info))
+}
+
+/// Maps update client end-point, the `If-Match` header must have the ETag of the client
+pub async fn map_update_client(
+    service: web::Data<DynClientService>,
+    request: HttpRequest,
+    client_id: web::Path<i32>,
+    update_client: web::Json<UpdateClient>,
+) -> Result<HttpResponse, CommonError> {
+    let version = read_if_match_version(&request)?;
+    let client_info = service
+        .update_client(client_id.into_inner(), version, update_client.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, entity_tag(client_info.version)))
+        .json(client_info))
+}
+
+/// Maps new transaction batch end-point
+pub async fn map_create_transaction_batch(
+    service: web::Data<DynClientService>,
+    batch: web::Json<NewTransactionBatch>,
+) -> Result<HttpResponse, CommonError> {
+    let report = service.create_transaction_batch(batch.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Maps reverse transaction end-point
+pub async fn map_reverse_transaction(
+    service: web::Data<DynClientService>,
+    transaction_id: web::Path<i32>,
+    reverse_transaction: web::Json<ReverseTransaction>,
+) -> Result<HttpResponse, CommonError> {
+    let transaction_info = service
+        .reverse_transaction(
+            transaction_id.into_inner(),
+            reverse_transaction.into_inner(),
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(transaction_info))
+}
+
+/// Maps new hold end-point
+pub async fn map_create_hold(
+    service: web::Data<DynClientService>,
+    new_hold: web::Json<NewHold>,
+) -> Result<HttpResponse, CommonError> {
+    let hold_info = service.create_hold(new_hold.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(hold_info))
+}
+
+/// Maps capture hold end-point
+pub async fn map_capture_hold(
+    service: web::Data<DynClientService>,
+    hold_id: web::Path<i32>,
+    capture_hold: web::Json<CaptureHold>,
+) -> Result<HttpResponse, CommonError> {
+    let hold_info = service
+        .capture_hold(hold_id.into_inner(), capture_hold.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(hold_info))
+}
+
+/// Maps release hold end-point
+pub async fn map_release_hold(
+    service: web::Data<DynClientService>,
+    hold_id: web::Path<i32>,
+) -> Result<HttpResponse, CommonError> {
+    let hold_info = service.release_hold(hold_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(hold_info))
+}
+
+/// Maps block client end-point
+pub async fn map_block_client(
+    service: web::Data<DynClientService>,
+    client_id: web::Path<i32>,
+) -> Result<HttpResponse, CommonError> {
+    let client_info = service.block_client(client_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(client_info))
+}
+
+/// Maps unblock client end-point
+pub async fn map_unblock_client(
+    service: web::Data<DynClientService>,
+    client_id: web::Path<i32>,
+) -> Result<HttpResponse, CommonError> {
+    let client_info = service.unblock_client(client_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(client_info))
+}
+
+/// Maps close client end-point
+pub async fn map_close_client(
+    service: web::Data<DynClientService>,
+    client_id: web::Path<i32>,
+    close_client: web::Json<CloseClient>,
+) -> Result<HttpResponse, CommonError> {
+    let client_info = service
+        .close_client(client_id.into_inner(), close_client.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(client_info))
+}
+
+/// Maps get client transactions end-point
+pub async fn map_get_client_transactions(
+    service: web::Data<DynClientService>,
+    client_id: web::Path<i32>,
+    filter: web::Query<TransactionFilter>,
+) -> Result<HttpResponse, CommonError> {
+    let transaction_page = service
+        .get_client_transactions(client_id.into_inner(), filter.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(transaction_page))
+}
+
+/// Maps get client statement end-point, a CSV statement is sent as a downloadable file
+pub async fn map_get_client_statement(
+    service: web::Data<DynClientService>,
+    client_id: web::Path<i32>,
+    filter: web::Query<StatementFilter>,
+) -> Result<HttpResponse, CommonError> {
+    let filter = filter.into_inner();
+    let statement = service
+        .get_client_statement(client_id.into_inner(), filter.from, filter.to)
+        .await?;
+    match filter.format.unwrap_or_default() {
+        StatementFormat::Json => Ok(HttpResponse::Ok().json(statement)),
+        format @ StatementFormat::Csv => Ok(HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name(&statement)),
+            ))
+            .body(render_statement_csv(&statement)?)),
     }
 }
 
+/// Maps list clients end-point
+pub async fn map_list_clients(
+    service: web::Data<DynClientService>,
+    filter: web::Query<ClientFilter>,
+) -> Result<HttpResponse, CommonError> {
+    let client_page = service.list_clients(filter.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(client_page))
+}
+
+/// ETag of a client with [u64] version
+fn entity_tag(version: u64) -> String {
+    format!("\"{version}\"")
+}
+
+/// Read the client version from the `If-Match` header of [HttpRequest] request, with or without quotes
+/// Returns a [CommonError] if the header is missing or is not the ETag of a client
+fn read_if_match_version(request: &HttpRequest) -> Result<u64, CommonError> {
+    let if_match = request
+        .headers()
+        .get(header::IF_MATCH)
+        .ok_or_else(|| CommonError::PreconditionRequired(header::IF_MATCH.to_string()))?;
+    if_match
+        .to_str()
+        .ok()
+        .map(|if_match| if_match.trim().trim_start_matches("W/").trim_matches('"'))
+        .and_then(|version| version.parse().ok())
+        .ok_or_else(|| CommonError::InvalidField {
+            field: header::IF_MATCH.to_string(),
+            reason: "must be the ETag of the client".to_string(),
+        })
+}
+
+/// Read the `Idempotency-Key` header from [HttpRequest] request, if informed
+fn read_idempotency_key(request: &HttpRequest) -> Option<String> {
+    request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .map(|idempotency_key| idempotency_key.trim().to_string())
+        .filter(|idempotency_key| !idempotency_key.is_empty())
+}
+
 /// Unit tests cases
 #[cfg(test)]
 mod tests {
+    use crate::client_import::client_import_format::ClientImportFormat;
     use crate::constants::constants::{
-        MAIN_PATH, NEW_CLIENT_PATH, NEW_CREDIT_TRANSACTION_PATH, NEW_DEBIT_TRANSACTION_PATH,
-        STORE_BALANCE_PATH,
+        BALANCE_FILE_RECONCILIATION_PATH, CLIENTS_PATH, CLIENT_BALANCE_FOLDER,
+        CLIENT_STATEMENT_PATH, IDEMPOTENCY_KEY_HEADER, MAIN_PATH, NEW_CLIENT_PATH,
+        NEW_CREDIT_TRANSACTION_PATH, NEW_DEBIT_TRANSACTION_PATH, STORE_BALANCE_PATH, TRANSFER_PATH,
     };
     use crate::controller::client_controller::{
-        map_create_balance_files, map_create_new_client, map_create_new_credit_transaction,
-        map_create_new_debit_transaction, map_get_client_balance,
+        json_config, map_block_client, map_capture_hold, map_close_client,
+        map_create_balance_files, map_create_hold, map_create_new_client,
+        map_create_new_credit_transaction, map_create_new_debit_transaction,
+        map_create_transaction_batch, map_create_transfer, map_get_client_balance,
+        map_get_client_by_document, map_get_client_statement, map_get_client_transactions,
+        map_import_clients, map_list_clients, map_reconcile_balance_file, map_reverse_transaction,
+        map_update_client, query_config,
     };
+    use crate::dto::client_filter_dto::ClientSortField;
+    use crate::dto::client_import_report_dto::{ClientImportReport, ImportedClient};
     use crate::dto::client_info_dto::ClientInfo;
-    use crate::errors::common_error::CommonError;
+    use crate::dto::client_page_dto::ClientPage;
+    use crate::dto::client_statement_dto::{ClientStatement, StatementLine};
+    use crate::dto::error_response_dto::ErrorResponse;
+    use crate::dto::hold_info_dto::HoldInfo;
+    use crate::dto::reconciliation_report_dto::ReconciliationReport;
+    use crate::dto::transaction_batch_dto::BatchMode;
+    use crate::dto::transaction_batch_report_dto::{
+        BatchItemResult, BatchItemStatus, TransactionBatchReport,
+    };
+    use crate::dto::transaction_info_dto::TransactionInfo;
+    use crate::dto::transaction_page_dto::TransactionPage;
+    use crate::dto::transfer_info_dto::TransferInfo;
+    use crate::errors::common_error::{CommonError, REQUEST_ID_HEADER};
+    use crate::model::client_model::{ClientStatus, ClosureReason};
+    use crate::model::hold_model::HoldStatus;
+    use crate::model::transaction_model::TransactionKind;
     use crate::service::client_service::{DynClientService, MockClientServiceTrait};
-    use crate::stub::client_info_stub::stub::{create_client_info_stub, CLIENT_ID};
-    use crate::stub::new_client_stub::stub::create_new_client_stub;
+    use crate::stub::client_info_stub::stub::{create_client_info_stub, CLIENT_BALANCE, CLIENT_ID};
+    use crate::stub::hold_info_stub::stub::{create_hold_info_stub, HOLD_ID};
+    use crate::stub::new_client_stub::stub::{create_new_client_stub, CLIENT_DOCUMENT_NUMBER};
     use crate::stub::new_credit_transaction_stub::stub::create_new_credit_transaction_stub;
     use crate::stub::new_debit_transaction_stub::stub::create_new_debit_transaction_stub;
+    use crate::stub::new_hold_stub::stub::{create_new_hold_stub, HOLD_AMOUNT};
+    use crate::stub::new_transfer_stub::stub::{create_new_transfer_stub, TO_CLIENT_ID};
+    use crate::stub::transaction_info_stub::stub::{
+        create_transaction_info_stub, TRANSACTION_AMOUNT, TRANSACTION_CREATED_AT, TRANSACTION_ID,
+    };
     use actix_web::{test, web, App};
+    use chrono::NaiveDate;
     use http::StatusCode;
     use rust_decimal::Decimal;
+    use serde_json::json;
     use std::future;
     use std::sync::Arc;
 
     const MOCK_CLIENT_BALANCE_PATH: &str = "/client_balance/";
+    const MOCK_CLIENT_TRANSACTIONS_PATH: &str = "/transactions";
+    const MOCK_CLIENT_BY_DOCUMENT_PATH: &str = "/by_document/";
+    const IDEMPOTENCY_KEY: &str = "some-idempotency-key";
+    const BALANCE_FILE_NAME: &str = "01122023_1.DAT";
 
     ///Scenario:
     /// Executes map_create_new_client endpoint flow
@@ -194,7 +532,11 @@ mod tests {
 
         mock_service
             .expect_create_new_client()
-            .return_once(move |_p1| Box::pin(future::ready(Err(CommonError::Forbiden))));
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::DocumentAlreadyExists(
+                    CLIENT_DOCUMENT_NUMBER.to_string(),
+                ))))
+            });
 
         let path = format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH);
 
@@ -261,7 +603,9 @@ mod tests {
 
         mock_service
             .expect_create_new_credit_transaction()
-            .return_once(move |_p1| Box::pin(future::ready(Err(CommonError::NotFound))));
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::ClientNotFound(CLIENT_ID))))
+            });
 
         let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
 
@@ -327,7 +671,9 @@ mod tests {
 
         mock_service
             .expect_create_new_debit_transaction()
-            .return_once(move |_p1| Box::pin(future::ready(Err(CommonError::NotFound))));
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::ClientNotFound(CLIENT_ID))))
+            });
 
         let path = format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH);
 
@@ -388,7 +734,9 @@ mod tests {
 
         mock_service
             .expect_get_client_balance()
-            .return_once(move |_p1| Box::pin(future::ready(Err(CommonError::NotFound))));
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::ClientNotFound(CLIENT_ID))))
+            });
 
         let route_pattern = format!("{}{}{{id}}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH);
 
@@ -441,7 +789,11 @@ mod tests {
 
         mock_service
             .expect_generate_file_with_all_clients_balances()
-            .return_once(move || Box::pin(future::ready(Err(CommonError::FolderCreationFailed))));
+            .return_once(move || {
+                Box::pin(future::ready(Err(CommonError::FolderCreationFailed(
+                    CLIENT_BALANCE_FOLDER.to_string(),
+                ))))
+            });
 
         let path = format!("{}{}", MAIN_PATH, STORE_BALANCE_PATH);
 
@@ -457,4 +809,1186 @@ mod tests {
         let resp = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    /// Scenario:
+    /// Executes map_get_client_transactions endpoint flow
+    /// HTTP Status 200 and the proper transaction page should be returned
+    #[actix_web::test]
+    async fn when_map_get_client_transactions_is_valid_should_return_ok_status() {
+        let transaction_page = TransactionPage {
+            items: vec![create_transaction_info_stub()],
+            page: 1,
+            page_size: 20,
+            total_items: 1,
+        };
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_transactions()
+            .withf(|client_id, filter| *client_id == CLIENT_ID && filter.page == Some(1))
+            .return_once(move |_p1, _p2| Box::pin(future::ready(Ok(transaction_page))));
+
+        let route_pattern = format!("{}/{{id}}{}", MAIN_PATH, MOCK_CLIENT_TRANSACTIONS_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::get().to(map_get_client_transactions)),
+        )
+        .await;
+
+        let path = format!(
+            "{}/{}{}?from=2025-07-01&to=2025-07-31&page=1",
+            MAIN_PATH, CLIENT_ID, MOCK_CLIENT_TRANSACTIONS_PATH
+        );
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: TransactionPage = test::read_body_json(resp).await;
+        assert_eq!(body.items, vec![create_transaction_info_stub()]);
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_transactions when service returns an error
+    /// A HTTP Status error should be returned
+    #[actix_web::test]
+    async fn when_map_get_client_transactions_should_return_http_error() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_transactions()
+            .return_once(move |_p1, _p2| {
+                Box::pin(future::ready(Err(CommonError::ClientNotFound(CLIENT_ID))))
+            });
+
+        let route_pattern = format!("{}/{{id}}{}", MAIN_PATH, MOCK_CLIENT_TRANSACTIONS_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::get().to(map_get_client_transactions)),
+        )
+        .await;
+
+        let path = format!(
+            "{}/{}{}",
+            MAIN_PATH, CLIENT_ID, MOCK_CLIENT_TRANSACTIONS_PATH
+        );
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Scenario:
+    /// Executes map_create_new_credit_transaction with an `Idempotency-Key` header
+    /// The idempotency key should be sent to the service
+    #[actix_web::test]
+    async fn when_map_create_new_credit_transaction_with_idempotency_key_header_should_send_it_to_service(
+    ) {
+        let new_credit = create_new_credit_transaction_stub();
+
+        let mut mock_service = MockClientServiceTrait::new();
+        let expected_balance = Decimal::new(100, 2);
+
+        mock_service
+            .expect_create_new_credit_transaction()
+            .withf(|credit| credit.idempotency_key.as_deref() == Some(IDEMPOTENCY_KEY))
+            .return_once(move |_p1| Box::pin(future::ready(Ok(expected_balance))));
+
+        let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_new_credit_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .insert_header((IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_KEY))
+            .set_json(&new_credit)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    /// Scenario:
+    /// Executes map_create_new_debit_transaction when the idempotency key was used with another payload
+    /// A HTTP Status error should be returned
+    #[actix_web::test]
+    async fn when_map_create_new_debit_transaction_with_idempotency_key_conflict_should_return_http_error(
+    ) {
+        let new_debit = create_new_debit_transaction_stub();
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_new_debit_transaction()
+            .withf(|debit| debit.idempotency_key.as_deref() == Some(IDEMPOTENCY_KEY))
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::IdempotencyKeyConflict(
+                    IDEMPOTENCY_KEY.to_string(),
+                ))))
+            });
+
+        let path = format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_new_debit_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .insert_header((IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_KEY))
+            .set_json(&new_debit)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    /// Scenario:
+    /// Executes map_create_transfer endpoint flow
+    /// HTTP Status 200 and both balances should be returned
+    #[actix_web::test]
+    async fn when_map_create_transfer_is_valid_should_return_ok_status() {
+        let new_transfer = create_new_transfer_stub();
+        let transfer_info = TransferInfo {
+            from_client_id: CLIENT_ID,
+            from_balance: Decimal::ZERO,
+            to_client_id: TO_CLIENT_ID,
+            to_balance: new_transfer.transfer_amount,
+        };
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_transfer()
+            .return_once(move |_p1| Box::pin(future::ready(Ok(transfer_info))));
+
+        let path = format!("{}{}", MAIN_PATH, TRANSFER_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_transfer)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(&new_transfer)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: TransferInfo = test::read_body_json(resp).await;
+        assert_eq!(body.to_balance, new_transfer.transfer_amount);
+    }
+
+    /// Scenario:
+    /// Executes map_create_transfer when service returns an error
+    /// A HTTP Status error should be returned
+    #[actix_web::test]
+    async fn when_map_create_transfer_should_return_http_error() {
+        let new_transfer = create_new_transfer_stub();
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_transfer()
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::ClientNotFound(CLIENT_ID))))
+            });
+
+        let path = format!("{}{}", MAIN_PATH, TRANSFER_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_transfer)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(&new_transfer)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Scenario:
+    /// Executes map_create_new_credit_transaction when the amount is invalid
+    /// HTTP Status 400 naming the offending field should be returned
+    #[actix_web::test]
+    async fn when_map_create_new_credit_transaction_with_invalid_amount_should_return_bad_request()
+    {
+        let new_credit = create_new_credit_transaction_stub();
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_new_credit_transaction()
+            .return_once(move |_p1| {
+                Box::pin(future::ready(Err(CommonError::InvalidField {
+                    field: "credit_amount".to_string(),
+                    reason: "must be greater than zero".to_string(),
+                })))
+            });
+
+        let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_new_credit_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(&new_credit)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.code, "INVALID_FIELD");
+        assert_eq!(
+            body.message,
+            "Invalid credit_amount: must be greater than zero"
+        );
+        assert_eq!(
+            body.details,
+            Some(json!({ "field": "credit_amount", "reason": "must be greater than zero" }))
+        );
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_balance when the client does not exist
+    /// A JSON error envelope naming the client id should be returned
+    #[actix_web::test]
+    async fn when_map_get_client_balance_and_client_not_found_should_return_error_envelope() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_balance()
+            .return_once(move |p1| Box::pin(future::ready(Err(CommonError::ClientNotFound(p1)))));
+
+        let route_pattern = format!("{}{}{{id}}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::get().to(map_get_client_balance)),
+        )
+        .await;
+
+        let path = format!("{}{}{}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH, CLIENT_ID);
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let request_id = resp.headers().get(REQUEST_ID_HEADER).cloned().unwrap();
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.code, "CLIENT_NOT_FOUND");
+        assert_eq!(body.message, format!("Client {} not found", CLIENT_ID));
+        assert_eq!(body.details, Some(json!({ "client_id": CLIENT_ID })));
+        assert_eq!(body.request_id, request_id.to_str().unwrap());
+    }
+
+    /// Scenario:
+    /// Executes map_create_new_credit_transaction with a malformed JSON body
+    /// HTTP Status 400 with a JSON error envelope should be returned
+    #[actix_web::test]
+    async fn when_map_create_new_credit_transaction_with_malformed_body_should_return_error_envelope(
+    ) {
+        let mock_service = MockClientServiceTrait::new();
+
+        let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .app_data(json_config())
+                .route(&path, web::post().to(map_create_new_credit_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .insert_header(("Content-Type", "application/json"))
+            .set_payload("{\"client_id\": \"not-a-number\"}")
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(body.code, "INVALID_FIELD");
+        assert_eq!(body.details.unwrap()["field"], "body");
+    }
+
+    /// Scenario:
+    /// Executes map_list_clients endpoint flow with filters
+    /// HTTP Status 200 and the proper client page should be returned
+    #[actix_web::test]
+    async fn when_map_list_clients_is_valid_should_return_ok_status() {
+        let client_page = ClientPage {
+            items: vec![create_client_info_stub()],
+            page_size: 10,
+            next_cursor: Some("69643a31".to_string()),
+        };
+
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_list_clients()
+            .withf(|filter| {
+                filter.country.as_deref() == Some("AR")
+                    && filter.sort_by == Some(ClientSortField::Name)
+                    && filter.max_balance == Some(Decimal::new(500, 1))
+            })
+            .return_once(move |_p1| Box::pin(future::ready(Ok(client_page))));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(CLIENTS_PATH, web::get().to(map_list_clients)),
+        )
+        .await;
+
+        let path = format!(
+            "{}?country=AR&sort_by=name&max_balance=50.0&page_size=10",
+            CLIENTS_PATH
+        );
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: ClientPage = test::read_body_json(resp).await;
+        assert_eq!(body.items, vec![create_client_info_stub()]);
+        assert_eq!(body.next_cursor, Some("69643a31".to_string()));
+    }
+
+    /// Scenario:
+    /// Executes map_list_clients when service returns an error
+    /// A HTTP Status error should be returned
+    #[actix_web::test]
+    async fn when_map_list_clients_should_return_http_error() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service.expect_list_clients().return_once(move |_p1| {
+            Box::pin(future::ready(Err(CommonError::InvalidField {
+                field: "cursor".to_string(),
+                reason: "invalid".to_string(),
+            })))
+        });
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(CLIENTS_PATH, web::get().to(map_list_clients)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("{}?cursor=zz", CLIENTS_PATH))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_by_document endpoint flow
+    /// HTTP Status 200 and the client info should be returned
+    #[actix_web::test]
+    async fn when_map_get_client_by_document_is_valid_should_return_ok_status() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_by_document()
+            .withf(|document_number| document_number == CLIENT_DOCUMENT_NUMBER)
+            .return_once(move |_p1| Box::pin(future::ready(Ok(create_client_info_stub()))));
+
+        let route_pattern = format!(
+            "{}{}{{document_number}}",
+            MAIN_PATH, MOCK_CLIENT_BY_DOCUMENT_PATH
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::get().to(map_get_client_by_document)),
+        )
+        .await;
+
+        let path = format!(
+            "{}{}{}",
+            MAIN_PATH, MOCK_CLIENT_BY_DOCUMENT_PATH, CLIENT_DOCUMENT_NUMBER
+        );
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: ClientInfo = test::read_body_json(resp).await;
+        assert_eq!(body, create_client_info_stub());
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_by_document when the document number has not existed
+    /// A HTTP Status 404 should be returned
+    #[actix_web::test]
+    async fn when_map_get_client_by_document_and_document_not_exists_should_return_not_found() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_by_document()
+            .return_once(move |document_number| {
+                Box::pin(future::ready(Err(CommonError::DocumentNotFound(
+                    document_number,
+                ))))
+            });
+
+        let route_pattern = format!(
+            "{}{}{{document_number}}",
+            MAIN_PATH, MOCK_CLIENT_BY_DOCUMENT_PATH
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::get().to(map_get_client_by_document)),
+        )
+        .await;
+
+        let path = format!("{}{}unknown", MAIN_PATH, MOCK_CLIENT_BY_DOCUMENT_PATH);
+
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Scenario:
+    /// Executes map_update_client with an `If-Match` header
+    /// HTTP Status 200, the updated client and its new ETag should be returned
+    #[actix_web::test]
+    async fn when_map_update_client_with_if_match_should_return_ok_and_etag() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_update_client()
+            .withf(|client_id, version, update_client| {
+                *client_id == CLIENT_ID
+                    && *version == 1
+                    && update_client.country.as_deref() == Some("UY")
+            })
+            .return_once(move |_p1, _p2, _p3| {
+                let mut client_info = create_client_info_stub();
+                client_info.version = 2;
+                Box::pin(future::ready(Ok(client_info)))
+            });
+
+        let route_pattern = format!("{}/{{id}}", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::patch().to(map_update_client)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("{}/{}", MAIN_PATH, CLIENT_ID))
+            .insert_header(("If-Match", "\"1\""))
+            .set_json(json!({ "country": "UY" }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("ETag").unwrap(), "\"2\"");
+    }
+
+    /// Scenario:
+    /// Executes map_update_client without an `If-Match` header
+    /// A HTTP Status 428 should be returned without calling the service
+    #[actix_web::test]
+    async fn when_map_update_client_without_if_match_should_return_precondition_required() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service.expect_update_client().never();
+
+        let route_pattern = format!("{}/{{id}}", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::patch().to(map_update_client)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("{}/{}", MAIN_PATH, CLIENT_ID))
+            .set_json(json!({ "country": "UY" }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_REQUIRED);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("PRECONDITION_REQUIRED", body.code);
+    }
+
+    /// Scenario:
+    /// Executes map_block_client with an active client
+    /// Expectation:
+    /// A HTTP Status 200 should be returned with the blocked client
+    #[actix_web::test]
+    async fn when_map_block_client_should_return_blocked_client() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_block_client()
+            .withf(|client_id| *client_id == CLIENT_ID)
+            .return_once(|_p1| {
+                let mut client_info = create_client_info_stub();
+                client_info.status = ClientStatus::Blocked;
+                Box::pin(future::ready(Ok(client_info)))
+            });
+
+        let route_pattern = format!("{}/{{id}}/block", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::post().to(map_block_client)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("{}/{}/block", MAIN_PATH, CLIENT_ID))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: ClientInfo = test::read_body_json(resp).await;
+        assert_eq!(ClientStatus::Blocked, body.status);
+    }
+
+    /// Scenario:
+    /// Executes map_close_client with a reason code and the service rejects it because of the balance
+    /// Expectation:
+    /// A HTTP Status 409 should be returned with the BALANCE_NOT_ZERO code
+    #[actix_web::test]
+    async fn when_map_close_client_with_balance_should_return_conflict() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_close_client()
+            .withf(|client_id, close_client| {
+                *client_id == CLIENT_ID
+                    && close_client.reason_code == ClosureReason::CustomerRequest
+            })
+            .return_once(|client_id, _p2| {
+                Box::pin(future::ready(Err(CommonError::BalanceNotZero {
+                    client_id,
+                    balance: *CLIENT_BALANCE,
+                })))
+            });
+
+        let route_pattern = format!("{}/{{id}}/close", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::post().to(map_close_client)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("{}/{}/close", MAIN_PATH, CLIENT_ID))
+            .set_json(json!({ "reason_code": "CUSTOMER_REQUEST" }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("BALANCE_NOT_ZERO", body.code);
+    }
+
+    /// Scenario:
+    /// Executes map_create_hold with a valid hold
+    /// Expectation:
+    /// A HTTP Status 200 should be returned with the hold and both balances
+    #[actix_web::test]
+    async fn when_map_create_hold_should_return_hold_info() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_hold()
+            .withf(|new_hold| {
+                new_hold.client_id == CLIENT_ID && new_hold.hold_amount == *HOLD_AMOUNT
+            })
+            .return_once(|_p1| Box::pin(future::ready(Ok(create_hold_info_stub()))));
+
+        let path = format!("{}/new_hold", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_hold)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(create_new_hold_stub())
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: HoldInfo = test::read_body_json(resp).await;
+        assert_eq!(create_hold_info_stub(), body);
+    }
+
+    /// Scenario:
+    /// Executes map_capture_hold on a hold that was already released
+    /// Expectation:
+    /// A HTTP Status 409 should be returned with the HOLD_NOT_ACTIVE code
+    #[actix_web::test]
+    async fn when_map_capture_hold_not_active_should_return_conflict() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_capture_hold()
+            .withf(|hold_id, capture_hold| {
+                *hold_id == HOLD_ID && capture_hold.capture_amount.is_none()
+            })
+            .return_once(|hold_id, _p2| {
+                Box::pin(future::ready(Err(CommonError::HoldNotActive {
+                    hold_id,
+                    status: HoldStatus::Released,
+                })))
+            });
+
+        let route_pattern = format!("{}/hold/{{hold_id}}/capture", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::post().to(map_capture_hold)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("{}/hold/{}/capture", MAIN_PATH, HOLD_ID))
+            .set_json(json!({}))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("HOLD_NOT_ACTIVE", body.code);
+    }
+
+    /// Scenario:
+    /// Executes map_reverse_transaction with a partial amount
+    /// Expectation:
+    /// A HTTP Status 200 should be returned with the compensating entry linked to the transaction
+    #[actix_web::test]
+    async fn when_map_reverse_transaction_should_return_linked_entry() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_reverse_transaction()
+            .withf(|transaction_id, reverse_transaction| {
+                *transaction_id == TRANSACTION_ID
+                    && reverse_transaction.amount == Some(*TRANSACTION_AMOUNT)
+            })
+            .return_once(|transaction_id, _p2| {
+                let mut reversal = create_transaction_info_stub();
+                reversal.transaction_id = transaction_id + 1;
+                reversal.reversal_of = Some(transaction_id);
+                Box::pin(future::ready(Ok(reversal)))
+            });
+
+        let route_pattern = format!("{}/transaction/{{transaction_id}}/reversal", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::post().to(map_reverse_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!(
+                "{}/transaction/{}/reversal",
+                MAIN_PATH, TRANSACTION_ID
+            ))
+            .set_json(json!({ "amount": *TRANSACTION_AMOUNT }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: TransactionInfo = test::read_body_json(resp).await;
+        assert_eq!(Some(TRANSACTION_ID), body.reversal_of);
+    }
+
+    /// Scenario:
+    /// Executes map_reverse_transaction on a transaction that was already fully reversed
+    /// Expectation:
+    /// A HTTP Status 409 should be returned with the TRANSACTION_ALREADY_REVERSED code
+    #[actix_web::test]
+    async fn when_map_reverse_transaction_already_reversed_should_return_conflict() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_reverse_transaction()
+            .withf(|transaction_id, reverse_transaction| {
+                *transaction_id == TRANSACTION_ID && reverse_transaction.amount.is_none()
+            })
+            .return_once(|transaction_id, _p2| {
+                Box::pin(future::ready(Err(CommonError::TransactionAlreadyReversed(
+                    transaction_id,
+                ))))
+            });
+
+        let route_pattern = format!("{}/transaction/{{transaction_id}}/reversal", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&route_pattern, web::post().to(map_reverse_transaction)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!(
+                "{}/transaction/{}/reversal",
+                MAIN_PATH, TRANSACTION_ID
+            ))
+            .set_json(json!({}))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("TRANSACTION_ALREADY_REVERSED", body.code);
+    }
+
+    /// Scenario:
+    /// Executes map_create_transaction_batch in best effort mode with a credit and a debit
+    /// Expectation:
+    /// A HTTP Status 200 should be returned with the result of every item
+    #[actix_web::test]
+    async fn when_map_create_transaction_batch_should_return_batch_report() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_transaction_batch()
+            .withf(|batch| batch.mode == BatchMode::BestEffort && batch.items.len() == 2)
+            .return_once(|batch| {
+                let items = batch
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| BatchItemResult {
+                        index,
+                        client_id: item.client_id(),
+                        kind: item.kind(),
+                        amount: item.amount(),
+                        status: BatchItemStatus::Applied,
+                        balance: Some(*CLIENT_BALANCE),
+                        error_code: None,
+                        error_message: None,
+                    })
+                    .collect();
+                Box::pin(future::ready(Ok(TransactionBatchReport {
+                    mode: batch.mode,
+                    applied_items: 2,
+                    rejected_items: 0,
+                    items,
+                })))
+            });
+
+        let path = format!("{}/new_transaction_batch", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_transaction_batch)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(json!({
+                "mode": "BEST_EFFORT",
+                "items": [
+                    create_new_credit_transaction_stub(),
+                    create_new_debit_transaction_stub(),
+                ],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: TransactionBatchReport = test::read_body_json(resp).await;
+        assert_eq!(2, body.applied_items);
+        assert_eq!(CLIENT_ID, body.items[1].client_id);
+    }
+
+    /// Scenario:
+    /// Executes map_create_transaction_batch in all or nothing mode and the service rejects an item of an unknown client
+    /// Expectation:
+    /// A HTTP Status 404 should be returned with the BATCH_ITEM_REJECTED code and the index of the item
+    #[actix_web::test]
+    async fn when_map_create_transaction_batch_with_rejected_item_should_return_item_error() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_create_transaction_batch()
+            .withf(|batch| batch.mode == BatchMode::AllOrNothing)
+            .return_once(|_p1| {
+                Box::pin(future::ready(Err(CommonError::BatchItemRejected {
+                    index: 1,
+                    error: Box::new(CommonError::ClientNotFound(TO_CLIENT_ID)),
+                })))
+            });
+
+        let path = format!("{}/new_transaction_batch", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_create_transaction_batch)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .set_json(json!({
+                "items": [
+                    create_new_credit_transaction_stub(),
+                    { "client_id": TO_CLIENT_ID, "debit_amount": "1.00" },
+                ],
+            }))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("BATCH_ITEM_REJECTED", body.code);
+        let details = body.details.unwrap();
+        assert_eq!(json!(1), details["index"]);
+        assert_eq!(json!("CLIENT_NOT_FOUND"), details["code"]);
+    }
+
+    /// Scenario:
+    /// Executes map_import_clients with a JSON Lines file
+    /// Expectation:
+    /// A HTTP Status 200 should be returned with the import report as a downloadable file
+    #[actix_web::test]
+    async fn when_map_import_clients_should_return_downloadable_report() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_import_clients()
+            .withf(|content, format| {
+                content.contains(CLIENT_DOCUMENT_NUMBER) && *format == ClientImportFormat::Ndjson
+            })
+            .return_once(|_p1, _p2| {
+                Box::pin(future::ready(Ok(ClientImportReport {
+                    created_count: 1,
+                    rejected_count: 0,
+                    created: vec![ImportedClient {
+                        line: 1,
+                        client_id: CLIENT_ID,
+                        document_number: CLIENT_DOCUMENT_NUMBER.to_string(),
+                    }],
+                    rejected: Vec::new(),
+                })))
+            });
+
+        let path = format!("{}/import_clients", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(&path, web::post().to(map_import_clients)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("{}?format=ndjson", path))
+            .set_payload(serde_json::to_string(&create_new_client_stub()).unwrap())
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            "attachment; filename=\"client_import_report.json\"",
+            resp.headers().get("Content-Disposition").unwrap()
+        );
+
+        let body: ClientImportReport = test::read_body_json(resp).await;
+        assert_eq!(CLIENT_ID, body.created[0].client_id);
+    }
+
+    /// Scenario:
+    /// Executes map_import_clients with an unknown format
+    /// Expectation:
+    /// A HTTP Status 400 should be returned without calling the service
+    #[actix_web::test]
+    async fn when_map_import_clients_with_unknown_format_should_return_bad_request() {
+        let mock_service = MockClientServiceTrait::new();
+
+        let path = format!("{}/import_clients", MAIN_PATH);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .app_data(query_config())
+                .route(&path, web::post().to(map_import_clients)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("{}?format=xml", path))
+            .set_payload("client_name,birth_date,document_number,country")
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!("INVALID_FIELD", body.code);
+    }
+
+    /// Create a [ClientStatement] of July 2025 with the stub transaction
+    fn create_client_statement() -> ClientStatement {
+        ClientStatement {
+            client: create_client_info_stub(),
+            from: NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2025, 7, 31).unwrap(),
+            opening_balance: Decimal::ZERO,
+            closing_balance: *TRANSACTION_AMOUNT,
+            movements: vec![StatementLine {
+                transaction_id: TRANSACTION_ID,
+                created_at: *TRANSACTION_CREATED_AT,
+                kind: TransactionKind::Credit,
+                amount: *TRANSACTION_AMOUNT,
+                balance: *TRANSACTION_AMOUNT,
+                reversal_of: None,
+            }],
+        }
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_statement without format
+    /// Expectation:
+    /// The statement of the period should be returned as JSON
+    #[actix_web::test]
+    async fn when_map_get_client_statement_should_return_json_statement() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_statement()
+            .withf(|client_id, from, to| {
+                *client_id == CLIENT_ID
+                    && *from == NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+                    && *to == NaiveDate::from_ymd_opt(2025, 7, 31).unwrap()
+            })
+            .return_once(move |_p1, _p2, _p3| {
+                Box::pin(future::ready(Ok(create_client_statement())))
+            });
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .app_data(query_config())
+                .route(
+                    &format!("{}{}", MAIN_PATH, CLIENT_STATEMENT_PATH),
+                    web::get().to(map_get_client_statement),
+                ),
+        )
+        .await;
+
+        let path = format!(
+            "{}/{}/statement?from=2025-07-01&to=2025-07-31",
+            MAIN_PATH, CLIENT_ID
+        );
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: ClientStatement = test::read_body_json(resp).await;
+        assert_eq!(create_client_statement(), body);
+    }
+
+    /// Scenario:
+    /// Executes map_get_client_statement with the csv format, then without the period
+    /// Expectation:
+    /// The statement should be sent as a CSV attachment, and the request without the period rejected
+    #[actix_web::test]
+    async fn when_map_get_client_statement_with_csv_format_should_return_csv_attachment() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_get_client_statement()
+            .times(1)
+            .return_once(move |_p1, _p2, _p3| {
+                Box::pin(future::ready(Ok(create_client_statement())))
+            });
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .app_data(query_config())
+                .route(
+                    &format!("{}{}", MAIN_PATH, CLIENT_STATEMENT_PATH),
+                    web::get().to(map_get_client_statement),
+                ),
+        )
+        .await;
+
+        let path = format!(
+            "{}/{}/statement?from=2025-07-01&to=2025-07-31&format=csv",
+            MAIN_PATH, CLIENT_ID
+        );
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            "text/csv; charset=utf-8",
+            resp.headers().get("content-type").unwrap()
+        );
+        assert_eq!(
+            "attachment; filename=\"statement_1_2025-07-01_2025-07-31.csv\"",
+            resp.headers().get("content-disposition").unwrap()
+        );
+        let body = test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("client_id,client_name,"));
+        assert!(body.contains("\ntransaction_id,created_at,kind,amount,balance,reversal_of\n"));
+
+        let path = format!("{}/{}/statement?format=csv", MAIN_PATH, CLIENT_ID);
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// Scenario:
+    /// Executes map_reconcile_balance_file when the balance file matches the movements
+    /// Expectation:
+    /// A [ReconciliationReport] without mismatches should be returned
+    #[actix_web::test]
+    async fn when_map_reconcile_balance_file_should_return_report() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_reconcile_balance_file()
+            .withf(|file_name| file_name == BALANCE_FILE_NAME)
+            .return_once(move |file_name| {
+                Box::pin(future::ready(Ok(ReconciliationReport {
+                    file_name,
+                    checked_clients: 1,
+                    mismatches: vec![],
+                })))
+            });
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(
+                    &format!("{}{}", MAIN_PATH, BALANCE_FILE_RECONCILIATION_PATH),
+                    web::get().to(map_reconcile_balance_file),
+                ),
+        )
+        .await;
+
+        let path = format!(
+            "{}/balance_file/{}/reconciliation",
+            MAIN_PATH, BALANCE_FILE_NAME
+        );
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: ReconciliationReport = test::read_body_json(resp).await;
+        assert_eq!(BALANCE_FILE_NAME, body.file_name);
+        assert!(body.is_balanced());
+    }
+
+    /// Scenario:
+    /// Executes map_reconcile_balance_file when the balance file was not written by a balance reset
+    /// Expectation:
+    /// A HTTP Status error should be returned
+    #[actix_web::test]
+    async fn when_map_reconcile_balance_file_should_return_http_error() {
+        let mut mock_service = MockClientServiceTrait::new();
+
+        mock_service
+            .expect_reconcile_balance_file()
+            .return_once(move |file_name| {
+                Box::pin(future::ready(Err(CommonError::BalanceFileNotFound(
+                    file_name,
+                ))))
+            });
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Arc::new(mock_service) as DynClientService))
+                .route(
+                    &format!("{}{}", MAIN_PATH, BALANCE_FILE_RECONCILIATION_PATH),
+                    web::get().to(map_reconcile_balance_file),
+                ),
+        )
+        .await;
+
+        let path = format!(
+            "{}/balance_file/{}/reconciliation",
+            MAIN_PATH, BALANCE_FILE_NAME
+        );
+        let req = test::TestRequest::get().uri(&path).to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,32 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Capture Hold struct
+#[derive(Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CaptureHold {
+    // money debited, the whole hold when not informed, the rest is released
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_amount: Option<Decimal>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::CaptureHold;
+    use rust_decimal::Decimal;
+
+    /// Scenario:
+    /// Deserialize a [CaptureHold] without and with a capture amount
+    /// Expectation:
+    /// The capture amount should be None for a full capture and the informed amount otherwise
+    #[test]
+    fn when_deserialize_capture_hold_should_read_optional_capture_amount() {
+        let full_capture: CaptureHold = serde_json::from_str("{}").unwrap();
+        let partial_capture: CaptureHold =
+            serde_json::from_str(r#"{ "capture_amount": "0.50" }"#).unwrap();
+
+        assert!(full_capture.capture_amount.is_none());
+        assert_eq!(Some(Decimal::new(50, 2)), partial_capture.capture_amount);
+    }
+}
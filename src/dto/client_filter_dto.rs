@@ -0,0 +1,61 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Client listing order
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientSortField {
+    // ascending client id
+    #[default]
+    Id,
+    // ascending client name, then client id
+    Name,
+}
+
+/// Client Filter struct, every field is optional
+#[derive(Deserialize, Serialize, Default)]
+pub struct ClientFilter {
+    // country, ignoring case
+    pub country: Option<String>,
+    // exact document number
+    pub document_number: Option<String>,
+    // lowest balance included
+    pub min_balance: Option<Decimal>,
+    // highest balance included
+    pub max_balance: Option<Decimal>,
+    // part of the client name, ignoring case
+    pub name: Option<String>,
+    // listing order
+    pub sort_by: Option<ClientSortField>,
+    // next_cursor of the previous page
+    pub cursor: Option<String>,
+    // clients per page
+    pub page_size: Option<usize>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::{ClientFilter, ClientSortField};
+    use actix_web::web;
+
+    /// Scenario:
+    /// Deserialize a [ClientFilter] from a query string
+    /// Expectation:
+    /// A [ClientFilter] with the informed filters should be created
+    #[test]
+    fn when_deserialize_client_filter_from_query_should_retrieve_set_values() {
+        let target = web::Query::<ClientFilter>::from_query(
+            "country=AR&min_balance=10.50&sort_by=name&page_size=5",
+        )
+        .unwrap()
+        .into_inner();
+
+        assert_eq!(Some("AR".to_string()), target.country);
+        assert_eq!(Some("10.50".parse().unwrap()), target.min_balance);
+        assert_eq!(Some(ClientSortField::Name), target.sort_by);
+        assert_eq!(Some(5), target.page_size);
+        assert!(target.document_number.is_none());
+        assert!(target.cursor.is_none());
+    }
+}
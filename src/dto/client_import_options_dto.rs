@@ -0,0 +1,9 @@
+use crate::client_import::client_import_format::ClientImportFormat;
+use serde::Deserialize;
+
+/// Client Import Options struct, every field is optional
+#[derive(Deserialize, Default)]
+pub struct ClientImportOptions {
+    // layout of the file, csv when not informed
+    pub format: Option<ClientImportFormat>,
+}
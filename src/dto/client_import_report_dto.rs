@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Imported Client struct
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ImportedClient {
+    // line of the row in the file
+    pub line: usize,
+    // generated client id
+    pub client_id: i32,
+    // document number
+    pub document_number: String,
+}
+
+/// Rejected Client Row struct
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RejectedClientRow {
+    // line of the row in the file
+    pub line: usize,
+    // document number, None if the row cannot be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_number: Option<String>,
+    // error code
+    pub error_code: String,
+    // why the row was rejected
+    pub reason: String,
+}
+
+/// Client Import Report struct
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct ClientImportReport {
+    // number of clients created
+    pub created_count: usize,
+    // number of rows rejected
+    pub rejected_count: usize,
+    // created clients, in file order
+    pub created: Vec<ImportedClient>,
+    // rejected rows, in file order
+    pub rejected: Vec<RejectedClientRow>,
+}
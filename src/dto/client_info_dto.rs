@@ -1,3 +1,4 @@
+use crate::model::client_model::ClientStatus;
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -15,8 +16,14 @@ pub struct ClientInfo {
     pub document_number: String,
     //country
     pub country: String,
-    // money in an account
+    // money in an account, including the held money
     pub balance: Decimal,
+    // money that can be debited or held, the balance minus the active holds
+    pub available_balance: Decimal,
+    // profile version, also sent as the ETag header
+    pub version: u64,
+    // account status
+    pub status: ClientStatus,
 }
 
 /// Unit tests cases
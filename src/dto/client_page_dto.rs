@@ -0,0 +1,37 @@
+use crate::dto::client_info_dto::ClientInfo;
+use serde::{Deserialize, Serialize};
+
+/// Client Page struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ClientPage {
+    // clients of the requested page
+    pub items: Vec<ClientInfo>,
+    // clients per page
+    pub page_size: usize,
+    // cursor of the next page, missing on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::ClientPage;
+    use crate::stub::client_info_stub::stub::create_client_info_stub;
+
+    /// Scenario:
+    /// Creates a [ClientPage] struct with valid values
+    /// Expectation:
+    /// A [ClientPage] with proper values should be created
+    #[test]
+    fn when_create_client_page_with_proper_values_should_retrieve_set_values() {
+        let target = ClientPage {
+            items: vec![create_client_info_stub()],
+            page_size: 20,
+            next_cursor: None,
+        };
+
+        assert_eq!(create_client_info_stub(), target.items[0]);
+        assert_eq!(20, target.page_size);
+        assert!(target.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::dto::client_info_dto::ClientInfo;
+use crate::model::transaction_model::TransactionKind;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Statement Line struct, a movement of the client account
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct StatementLine {
+    // transaction id
+    pub transaction_id: i32,
+    // transaction date
+    pub created_at: DateTime<Utc>,
+    // credit, debit, settlement or opening balance
+    pub kind: TransactionKind,
+    // money added to the balance, negative when it was taken out
+    pub amount: Decimal,
+    // client balance after the movement
+    pub balance: Decimal,
+    // transaction id compensated by this movement, None if it is not a reversal
+    pub reversal_of: Option<i32>,
+}
+
+/// Client Statement struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ClientStatement {
+    // client data when the statement was requested
+    pub client: ClientInfo,
+    // first day included
+    pub from: NaiveDate,
+    // last day included
+    pub to: NaiveDate,
+    // balance before the first day
+    pub opening_balance: Decimal,
+    // balance after the last day
+    pub closing_balance: Decimal,
+    // movements of the period, oldest first
+    pub movements: Vec<StatementLine>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::client_info_stub::stub::create_client_info_stub;
+    use crate::stub::transaction_info_stub::stub::{
+        TRANSACTION_AMOUNT, TRANSACTION_CREATED_AT, TRANSACTION_ID,
+    };
+
+    /// Scenario:
+    /// Creates a [ClientStatement] struct with valid values
+    /// Expectation:
+    /// A [ClientStatement] with proper values should be created
+    #[test]
+    fn when_create_client_statement_with_proper_values_should_retrieve_set_values() {
+        let from = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 7, 31).unwrap();
+
+        let target = ClientStatement {
+            client: create_client_info_stub(),
+            from,
+            to,
+            opening_balance: Decimal::ZERO,
+            closing_balance: *TRANSACTION_AMOUNT,
+            movements: vec![StatementLine {
+                transaction_id: TRANSACTION_ID,
+                created_at: *TRANSACTION_CREATED_AT,
+                kind: TransactionKind::Credit,
+                amount: *TRANSACTION_AMOUNT,
+                balance: *TRANSACTION_AMOUNT,
+                reversal_of: None,
+            }],
+        };
+
+        assert_eq!(create_client_info_stub(), target.client);
+        assert_eq!(from, target.from);
+        assert_eq!(to, target.to);
+        assert_eq!(Decimal::ZERO, target.opening_balance);
+        assert_eq!(*TRANSACTION_AMOUNT, target.closing_balance);
+        assert_eq!(TRANSACTION_ID, target.movements[0].transaction_id);
+        assert_eq!(*TRANSACTION_AMOUNT, target.movements[0].amount);
+    }
+}
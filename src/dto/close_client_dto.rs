@@ -0,0 +1,30 @@
+use crate::model::client_model::ClosureReason;
+use serde::{Deserialize, Serialize};
+
+/// Close Client struct
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CloseClient {
+    // why the account is closed
+    pub reason_code: ClosureReason,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::CloseClient;
+    use crate::model::client_model::ClosureReason;
+
+    /// Scenario:
+    /// Deserialize a [CloseClient] with a known and with an unknown reason code
+    /// Expectation:
+    /// The known reason code should be read and the unknown one should be rejected
+    #[test]
+    fn when_deserialize_close_client_should_read_reason_code() {
+        let target: CloseClient =
+            serde_json::from_str(r#"{"reason_code":"CUSTOMER_REQUEST"}"#).unwrap();
+
+        assert_eq!(ClosureReason::CustomerRequest, target.reason_code);
+        assert!(serde_json::from_str::<CloseClient>(r#"{"reason_code":"BORED"}"#).is_err());
+    }
+}
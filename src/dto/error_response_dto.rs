@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Error Response struct, body of every failed request
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ErrorResponse {
+    // machine readable error code
+    pub code: String,
+    // human readable error message
+    pub message: String,
+    // context of the error, for example the client id not found
+    pub details: Option<Value>,
+    // id to find the error in the logs
+    pub request_id: String,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::ErrorResponse;
+    use serde_json::json;
+
+    const CODE: &str = "CLIENT_NOT_FOUND";
+    const MESSAGE: &str = "Client 1 not found";
+    const REQUEST_ID: &str = "some-request-id";
+
+    /// Scenario:
+    /// Creates a [ErrorResponse] struct with valid values
+    /// Expectation:
+    /// A [ErrorResponse] with proper values should be created
+    #[test]
+    fn when_create_error_response_with_proper_values_should_retrieve_set_values() {
+        let target = ErrorResponse {
+            code: CODE.to_string(),
+            message: MESSAGE.to_string(),
+            details: Some(json!({ "client_id": 1 })),
+            request_id: REQUEST_ID.to_string(),
+        };
+
+        assert_eq!(CODE, target.code);
+        assert_eq!(MESSAGE, target.message);
+        assert_eq!(Some(json!({ "client_id": 1 })), target.details);
+        assert_eq!(REQUEST_ID, target.request_id);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::model::hold_model::HoldStatus;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Hold Info struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct HoldInfo {
+    // hold id
+    pub hold_id: i32,
+    // client id
+    pub client_id: i32,
+    // money reserved
+    pub amount: Decimal,
+    // money debited by the capture
+    pub captured_amount: Decimal,
+    // hold status
+    pub status: HoldStatus,
+    // when an active hold is released automatically
+    pub expires_at: DateTime<Utc>,
+    // client ledger balance after the operation
+    pub balance: Decimal,
+    // client available balance after the operation
+    pub available_balance: Decimal,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::model::hold_model::HoldStatus;
+    use crate::stub::client_info_stub::stub::{CLIENT_BALANCE, CLIENT_ID};
+    use crate::stub::hold_info_stub::stub::*;
+    use crate::stub::new_hold_stub::stub::HOLD_AMOUNT;
+
+    /// Scenario:
+    /// Creates a [HoldInfo] struct with valid values
+    /// Expectation:
+    /// A [HoldInfo] with proper values should be created
+    #[test]
+    fn when_create_hold_info_with_proper_values_should_retrieve_set_values() {
+        let target = create_hold_info_stub();
+
+        assert_eq!(HOLD_ID, target.hold_id);
+        assert_eq!(CLIENT_ID, target.client_id);
+        assert_eq!(*HOLD_AMOUNT, target.amount);
+        assert_eq!(HoldStatus::Active, target.status);
+        assert_eq!(*HOLD_EXPIRES_AT, target.expires_at);
+        assert_eq!(*CLIENT_BALANCE, target.balance);
+        assert_eq!(*CLIENT_BALANCE - *HOLD_AMOUNT, target.available_balance);
+    }
+}
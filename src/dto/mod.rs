@@ -1,5 +1,27 @@
+pub mod capture_hold_dto;
 pub mod client_balance_dto;
+pub mod client_filter_dto;
+pub mod client_import_options_dto;
+pub mod client_import_report_dto;
 pub mod client_info_dto;
+pub mod client_page_dto;
+pub mod client_statement_dto;
+pub mod close_client_dto;
+pub mod error_response_dto;
+pub mod hold_info_dto;
 pub mod new_client_dto;
 pub mod new_credit_transaction;
 pub mod new_debit_transaction;
+pub mod new_hold_dto;
+pub mod new_transfer_dto;
+pub mod opening_balances_report_dto;
+pub mod reconciliation_report_dto;
+pub mod reverse_transaction_dto;
+pub mod statement_filter_dto;
+pub mod transaction_batch_dto;
+pub mod transaction_batch_report_dto;
+pub mod transaction_filter_dto;
+pub mod transaction_info_dto;
+pub mod transaction_page_dto;
+pub mod transfer_info_dto;
+pub mod update_client_dto;
@@ -8,6 +8,9 @@ pub struct NewDebitTransaction {
     pub client_id: i32,
     // money debited
     pub debit_amount: Decimal,
+    // key to deduplicate retries, also accepted through the `Idempotency-Key` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 /// Unit tests cases
@@ -28,5 +31,6 @@ mod tests {
 
         assert_eq!(CLIENT_ID, target.client_id);
         assert_eq!(CLIENT_DEBIT_AMOUNT.clone(), target.debit_amount);
+        assert!(target.idempotency_key.is_none());
     }
 }
@@ -0,0 +1,34 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// New Hold struct
+#[derive(Deserialize, Serialize)]
+pub struct NewHold {
+    // client id
+    pub client_id: i32,
+    // money reserved
+    pub hold_amount: Decimal,
+    // seconds until the hold is released automatically, a week when not informed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_seconds: Option<i64>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use crate::stub::new_hold_stub::stub::*;
+
+    /// Scenario:
+    /// Creates a [NewHold] struct with valid values
+    /// Expectation:
+    /// A [NewHold] with proper values should be created
+    #[test]
+    fn when_create_new_hold_with_proper_values_should_retrieve_set_values() {
+        let target = create_new_hold_stub();
+
+        assert_eq!(CLIENT_ID, target.client_id);
+        assert_eq!(*HOLD_AMOUNT, target.hold_amount);
+        assert!(target.expires_in_seconds.is_none());
+    }
+}
@@ -0,0 +1,33 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// New Transfer struct
+#[derive(Deserialize, Serialize)]
+pub struct NewTransfer {
+    // client id debited
+    pub from_client_id: i32,
+    // client id credited
+    pub to_client_id: i32,
+    // money transferred
+    pub transfer_amount: Decimal,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use crate::stub::new_transfer_stub::stub::*;
+
+    /// Scenario:
+    /// Creates a [NewTransfer] struct with valid values
+    /// Expectation:
+    /// A [NewTransfer] with proper values should be created
+    #[test]
+    fn when_create_new_transfer_with_proper_values_should_retrieve_set_values() {
+        let target = create_new_transfer_stub();
+
+        assert_eq!(CLIENT_ID, target.from_client_id);
+        assert_eq!(TO_CLIENT_ID, target.to_client_id);
+        assert_eq!(*TRANSFER_AMOUNT, target.transfer_amount);
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// Opening Balances Report struct
+#[derive(Serialize, PartialEq, Debug)]
+pub struct OpeningBalancesReport {
+    // balance file loaded
+    pub file_name: String,
+    // clients whose balance was loaded
+    pub restored_client_ids: Vec<i32>,
+    // clients in the file that are missing from the current state
+    pub missing_client_ids: Vec<i32>,
+}
@@ -0,0 +1,34 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Balance Mismatch struct, a client whose stored balance differs from its movements
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct BalanceMismatch {
+    // client id
+    pub client_id: i32,
+    // balance written in the file, None if the client is missing from it
+    pub file_balance: Option<Decimal>,
+    // balance recomputed from the ledger
+    pub expected_balance: Decimal,
+    // file balance minus expected balance
+    pub difference: Decimal,
+}
+
+/// Reconciliation Report struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ReconciliationReport {
+    // balance file checked
+    pub file_name: String,
+    // clients in the file or with movements before it was written
+    pub checked_clients: usize,
+    // clients whose balance does not match, sorted by client id
+    pub mismatches: Vec<BalanceMismatch>,
+}
+
+/// Implementation Reconciliation Report
+impl ReconciliationReport {
+    /// True when every balance of the file matches the movements
+    pub fn is_balanced(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
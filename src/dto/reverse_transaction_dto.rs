@@ -0,0 +1,32 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Reverse Transaction struct
+#[derive(Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ReverseTransaction {
+    // money refunded, the rest of the transaction when not informed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Decimal>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::ReverseTransaction;
+    use rust_decimal::Decimal;
+
+    /// Scenario:
+    /// Deserialize a [ReverseTransaction] without and with an amount
+    /// Expectation:
+    /// The amount should be None for a full reversal and the informed amount for a partial refund
+    #[test]
+    fn when_deserialize_reverse_transaction_should_read_optional_amount() {
+        let full_reversal: ReverseTransaction = serde_json::from_str("{}").unwrap();
+        let partial_refund: ReverseTransaction =
+            serde_json::from_str(r#"{ "amount": "0.25" }"#).unwrap();
+
+        assert!(full_reversal.amount.is_none());
+        assert_eq!(Some(Decimal::new(25, 2)), partial_refund.amount);
+    }
+}
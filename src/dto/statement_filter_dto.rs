@@ -0,0 +1,14 @@
+use crate::statement::statement_format::StatementFormat;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// Statement Filter struct, the period is required
+#[derive(Deserialize)]
+pub struct StatementFilter {
+    // first day included (format `YYYY-MM-DD`)
+    pub from: NaiveDate,
+    // last day included (format `YYYY-MM-DD`)
+    pub to: NaiveDate,
+    // `json` (default) or `csv`
+    pub format: Option<StatementFormat>,
+}
@@ -0,0 +1,104 @@
+use crate::dto::new_credit_transaction::NewCreditTransaction;
+use crate::dto::new_debit_transaction::NewDebitTransaction;
+use crate::model::transaction_model::TransactionKind;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// How a batch handles a rejected item
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BatchMode {
+    // any rejected item rejects the whole batch
+    #[default]
+    AllOrNothing,
+    // rejected items are reported and the rest are applied
+    BestEffort,
+}
+
+/// Item of a batch, read as a credit when it has `credit_amount` and as a debit when it has `debit_amount`
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BatchTransaction {
+    Credit(NewCreditTransaction),
+    Debit(NewDebitTransaction),
+}
+
+/// Implementation Batch transaction
+impl BatchTransaction {
+    /// Client id of the item
+    pub fn client_id(&self) -> i32 {
+        match self {
+            BatchTransaction::Credit(credit_transaction) => credit_transaction.client_id,
+            BatchTransaction::Debit(debit_transaction) => debit_transaction.client_id,
+        }
+    }
+
+    /// Kind of transaction posted by the item
+    pub fn kind(&self) -> TransactionKind {
+        match self {
+            BatchTransaction::Credit(_) => TransactionKind::Credit,
+            BatchTransaction::Debit(_) => TransactionKind::Debit,
+        }
+    }
+
+    /// Money credited or debited by the item
+    pub fn amount(&self) -> Decimal {
+        match self {
+            BatchTransaction::Credit(credit_transaction) => credit_transaction.credit_amount,
+            BatchTransaction::Debit(debit_transaction) => debit_transaction.debit_amount,
+        }
+    }
+
+    /// Idempotency key of the item, if any
+    pub fn idempotency_key(&self) -> Option<&str> {
+        match self {
+            BatchTransaction::Credit(credit_transaction) => {
+                credit_transaction.idempotency_key.as_deref()
+            }
+            BatchTransaction::Debit(debit_transaction) => {
+                debit_transaction.idempotency_key.as_deref()
+            }
+        }
+    }
+}
+
+/// New Transaction Batch struct
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewTransactionBatch {
+    // all or nothing when not informed
+    #[serde(default)]
+    pub mode: BatchMode,
+    // credits and debits, applied in order
+    pub items: Vec<BatchTransaction>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::{BatchMode, NewTransactionBatch};
+    use crate::model::transaction_model::TransactionKind;
+    use rust_decimal::Decimal;
+
+    /// Scenario:
+    /// Deserialize a [NewTransactionBatch] without mode and with a credit and a debit
+    /// Expectation:
+    /// The mode should be all or nothing and each item should be read by its amount field
+    #[test]
+    fn when_deserialize_new_transaction_batch_should_read_mixed_items() {
+        let batch: NewTransactionBatch = serde_json::from_str(
+            r#"{ "items": [
+                { "client_id": 1, "credit_amount": "1.50" },
+                { "client_id": 2, "debit_amount": "0.25" }
+            ] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(BatchMode::AllOrNothing, batch.mode);
+        assert_eq!(TransactionKind::Credit, batch.items[0].kind());
+        assert_eq!(Decimal::new(150, 2), batch.items[0].amount());
+        assert_eq!(TransactionKind::Debit, batch.items[1].kind());
+        assert_eq!(2, batch.items[1].client_id());
+        assert!(batch.items[1].idempotency_key().is_none());
+    }
+}
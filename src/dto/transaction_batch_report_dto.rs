@@ -0,0 +1,51 @@
+use crate::dto::transaction_batch_dto::BatchMode;
+use crate::model::transaction_model::TransactionKind;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a batch item
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BatchItemStatus {
+    // posted on the client account
+    Applied,
+    // not posted, see its error code
+    Rejected,
+}
+
+/// Batch Item Result struct
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct BatchItemResult {
+    // position of the item in the batch, starting at 0
+    pub index: usize,
+    // client id
+    pub client_id: i32,
+    // credit or debit
+    pub kind: TransactionKind,
+    // money credited or debited
+    pub amount: Decimal,
+    // applied or rejected
+    pub status: BatchItemStatus,
+    // client balance after the item, only for applied items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Decimal>,
+    // error code, only for rejected items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    // error message, only for rejected items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+/// Transaction Batch Report struct
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TransactionBatchReport {
+    // mode the batch was applied with
+    pub mode: BatchMode,
+    // items posted
+    pub applied_items: usize,
+    // items not posted
+    pub rejected_items: usize,
+    // result of every item, in the batch order
+    pub items: Vec<BatchItemResult>,
+}
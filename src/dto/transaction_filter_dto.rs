@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Transaction Filter struct, every field is optional
+#[derive(Deserialize, Serialize, Default)]
+pub struct TransactionFilter {
+    // first day included (format `YYYY-MM-DD`)
+    pub from: Option<NaiveDate>,
+    // last day included (format `YYYY-MM-DD`)
+    pub to: Option<NaiveDate>,
+    // page number, starting at 1
+    pub page: Option<usize>,
+    // transactions per page
+    pub page_size: Option<usize>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::TransactionFilter;
+
+    /// Scenario:
+    /// Creates a [TransactionFilter] struct with default values
+    /// Expectation:
+    /// A [TransactionFilter] without filters should be created
+    #[test]
+    fn when_create_default_transaction_filter_should_retrieve_empty_values() {
+        let target = TransactionFilter::default();
+
+        assert!(target.from.is_none());
+        assert!(target.to.is_none());
+        assert!(target.page.is_none());
+        assert!(target.page_size.is_none());
+    }
+}
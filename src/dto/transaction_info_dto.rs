@@ -0,0 +1,49 @@
+use crate::model::transaction_model::TransactionKind;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Transaction Info struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct TransactionInfo {
+    // transaction id
+    pub transaction_id: i32,
+    // client id
+    pub client_id: i32,
+    // credit or debit
+    pub kind: TransactionKind,
+    // money moved by the transaction
+    pub amount: Decimal,
+    // client balance after the transaction
+    pub balance: Decimal,
+    // transaction date
+    pub created_at: DateTime<Utc>,
+    // transaction id compensated by this entry, None if it is not a reversal
+    pub reversal_of: Option<i32>,
+    // money of this transaction already compensated by reversals
+    pub reversed_amount: Decimal,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::model::transaction_model::TransactionKind;
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use crate::stub::transaction_info_stub::stub::*;
+
+    /// Scenario:
+    /// Creates a [TransactionInfo] struct with valid values
+    /// Expectation:
+    /// A [TransactionInfo] with proper values should be created
+    #[test]
+    fn when_create_transaction_info_with_proper_values_should_retrieve_set_values() {
+        let target = create_transaction_info_stub();
+
+        assert_eq!(TRANSACTION_ID, target.transaction_id);
+        assert_eq!(CLIENT_ID, target.client_id);
+        assert_eq!(TransactionKind::Credit, target.kind);
+        assert_eq!(*TRANSACTION_AMOUNT, target.amount);
+        assert_eq!(*TRANSACTION_AMOUNT, target.balance);
+        assert_eq!(*TRANSACTION_CREATED_AT, target.created_at);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::dto::transaction_info_dto::TransactionInfo;
+use serde::{Deserialize, Serialize};
+
+/// Transaction Page struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct TransactionPage {
+    // transactions of the requested page
+    pub items: Vec<TransactionInfo>,
+    // page number, starting at 1
+    pub page: usize,
+    // transactions per page
+    pub page_size: usize,
+    // transactions matching the filter
+    pub total_items: usize,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::TransactionPage;
+    use crate::stub::transaction_info_stub::stub::create_transaction_info_stub;
+
+    /// Scenario:
+    /// Creates a [TransactionPage] struct with valid values
+    /// Expectation:
+    /// A [TransactionPage] with proper values should be created
+    #[test]
+    fn when_create_transaction_page_with_proper_values_should_retrieve_set_values() {
+        let target = TransactionPage {
+            items: vec![create_transaction_info_stub()],
+            page: 1,
+            page_size: 20,
+            total_items: 1,
+        };
+
+        assert_eq!(create_transaction_info_stub(), target.items[0]);
+        assert_eq!(1, target.page);
+        assert_eq!(20, target.page_size);
+        assert_eq!(1, target.total_items);
+    }
+}
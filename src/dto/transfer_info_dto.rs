@@ -0,0 +1,43 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Transfer Info struct
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct TransferInfo {
+    // client id debited
+    pub from_client_id: i32,
+    // balance of the debited client after the transfer
+    pub from_balance: Decimal,
+    // client id credited
+    pub to_client_id: i32,
+    // balance of the credited client after the transfer
+    pub to_balance: Decimal,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::TransferInfo;
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use crate::stub::new_transfer_stub::stub::*;
+    use rust_decimal::Decimal;
+
+    /// Scenario:
+    /// Creates a [TransferInfo] struct with valid values
+    /// Expectation:
+    /// A [TransferInfo] with proper values should be created
+    #[test]
+    fn when_create_transfer_info_with_proper_values_should_retrieve_set_values() {
+        let target = TransferInfo {
+            from_client_id: CLIENT_ID,
+            from_balance: Decimal::ZERO,
+            to_client_id: TO_CLIENT_ID,
+            to_balance: *TRANSFER_AMOUNT,
+        };
+
+        assert_eq!(CLIENT_ID, target.from_client_id);
+        assert_eq!(Decimal::ZERO, target.from_balance);
+        assert_eq!(TO_CLIENT_ID, target.to_client_id);
+        assert_eq!(*TRANSFER_AMOUNT, target.to_balance);
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Update Client struct, only the informed fields are changed
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateClient {
+    // client name
+    pub client_name: Option<String>,
+    // client birth date
+    pub birth_date: Option<NaiveDate>,
+    // document number, must stay unique
+    pub document_number: Option<String>,
+    //country
+    pub country: Option<String>,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::UpdateClient;
+
+    /// Scenario:
+    /// Deserialize an [UpdateClient] with one field and with a field that cannot be updated
+    /// Expectation:
+    /// Only the informed field should be set and the unknown field should be rejected
+    #[test]
+    fn when_deserialize_update_client_should_keep_only_informed_fields() {
+        let target: UpdateClient = serde_json::from_str(r#"{"country":"UY"}"#).unwrap();
+
+        assert_eq!(Some("UY".to_string()), target.country);
+        assert!(target.client_name.is_none());
+        assert!(target.birth_date.is_none());
+        assert!(target.document_number.is_none());
+        assert!(serde_json::from_str::<UpdateClient>(r#"{"balance":"10.00"}"#).is_err());
+    }
+}
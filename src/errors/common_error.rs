@@ -1,12 +1,509 @@
+use crate::dto::error_response_dto::ErrorResponse;
+use crate::model::client_model::ClientStatus;
+use crate::model::hold_model::HoldStatus;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use log::error;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::fmt;
+use uuid::Uuid;
+
+/// Header with the request id of a failed request
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
 /// Type of errors
 #[derive(Debug, PartialEq)]
 pub enum CommonError {
-    NotFound,
-    FolderCreationFailed,
-    FolderReadFailed,
+    // client id not found
+    ClientNotFound(i32),
+    // hold id not found
+    HoldNotFound(i32),
+    // transaction id not found
+    TransactionNotFound(i32),
+    // name of a file not written by a balance reset of this service
+    BalanceFileNotFound(String),
+    // folder path
+    FolderCreationFailed(String),
+    // folder path
+    FolderReadFailed(String),
     LockReadFailed,
     LockWriteFailed,
-    FileCreationFailed,
-    FileWriteFailed,
-    Forbiden,
+    // file name
+    FileCreationFailed(String),
+    // file name
+    FileWriteFailed(String),
+    // file name
+    FileReadFailed(String),
+    // document number already registered
+    DocumentAlreadyExists(String),
+    // document number not registered
+    DocumentNotFound(String),
+    // idempotency key used with another payload
+    IdempotencyKeyConflict(String),
+    InvalidField {
+        field: String,
+        reason: String,
+    },
+    // storage error description
+    StorageFailed(String),
+    // reason the balance file is rejected
+    InvalidBalanceFile(String),
+    // client id and its current version, the caller sent another one
+    VersionMismatch {
+        client_id: i32,
+        version: u64,
+    },
+    // header the request must send
+    PreconditionRequired(String),
+    // client id and its status, credits and debits need an active account
+    AccountNotActive {
+        client_id: i32,
+        status: ClientStatus,
+    },
+    // client id, current status and requested status
+    InvalidStatusTransition {
+        client_id: i32,
+        from: ClientStatus,
+        to: ClientStatus,
+    },
+    // client id and its balance, an account is only closed with a zero balance
+    BalanceNotZero {
+        client_id: i32,
+        balance: Decimal,
+    },
+    // client id and the amount reserved by its active holds, an account is only closed without active holds
+    HeldBalanceNotZero {
+        client_id: i32,
+        held_balance: Decimal,
+    },
+    // client id, balance still available under its overdraft limit and the rejected debit
+    InsufficientFunds {
+        client_id: i32,
+        available_balance: Decimal,
+        amount: Decimal,
+    },
+    // hold id and its status, only active holds are captured or released
+    HoldNotActive {
+        hold_id: i32,
+        status: HoldStatus,
+    },
+    // transaction id, its whole amount was already reversed
+    TransactionAlreadyReversed(i32),
+    // position of the item rejecting an all or nothing batch and its error
+    BatchItemRejected {
+        index: usize,
+        error: Box<CommonError>,
+    },
+}
+
+/// Constructors shared by the validators
+impl CommonError {
+    /// Build the [CommonError] of an invalid [str] field for [String] reason and log it
+    pub fn invalid_field(field: &str, reason: String) -> Self {
+        error!("validate - error - invalid field: {field} - reason: {reason}");
+        CommonError::InvalidField {
+            field: field.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Error information exposed to the callers
+impl CommonError {
+    /// Machine readable code of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            CommonError::ClientNotFound(_) => "CLIENT_NOT_FOUND",
+            CommonError::HoldNotFound(_) => "HOLD_NOT_FOUND",
+            CommonError::TransactionNotFound(_) => "TRANSACTION_NOT_FOUND",
+            CommonError::BalanceFileNotFound(_) => "BALANCE_FILE_NOT_FOUND",
+            CommonError::FolderCreationFailed(_) => "FOLDER_CREATION_FAILED",
+            CommonError::FolderReadFailed(_) => "FOLDER_READ_FAILED",
+            CommonError::LockReadFailed => "LOCK_READ_FAILED",
+            CommonError::LockWriteFailed => "LOCK_WRITE_FAILED",
+            CommonError::FileCreationFailed(_) => "FILE_CREATION_FAILED",
+            CommonError::FileWriteFailed(_) => "FILE_WRITE_FAILED",
+            CommonError::FileReadFailed(_) => "FILE_READ_FAILED",
+            CommonError::DocumentAlreadyExists(_) => "DOCUMENT_ALREADY_EXISTS",
+            CommonError::DocumentNotFound(_) => "DOCUMENT_NOT_FOUND",
+            CommonError::IdempotencyKeyConflict(_) => "IDEMPOTENCY_KEY_CONFLICT",
+            CommonError::InvalidField { .. } => "INVALID_FIELD",
+            CommonError::StorageFailed(_) => "STORAGE_FAILED",
+            CommonError::InvalidBalanceFile(_) => "INVALID_BALANCE_FILE",
+            CommonError::VersionMismatch { .. } => "VERSION_MISMATCH",
+            CommonError::PreconditionRequired(_) => "PRECONDITION_REQUIRED",
+            CommonError::AccountNotActive { .. } => "ACCOUNT_NOT_ACTIVE",
+            CommonError::InvalidStatusTransition { .. } => "INVALID_STATUS_TRANSITION",
+            CommonError::BalanceNotZero { .. } => "BALANCE_NOT_ZERO",
+            CommonError::HeldBalanceNotZero { .. } => "HELD_BALANCE_NOT_ZERO",
+            CommonError::InsufficientFunds { .. } => "INSUFFICIENT_FUNDS",
+            CommonError::HoldNotActive { .. } => "HOLD_NOT_ACTIVE",
+            CommonError::TransactionAlreadyReversed(_) => "TRANSACTION_ALREADY_REVERSED",
+            CommonError::BatchItemRejected { .. } => "BATCH_ITEM_REJECTED",
+        }
+    }
+
+    /// Context of the error, if any
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            CommonError::ClientNotFound(client_id) => Some(json!({ "client_id": client_id })),
+            CommonError::HoldNotFound(hold_id) => Some(json!({ "hold_id": hold_id })),
+            CommonError::TransactionNotFound(transaction_id)
+            | CommonError::TransactionAlreadyReversed(transaction_id) => {
+                Some(json!({ "transaction_id": transaction_id }))
+            }
+            CommonError::FolderCreationFailed(folder) | CommonError::FolderReadFailed(folder) => {
+                Some(json!({ "folder": folder }))
+            }
+            CommonError::FileCreationFailed(file_name)
+            | CommonError::FileWriteFailed(file_name)
+            | CommonError::FileReadFailed(file_name)
+            | CommonError::BalanceFileNotFound(file_name) => {
+                Some(json!({ "file_name": file_name }))
+            }
+            CommonError::DocumentAlreadyExists(document_number)
+            | CommonError::DocumentNotFound(document_number) => {
+                Some(json!({ "document_number": document_number }))
+            }
+            CommonError::IdempotencyKeyConflict(idempotency_key) => {
+                Some(json!({ "idempotency_key": idempotency_key }))
+            }
+            CommonError::InvalidField { field, reason } => {
+                Some(json!({ "field": field, "reason": reason }))
+            }
+            CommonError::InvalidBalanceFile(reason) => Some(json!({ "reason": reason })),
+            CommonError::VersionMismatch { client_id, version } => {
+                Some(json!({ "client_id": client_id, "version": version }))
+            }
+            CommonError::PreconditionRequired(header) => Some(json!({ "header": header })),
+            CommonError::AccountNotActive { client_id, status } => {
+                Some(json!({ "client_id": client_id, "status": status }))
+            }
+            CommonError::InvalidStatusTransition {
+                client_id,
+                from,
+                to,
+            } => Some(json!({ "client_id": client_id, "from": from, "to": to })),
+            CommonError::BalanceNotZero { client_id, balance } => {
+                Some(json!({ "client_id": client_id, "balance": balance }))
+            }
+            CommonError::HeldBalanceNotZero {
+                client_id,
+                held_balance,
+            } => Some(json!({ "client_id": client_id, "held_balance": held_balance })),
+            CommonError::InsufficientFunds {
+                client_id,
+                available_balance,
+                amount,
+            } => Some(json!({
+                "client_id": client_id,
+                "available_balance": available_balance,
+                "amount": amount
+            })),
+            CommonError::HoldNotActive { hold_id, status } => {
+                Some(json!({ "hold_id": hold_id, "status": status }))
+            }
+            CommonError::BatchItemRejected { index, error } => Some(json!({
+                "index": index,
+                "code": error.code(),
+                "message": error.to_string(),
+                "details": error.details(),
+            })),
+            CommonError::LockReadFailed
+            | CommonError::LockWriteFailed
+            | CommonError::StorageFailed(_) => None,
+        }
+    }
+}
+
+/// Human readable message of the error
+impl fmt::Display for CommonError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommonError::ClientNotFound(client_id) => {
+                write!(formatter, "Client {client_id} not found")
+            }
+            CommonError::HoldNotFound(hold_id) => write!(formatter, "Hold {hold_id} not found"),
+            CommonError::TransactionNotFound(transaction_id) => {
+                write!(formatter, "Transaction {transaction_id} not found")
+            }
+            CommonError::BalanceFileNotFound(file_name) => {
+                write!(formatter, "Balance file {file_name} not found")
+            }
+            CommonError::FolderCreationFailed(folder) => {
+                write!(formatter, "Error when creating folder {folder}")
+            }
+            CommonError::FolderReadFailed(folder) => {
+                write!(formatter, "Error when reading folder {folder}")
+            }
+            CommonError::LockReadFailed => write!(formatter, "Error when reading app_state"),
+            CommonError::LockWriteFailed => write!(formatter, "Error when writing app_state"),
+            CommonError::FileCreationFailed(file_name) => {
+                write!(formatter, "Error when creating file {file_name}")
+            }
+            CommonError::FileWriteFailed(file_name) => {
+                write!(formatter, "Error when writing to the file {file_name}")
+            }
+            CommonError::FileReadFailed(file_name) => {
+                write!(formatter, "Error when reading the file {file_name}")
+            }
+            CommonError::DocumentAlreadyExists(document_number) => {
+                write!(
+                    formatter,
+                    "The document number {document_number} already exists"
+                )
+            }
+            CommonError::DocumentNotFound(document_number) => {
+                write!(
+                    formatter,
+                    "Client with document number {document_number} not found"
+                )
+            }
+            CommonError::IdempotencyKeyConflict(idempotency_key) => write!(
+                formatter,
+                "The idempotency key {idempotency_key} was already used with a different payload"
+            ),
+            CommonError::InvalidField { field, reason } => {
+                write!(formatter, "Invalid {field}: {reason}")
+            }
+            CommonError::StorageFailed(reason) => {
+                write!(formatter, "Error when accessing the storage: {reason}")
+            }
+            CommonError::InvalidBalanceFile(reason) => {
+                write!(formatter, "Invalid balance file: {reason}")
+            }
+            CommonError::VersionMismatch { client_id, version } => write!(
+                formatter,
+                "Client {client_id} was changed by another request, its current version is {version}"
+            ),
+            CommonError::PreconditionRequired(header) => {
+                write!(formatter, "The {header} header is required")
+            }
+            CommonError::AccountNotActive { client_id, status } => write!(
+                formatter,
+                "The account of client {client_id} is {}",
+                status.as_str()
+            ),
+            CommonError::InvalidStatusTransition {
+                client_id,
+                from,
+                to,
+            } => write!(
+                formatter,
+                "The account of client {client_id} cannot change from {} to {}",
+                from.as_str(),
+                to.as_str()
+            ),
+            CommonError::BalanceNotZero { client_id, balance } => write!(
+                formatter,
+                "The account of client {client_id} has a balance of {balance}, it must be zero"
+            ),
+            CommonError::HeldBalanceNotZero {
+                client_id,
+                held_balance,
+            } => write!(
+                formatter,
+                "The account of client {client_id} has {held_balance} held by active holds, they must be captured or released"
+            ),
+            CommonError::InsufficientFunds {
+                client_id,
+                available_balance,
+                amount,
+            } => write!(
+                formatter,
+                "Insufficient funds on the account of client {client_id}: {amount} requested, {available_balance} available"
+            ),
+            CommonError::HoldNotActive { hold_id, status } => {
+                write!(formatter, "Hold {hold_id} is not active, it is {status:?}")
+            }
+            CommonError::TransactionAlreadyReversed(transaction_id) => write!(
+                formatter,
+                "Transaction {transaction_id} was already fully reversed"
+            ),
+            CommonError::BatchItemRejected { index, error } => write!(
+                formatter,
+                "The batch was not applied, item {index} was rejected: {error}"
+            ),
+        }
+    }
+}
+
+/// Maps every [CommonError] to a JSON [ErrorResponse] with the proper HTTP status
+impl ResponseError for CommonError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            // the batch fails as its rejected item would
+            CommonError::BatchItemRejected { error, .. } => error.status_code(),
+            CommonError::ClientNotFound(_)
+            | CommonError::HoldNotFound(_)
+            | CommonError::TransactionNotFound(_)
+            | CommonError::BalanceFileNotFound(_)
+            | CommonError::DocumentNotFound(_) => StatusCode::NOT_FOUND,
+            CommonError::DocumentAlreadyExists(_) => StatusCode::FORBIDDEN,
+            CommonError::IdempotencyKeyConflict(_) | CommonError::InsufficientFunds { .. } => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            CommonError::InvalidField { .. } => StatusCode::BAD_REQUEST,
+            CommonError::VersionMismatch { .. } => StatusCode::PRECONDITION_FAILED,
+            CommonError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            CommonError::AccountNotActive { .. }
+            | CommonError::InvalidStatusTransition { .. }
+            | CommonError::BalanceNotZero { .. }
+            | CommonError::HeldBalanceNotZero { .. }
+            | CommonError::HoldNotActive { .. }
+            | CommonError::TransactionAlreadyReversed(_) => StatusCode::CONFLICT,
+            CommonError::FolderCreationFailed(_)
+            | CommonError::FolderReadFailed(_)
+            | CommonError::LockReadFailed
+            | CommonError::LockWriteFailed
+            | CommonError::FileCreationFailed(_)
+            | CommonError::FileWriteFailed(_)
+            | CommonError::FileReadFailed(_)
+            | CommonError::StorageFailed(_)
+            | CommonError::InvalidBalanceFile(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let request_id = Uuid::new_v4().to_string();
+        error!(
+            "error_response - request_id: {request_id} - code: {} - message: {self}",
+            self.code()
+        );
+
+        HttpResponse::build(self.status_code())
+            .insert_header((REQUEST_ID_HEADER, request_id.clone()))
+            .json(ErrorResponse {
+                code: self.code().to_string(),
+                message: self.to_string(),
+                details: self.details(),
+                request_id,
+            })
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+
+    /// Scenario:
+    /// Executes error_response with a [CommonError::ClientNotFound]
+    /// Expectation:
+    /// A 404 JSON envelope naming the client id should be returned
+    #[actix_web::test]
+    async fn when_error_response_with_client_not_found_should_return_json_envelope() {
+        let error = CommonError::ClientNotFound(3);
+
+        let response = error.error_response();
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        let request_id = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let error_response: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!("CLIENT_NOT_FOUND", error_response.code);
+        assert_eq!("Client 3 not found", error_response.message);
+        assert_eq!(Some(json!({ "client_id": 3 })), error_response.details);
+        assert_eq!(request_id, error_response.request_id);
+    }
+
+    /// Scenario:
+    /// Executes status_code with every kind of [CommonError]
+    /// Expectation:
+    /// The proper HTTP status should be returned
+    #[test]
+    fn when_status_code_should_return_status_per_variant() {
+        let invalid_field = CommonError::InvalidField {
+            field: "credit_amount".to_string(),
+            reason: "must be greater than zero".to_string(),
+        };
+
+        assert_eq!(StatusCode::BAD_REQUEST, invalid_field.status_code());
+        assert_eq!(
+            StatusCode::FORBIDDEN,
+            CommonError::DocumentAlreadyExists("123".to_string()).status_code()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            CommonError::DocumentNotFound("123".to_string()).status_code()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            CommonError::HoldNotFound(1).status_code()
+        );
+        assert_eq!(
+            StatusCode::CONFLICT,
+            CommonError::HoldNotActive {
+                hold_id: 1,
+                status: HoldStatus::Captured
+            }
+            .status_code()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            CommonError::TransactionNotFound(1).status_code()
+        );
+        assert_eq!(
+            StatusCode::CONFLICT,
+            CommonError::TransactionAlreadyReversed(1).status_code()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            CommonError::BalanceFileNotFound("01122023_1.DAT".to_string()).status_code()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            CommonError::BatchItemRejected {
+                index: 2,
+                error: Box::new(CommonError::ClientNotFound(1))
+            }
+            .status_code()
+        );
+        assert_eq!(
+            StatusCode::PRECONDITION_FAILED,
+            CommonError::VersionMismatch {
+                client_id: 1,
+                version: 2
+            }
+            .status_code()
+        );
+        assert_eq!(
+            StatusCode::PRECONDITION_REQUIRED,
+            CommonError::PreconditionRequired("If-Match".to_string()).status_code()
+        );
+        assert_eq!(
+            StatusCode::CONFLICT,
+            CommonError::AccountNotActive {
+                client_id: 1,
+                status: ClientStatus::Blocked
+            }
+            .status_code()
+        );
+        assert_eq!(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            CommonError::IdempotencyKeyConflict("key".to_string()).status_code()
+        );
+        assert_eq!(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            CommonError::InsufficientFunds {
+                client_id: 1,
+                available_balance: Decimal::new(500, 2),
+                amount: Decimal::new(1000, 2)
+            }
+            .status_code()
+        );
+        assert_eq!(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            CommonError::LockReadFailed.status_code()
+        );
+        assert!(CommonError::LockReadFailed.details().is_none());
+    }
 }
@@ -1,9 +1,15 @@
+pub mod balance_file;
+pub mod client_import;
+pub mod config;
 pub mod constants;
 pub mod controller;
 pub mod dto;
 pub mod errors;
 pub mod mapper;
 pub mod model;
+pub mod repository;
 pub mod service;
 pub mod state;
+pub mod statement;
 pub mod stub;
+pub mod validator;
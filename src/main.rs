@@ -1,28 +1,121 @@
 use actix_web::{web, App, HttpServer};
-use env_logger;
+use clap::Parser;
+use log::{error, info};
+use prex_core_challenge::client_import::client_import_format::ClientImportFormat;
+use prex_core_challenge::config::app_config::{AppConfig, CliArgs, Command};
+use prex_core_challenge::constants::constants::HOLD_EXPIRY_INTERVAL_SECONDS;
 use prex_core_challenge::controller::client_controller::ClientController;
-use prex_core_challenge::service::client_service::{ClientService, DynClientService};
+use prex_core_challenge::errors::common_error::CommonError;
+use prex_core_challenge::model::event_model::{Event, EventRecord};
+use prex_core_challenge::service::client_service::{
+    ClientService, ClientServiceTrait, DynClientService,
+};
 use prex_core_challenge::state::app_state::AppState;
-use std::collections::HashMap;
-use std::sync::atomic::AtomicI32;
+use prex_core_challenge::state::event_log::EventLog;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
-use std::sync::RwLock;
+use std::time::Duration;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "debug");
-    env_logger::init();
+    let mut cli_args = CliArgs::parse();
+    let command = cli_args.command.take();
+    // config file, overridden by environment variables, overridden by command line flags
+    let app_config = match AppConfig::load(cli_args) {
+        Ok(app_config) => app_config,
+        Err(error) => {
+            eprintln!("invalid configuration - {error}");
+            std::process::exit(1);
+        }
+    };
+    let balance_file_name_pattern = app_config
+        .balance_file_name_pattern()
+        .map_err(to_io_error)?;
+    let overdraft_limits = app_config.overdraft_limits().map_err(to_io_error)?;
+    env_logger::Builder::new()
+        .filter_level(app_config.level_filter().map_err(to_io_error)?)
+        .init();
+    info!("configuration: {app_config:?}");
 
     // declarate AppState
-    let app_state = AppState {
-        clients: Arc::new(RwLock::new(HashMap::new())),
-        client_id_unique: AtomicI32::new(1),
+    let mut app_state = AppState {
+        balance_folder: app_config.balance_folder.clone(),
+        balance_file_name_pattern,
+        balance_file_format: app_config.balance_file_format,
+        transaction_limits: app_config.transaction_limits(),
+        overdraft_limits,
+        ..Default::default()
     };
-    
+
+    // read the changes applied before the last shutdown and keep logging the new ones
+    let events = EventLog::read_all(&app_config.event_log_path).map_err(to_io_error)?;
+    app_state.event_log = Some(EventLog::open(&app_config.event_log_path).map_err(to_io_error)?);
+
     let share_state = Arc::new(app_state);
 
+    // balance files loaded on a previous startup are not loaded again
+    let loaded_file_names: HashSet<String> = events
+        .iter()
+        .filter_map(|record| match &record.event {
+            Event::OpeningBalancesLoaded { file_name, .. } => Some(file_name.clone()),
+            _ => None,
+        })
+        .collect();
+
     // create service
-    let client_service: DynClientService = Arc::new(ClientService::new(share_state.clone()));
+    let client_service = create_client_service(share_state.clone(), events)?;
+
+    // run the command on the restored state instead of starting the server
+    match command {
+        Some(Command::ImportClients {
+            file,
+            format,
+            report,
+        }) => {
+            return import_clients(&client_service, &file, format.as_deref(), report.as_deref())
+                .await
+                .map_err(to_io_error);
+        }
+        Some(Command::ReconcileBalanceFile { file, report }) => {
+            let balanced = reconcile_balance_file(&client_service, &file, report.as_deref())
+                .map_err(to_io_error)?;
+            if !balanced {
+                return Err(std::io::Error::other(format!(
+                    "{file} does not match the ledger"
+                )));
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    // opt-in load of the most recent balance file as opening balances
+    if app_config.restore_from_balance_file {
+        if let Some(report) = client_service
+            .restore_from_latest_balance_file(&loaded_file_names)
+            .await
+            .map_err(to_io_error)?
+        {
+            info!(
+                "opening balances loaded from {} - clients: {:?}",
+                report.file_name, report.restored_client_ids
+            );
+            if !report.missing_client_ids.is_empty() {
+                error!(
+                    "clients in {} missing from the current state: {:?}",
+                    report.file_name, report.missing_client_ids
+                );
+            }
+        }
+    }
+    let client_service = Arc::new(client_service);
+
+    // release the expired holds in the background
+    Arc::clone(&client_service)
+        .spawn_hold_expiry_task(Duration::from_secs(HOLD_EXPIRY_INTERVAL_SECONDS));
+    let client_service: DynClientService = client_service;
 
     // create controller
     let client_controller = ClientController::new(client_service.clone());
@@ -30,9 +123,175 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(client_service.clone()))
+            .service(client_controller.create_clients_routes())
             .service(client_controller.create_routes())
     })
-    .bind("127.0.0.1:8080")?
+    .workers(app_config.workers)
+    .bind(app_config.bind_address())?
     .run()
     .await
 }
+
+/// Client service keeping the clients in memory, rebuilt from [EventRecord] events
+#[cfg(not(feature = "sqlite"))]
+fn create_client_service(
+    app_state: Arc<AppState>,
+    events: Vec<EventRecord>,
+) -> std::io::Result<ClientService> {
+    restore_in_memory_client_service(app_state, events)
+}
+
+/// Client service keeping the clients in memory, rebuilt from [EventRecord] events
+fn restore_in_memory_client_service(
+    app_state: Arc<AppState>,
+    events: Vec<EventRecord>,
+) -> std::io::Result<ClientService> {
+    let client_service = ClientService::new(app_state);
+    let restored_events = client_service
+        .restore_from_event_log(events)
+        .map_err(to_io_error)?;
+    info!("state restored from the event log - events: {restored_events}");
+    Ok(client_service)
+}
+
+/// Create the clients of [str] file in [str] format, read from its extension when None,
+/// and write the import report to [str] report_file, printed when None
+/// Returns a [CommonError] if the format is unknown, the file cannot be read or the report cannot be written
+async fn import_clients(
+    client_service: &ClientService,
+    file: &str,
+    format: Option<&str>,
+    report_file: Option<&str>,
+) -> Result<(), CommonError> {
+    let format = match format {
+        Some(format) => format.parse()?,
+        None => {
+            ClientImportFormat::from_file_name(file).ok_or_else(|| CommonError::InvalidField {
+                field: "format".to_string(),
+                reason: format!(
+                    "cannot be read from the extension of {file}, use --format csv or ndjson"
+                ),
+            })?
+        }
+    };
+    let content =
+        std::fs::read_to_string(file).map_err(|_| CommonError::FileReadFailed(file.to_string()))?;
+
+    let report = client_service.import_clients(content, format).await?;
+    info!(
+        "clients imported from {file} - created: {} - rejected: {}",
+        report.created_count, report.rejected_count
+    );
+    write_report(&report, report_file)
+}
+
+/// Check the balances of [str] file against the movements recorded before it was written,
+/// and write the reconciliation report to [str] report_file, printed when None
+/// Returns true when every balance matches, or a [CommonError] if the file cannot be read or reconciled
+/// or the report cannot be written
+fn reconcile_balance_file(
+    client_service: &ClientService,
+    file: &str,
+    report_file: Option<&str>,
+) -> Result<bool, CommonError> {
+    let content =
+        std::fs::read_to_string(file).map_err(|_| CommonError::FileReadFailed(file.to_string()))?;
+    // balance files are registered by name, wherever they were moved to
+    let file_name = Path::new(file)
+        .file_name()
+        .map_or(file.to_string(), |file_name| {
+            file_name.to_string_lossy().to_string()
+        });
+
+    let report = client_service.reconcile_balance_content(&file_name, &content)?;
+    if report.is_balanced() {
+        info!("{file} reconciled - clients: {}", report.checked_clients);
+    } else {
+        error!(
+            "{file} does not match the ledger - mismatches: {:?}",
+            report.mismatches
+        );
+    }
+    write_report(&report, report_file)?;
+    Ok(report.is_balanced())
+}
+
+/// Write [Serialize] report as JSON to [str] report_file, printed when None
+/// Returns a [CommonError] if the report cannot be written
+fn write_report<T: Serialize>(report: &T, report_file: Option<&str>) -> Result<(), CommonError> {
+    let report_json = serde_json::to_string_pretty(report)
+        .map_err(|error| CommonError::StorageFailed(error.to_string()))?;
+    match report_file {
+        Some(report_file) => std::fs::write(report_file, report_json)
+            .map_err(|_| CommonError::FileWriteFailed(report_file.to_string())),
+        None => {
+            println!("{report_json}");
+            Ok(())
+        }
+    }
+}
+
+/// Map a [CommonError] raised while starting the server to [std::io::Error]
+fn to_io_error(error: CommonError) -> std::io::Error {
+    error!("main - error: {:?}", error);
+    std::io::Error::other(error.to_string())
+}
+
+/// Client service keeping the clients in the SQLite database of [SQLITE_PATH_ENV], in memory if it is not set
+/// The database already keeps the clients, so [EventRecord] events only rebuild the ledger, the holds and the ids
+#[cfg(feature = "sqlite")]
+fn create_client_service(
+    app_state: Arc<AppState>,
+    events: Vec<EventRecord>,
+) -> std::io::Result<ClientService> {
+    use prex_core_challenge::constants::constants::SQLITE_PATH_ENV;
+    use prex_core_challenge::repository::client_repository::ClientRepository;
+    use prex_core_challenge::repository::sqlite_client_repository::SqliteClientRepository;
+    use rust_decimal::Decimal;
+    use std::collections::HashMap;
+    use std::sync::atomic::Ordering;
+
+    let Ok(path) = std::env::var(SQLITE_PATH_ENV) else {
+        info!("{SQLITE_PATH_ENV} is not set, clients are kept in memory");
+        return restore_in_memory_client_service(app_state, events);
+    };
+
+    let client_repository = Arc::new(SqliteClientRepository::open(&path).map_err(to_io_error)?);
+    let clients = client_repository.list().map_err(to_io_error)?;
+    let client_service =
+        ClientService::with_repository(app_state.clone(), client_repository.clone());
+    let replayed_clients: HashMap<i32, Decimal> = client_service
+        .restore_ledger_from_event_log(events)
+        .map_err(to_io_error)?
+        .into_iter()
+        .map(|client| (client.client_id, client.held_balance))
+        .collect();
+
+    // holds are only kept in memory, the held balances follow the holds rebuilt from the event log
+    for client in &clients {
+        let held_balance = replayed_clients
+            .get(&client.client_id)
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        if client.held_balance != held_balance {
+            client_repository
+                .update_held_balance(client.client_id, held_balance)
+                .map_err(to_io_error)?;
+            info!(
+                "held balance restored - client id: {} - held balance: {} - saved held balance: {}",
+                client.client_id, held_balance, client.held_balance
+            );
+        }
+    }
+    // keep generating ids after the last saved client
+    let last_client_id = clients.last().map_or(0, |client| client.client_id);
+    app_state
+        .client_id_unique
+        .fetch_max(last_client_id + 1, Ordering::SeqCst);
+    info!(
+        "clients saved in {path} - last client id: {last_client_id} - next transaction id: {}",
+        app_state.transaction_id_unique.load(Ordering::SeqCst)
+    );
+
+    Ok(client_service)
+}
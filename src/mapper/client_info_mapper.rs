@@ -3,6 +3,7 @@ use crate::model::client_model::Client;
 
 /// Maps an [ClientInfo] from [Client] and [i32] client_id
 pub fn map_client_info(client: Client) -> ClientInfo {
+    let available_balance = client.available_balance();
     ClientInfo {
         client_id: client.client_id,
         client_name: client.client_name,
@@ -10,6 +11,9 @@ pub fn map_client_info(client: Client) -> ClientInfo {
         document_number: client.document_number,
         country: client.country,
         balance: client.balance,
+        available_balance,
+        version: client.version,
+        status: client.status,
     }
 }
 /// Unit tests cases
@@ -18,6 +22,7 @@ mod tests {
     use crate::mapper::client_info_mapper::map_client_info;
     use crate::model::client_model::Client;
     use crate::stub::client_info_stub::stub::create_client_info_stub;
+    use rust_decimal::Decimal;
 
     /// Scenario:
     /// Executes map_client_info with valid parameters
@@ -34,6 +39,9 @@ mod tests {
             document_number: expected_client_info.document_number.clone(),
             country: expected_client_info.country.clone(),
             balance: expected_client_info.balance,
+            version: expected_client_info.version,
+            status: expected_client_info.status,
+            held_balance: Decimal::ZERO,
         };
 
         let result = map_client_info(client_request);
@@ -44,5 +52,9 @@ mod tests {
         assert_eq!(expected_client_info.document_number, result.document_number);
         assert_eq!(expected_client_info.country, result.country);
         assert_eq!(expected_client_info.balance, result.balance);
+        assert_eq!(
+            expected_client_info.available_balance,
+            result.available_balance
+        );
     }
 }
@@ -0,0 +1,63 @@
+use crate::dto::hold_info_dto::HoldInfo;
+use crate::model::client_model::Client;
+use crate::model::hold_model::Hold;
+
+/// Maps an [HoldInfo] from [Hold] and the balances of its [Client] client
+pub fn map_hold_info(hold: Hold, client: &Client) -> HoldInfo {
+    HoldInfo {
+        hold_id: hold.hold_id,
+        client_id: hold.client_id,
+        amount: hold.amount,
+        captured_amount: hold.captured_amount,
+        status: hold.status,
+        expires_at: hold.expires_at,
+        balance: client.balance,
+        available_balance: client.available_balance(),
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::constants::constants::INITIAL_CLIENT_VERSION;
+    use crate::mapper::hold_info_mapper::map_hold_info;
+    use crate::model::client_model::{Client, ClientStatus};
+    use crate::model::hold_model::Hold;
+    use crate::stub::hold_info_stub::stub::create_hold_info_stub;
+    use crate::stub::new_client_stub::stub::create_new_client_stub;
+    use chrono::Utc;
+
+    /// Scenario:
+    /// Executes map_hold_info with valid parameters
+    /// Expectation:
+    /// A [HoldInfo] with the hold and the client balances should be returned
+    #[test]
+    fn when_map_hold_info_should_return_hold_info_struct() {
+        let expected_hold_info = create_hold_info_stub();
+        let new_client = create_new_client_stub();
+        let client = Client {
+            client_id: expected_hold_info.client_id,
+            client_name: new_client.client_name,
+            birth_date: new_client.birth_date,
+            document_number: new_client.document_number,
+            country: new_client.country,
+            balance: expected_hold_info.balance,
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: expected_hold_info.amount,
+        };
+        let hold = Hold {
+            hold_id: expected_hold_info.hold_id,
+            client_id: expected_hold_info.client_id,
+            amount: expected_hold_info.amount,
+            captured_amount: expected_hold_info.captured_amount,
+            status: expected_hold_info.status,
+            created_at: Utc::now(),
+            expires_at: expected_hold_info.expires_at,
+        };
+
+        let result = map_hold_info(hold, &client);
+
+        assert_eq!(expected_hold_info, result);
+    }
+}
@@ -1,2 +1,5 @@
 pub mod client_info_mapper;
+pub mod hold_info_mapper;
 pub mod new_client_mapper;
+pub mod statement_line_mapper;
+pub mod transaction_info_mapper;
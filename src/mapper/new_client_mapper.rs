@@ -1,5 +1,6 @@
+use crate::constants::constants::INITIAL_CLIENT_VERSION;
 use crate::dto::new_client_dto::NewClient;
-use crate::model::client_model::Client;
+use crate::model::client_model::{Client, ClientStatus};
 use rust_decimal::Decimal;
 
 /// Maps an [Client] from [NewClient] and [i32] client_id
@@ -11,6 +12,9 @@ pub fn map_client(new_client: NewClient, client_id: i32) -> Client {
         document_number: new_client.document_number,
         country: new_client.country,
         balance: Decimal::new(0, 0),
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
     }
 }
 
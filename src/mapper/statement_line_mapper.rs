@@ -0,0 +1,52 @@
+use crate::dto::client_statement_dto::StatementLine;
+use crate::model::transaction_model::Transaction;
+
+/// Maps a [StatementLine] from [Transaction], with the amount signed by its effect on the balance
+pub fn map_statement_line(transaction: &Transaction) -> StatementLine {
+    StatementLine {
+        transaction_id: transaction.transaction_id,
+        created_at: transaction.created_at,
+        kind: transaction.kind,
+        amount: transaction.kind.balance_change(transaction.amount),
+        balance: transaction.balance,
+        reversal_of: transaction.reversal_of,
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::mapper::statement_line_mapper::map_statement_line;
+    use crate::model::transaction_model::{Transaction, TransactionKind};
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use crate::stub::transaction_info_stub::stub::{
+        TRANSACTION_AMOUNT, TRANSACTION_CREATED_AT, TRANSACTION_ID,
+    };
+    use rust_decimal::Decimal;
+
+    /// Scenario:
+    /// Executes map_statement_line with a debit
+    /// Expectation:
+    /// A [StatementLine] with a negative amount should be returned
+    #[test]
+    fn when_map_statement_line_with_debit_should_return_negative_amount() {
+        let transaction = Transaction {
+            transaction_id: TRANSACTION_ID,
+            client_id: CLIENT_ID,
+            kind: TransactionKind::Debit,
+            amount: *TRANSACTION_AMOUNT,
+            balance: Decimal::ZERO,
+            created_at: *TRANSACTION_CREATED_AT,
+            reversal_of: None,
+            reversed_amount: Decimal::ZERO,
+        };
+
+        let result = map_statement_line(&transaction);
+
+        assert_eq!(TRANSACTION_ID, result.transaction_id);
+        assert_eq!(TransactionKind::Debit, result.kind);
+        assert_eq!(-*TRANSACTION_AMOUNT, result.amount);
+        assert_eq!(Decimal::ZERO, result.balance);
+        assert_eq!(*TRANSACTION_CREATED_AT, result.created_at);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::dto::transaction_info_dto::TransactionInfo;
+use crate::model::transaction_model::Transaction;
+
+/// Maps an [TransactionInfo] from [Transaction]
+pub fn map_transaction_info(transaction: Transaction) -> TransactionInfo {
+    TransactionInfo {
+        transaction_id: transaction.transaction_id,
+        client_id: transaction.client_id,
+        kind: transaction.kind,
+        amount: transaction.amount,
+        balance: transaction.balance,
+        created_at: transaction.created_at,
+        reversal_of: transaction.reversal_of,
+        reversed_amount: transaction.reversed_amount,
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use crate::mapper::transaction_info_mapper::map_transaction_info;
+    use crate::model::transaction_model::Transaction;
+    use crate::stub::transaction_info_stub::stub::create_transaction_info_stub;
+
+    /// Scenario:
+    /// Executes map_transaction_info with valid parameters
+    /// Expectation:
+    /// A [TransactionInfo] should be returned
+    #[test]
+    fn when_map_transaction_info_should_return_transaction_info_struct() {
+        let expected_transaction_info = create_transaction_info_stub();
+
+        let transaction = Transaction {
+            transaction_id: expected_transaction_info.transaction_id,
+            client_id: expected_transaction_info.client_id,
+            kind: expected_transaction_info.kind,
+            amount: expected_transaction_info.amount,
+            balance: expected_transaction_info.balance,
+            created_at: expected_transaction_info.created_at,
+            reversal_of: expected_transaction_info.reversal_of,
+            reversed_amount: expected_transaction_info.reversed_amount,
+        };
+
+        let result = map_transaction_info(transaction);
+
+        assert_eq!(expected_transaction_info, result);
+    }
+}
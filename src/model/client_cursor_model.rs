@@ -0,0 +1,150 @@
+use crate::dto::client_filter_dto::ClientSortField;
+use crate::errors::common_error::CommonError;
+use crate::model::client_model::Client;
+use std::cmp::Ordering;
+
+/// Position of the last client of a page, sent to the caller as an opaque hex string
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCursor {
+    // listing order the cursor was created for
+    pub sort_by: ClientSortField,
+    // id of the last client of the page
+    pub client_id: i32,
+    // name of the last client of the page, only kept when sorting by name
+    pub client_name: Option<String>,
+}
+
+/// Implementation Client cursor
+impl ClientCursor {
+    /// Cursor pointing at [Client] client for [ClientSortField] sort_by
+    pub fn after(client: &Client, sort_by: ClientSortField) -> Self {
+        ClientCursor {
+            sort_by,
+            client_id: client.client_id,
+            client_name: match sort_by {
+                ClientSortField::Id => None,
+                ClientSortField::Name => Some(client.client_name.clone()),
+            },
+        }
+    }
+
+    /// Hex string handed to the caller as next_cursor
+    pub fn encode(&self) -> String {
+        let position = match &self.client_name {
+            Some(client_name) => format!("name:{}:{}", self.client_id, client_name),
+            None => format!("id:{}", self.client_id),
+        };
+        position
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Read a cursor from [str] cursor for [ClientSortField] sort_by
+    /// Returns a [CommonError] if the cursor is malformed or was created for another order
+    pub fn decode(cursor: &str, sort_by: ClientSortField) -> Result<Self, CommonError> {
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|index| {
+                cursor
+                    .get(index..index + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid_cursor)?;
+        let position = String::from_utf8(bytes).map_err(|_| invalid_cursor())?;
+
+        let (client_id, client_name) = match (sort_by, position.split_once(':')) {
+            (ClientSortField::Id, Some(("id", client_id))) => (client_id, None),
+            (ClientSortField::Name, Some(("name", rest))) => {
+                let (client_id, client_name) = rest.split_once(':').ok_or_else(invalid_cursor)?;
+                (client_id, Some(client_name.to_string()))
+            }
+            _ => return Err(invalid_cursor()),
+        };
+
+        Ok(ClientCursor {
+            sort_by,
+            client_id: client_id.parse().map_err(|_| invalid_cursor())?,
+            client_name,
+        })
+    }
+
+    /// Whether [Client] client comes after this cursor in the listing order
+    pub fn is_before(&self, client: &Client) -> bool {
+        match &self.client_name {
+            Some(client_name) => {
+                compare_by_name(client_name, self.client_id, client) == Ordering::Less
+            }
+            None => self.client_id < client.client_id,
+        }
+    }
+}
+
+/// Order of [Client] client by name and then id, against [str] client_name and [i32] client_id
+pub fn compare_by_name(client_name: &str, client_id: i32, client: &Client) -> Ordering {
+    (client_name, client_id).cmp(&(client.client_name.as_str(), client.client_id))
+}
+
+/// Error returned for a cursor that cannot be read
+fn invalid_cursor() -> CommonError {
+    CommonError::InvalidField {
+        field: "cursor".to_string(),
+        reason: "must be the next_cursor of a previous page with the same sort_by".to_string(),
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::constants::INITIAL_CLIENT_VERSION;
+    use crate::model::client_model::ClientStatus;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    /// Get Client model
+    fn create_client(client_id: i32, client_name: &str) -> Client {
+        Client {
+            client_id,
+            client_name: client_name.to_string(),
+            birth_date: NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+            document_number: format!("DOC-{}", client_id),
+            country: "AR".to_string(),
+            balance: Decimal::ZERO,
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        }
+    }
+
+    /// Scenario:
+    /// Encode a [ClientCursor] sorted by name and decode it again
+    /// Expectation:
+    /// The same cursor should be returned and it should only accept the same order
+    #[test]
+    fn when_client_cursor_is_encoded_should_decode_the_same_cursor() {
+        let cursor = ClientCursor::after(&create_client(7, "Ana: Perez"), ClientSortField::Name);
+
+        let encoded = cursor.encode();
+        let expected_result = ClientCursor::decode(&encoded, ClientSortField::Name).unwrap();
+
+        assert_eq!(cursor, expected_result);
+        assert!(ClientCursor::decode(&encoded, ClientSortField::Id).is_err());
+        assert!(ClientCursor::decode("zz", ClientSortField::Id).is_err());
+    }
+
+    /// Scenario:
+    /// Execute is_before with clients before and after the cursor
+    /// Expectation:
+    /// Only the clients after the cursor position should be accepted
+    #[test]
+    fn when_is_before_should_accept_only_clients_after_the_cursor() {
+        let cursor = ClientCursor::after(&create_client(2, "Bruno"), ClientSortField::Name);
+
+        assert!(cursor.is_before(&create_client(1, "Carla")));
+        assert!(cursor.is_before(&create_client(3, "Bruno")));
+        assert!(!cursor.is_before(&create_client(1, "Bruno")));
+        assert!(!cursor.is_before(&create_client(5, "Ana")));
+    }
+}
@@ -1,5 +1,77 @@
+use crate::errors::common_error::CommonError;
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Account status of a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClientStatus {
+    // credits and debits are accepted
+    #[default]
+    Active,
+    // frozen, can be activated again
+    Blocked,
+    // final, only reached with a zero balance
+    Closed,
+}
+
+/// Implementation Client status
+impl ClientStatus {
+    /// Name of the status, the same as in JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientStatus::Active => "ACTIVE",
+            ClientStatus::Blocked => "BLOCKED",
+            ClientStatus::Closed => "CLOSED",
+        }
+    }
+
+    /// Whether the status can change to [ClientStatus] status:
+    /// active to blocked, blocked to active and active or blocked to closed
+    pub fn can_change_to(&self, status: ClientStatus) -> bool {
+        matches!(
+            (self, status),
+            (ClientStatus::Active, ClientStatus::Blocked)
+                | (ClientStatus::Blocked, ClientStatus::Active)
+                | (ClientStatus::Active, ClientStatus::Closed)
+                | (ClientStatus::Blocked, ClientStatus::Closed)
+        )
+    }
+}
+
+/// Read a [ClientStatus] from its name
+impl std::str::FromStr for ClientStatus {
+    type Err = CommonError;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "ACTIVE" => Ok(ClientStatus::Active),
+            "BLOCKED" => Ok(ClientStatus::Blocked),
+            "CLOSED" => Ok(ClientStatus::Closed),
+            _ => Err(CommonError::InvalidField {
+                field: "status".to_string(),
+                reason: "must be ACTIVE, BLOCKED or CLOSED".to_string(),
+            }),
+        }
+    }
+}
+
+/// Reason code of an account closing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClosureReason {
+    // the client asked to close the account
+    CustomerRequest,
+    // closed by a compliance decision
+    Compliance,
+    // closed after a fraud investigation
+    Fraud,
+    // the client passed away
+    Deceased,
+    // any other reason
+    Other,
+}
 
 /// Client model
 #[derive(Debug, Clone)]
@@ -16,11 +88,26 @@ pub struct Client {
     pub country: String,
     // money in account
     pub balance: Decimal,
+    // profile version, increased on every profile update
+    pub version: u64,
+    // account status
+    pub status: ClientStatus,
+    // money reserved by the active holds, still part of the balance
+    pub held_balance: Decimal,
+}
+
+/// Implementation Client
+impl Client {
+    /// Money that can be debited or held, the balance minus the held balance
+    pub fn available_balance(&self) -> Decimal {
+        self.balance - self.held_balance
+    }
 }
 /// Unit tests cases
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::constants::INITIAL_CLIENT_VERSION;
     use chrono::NaiveDate;
     use rust_decimal::Decimal;
 
@@ -42,6 +129,9 @@ mod tests {
             document_number: DOCUMENT_NUMBER.to_string(),
             country: COUNTRY.to_string(),
             balance,
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
         };
 
         assert_eq!(CLIENT_ID, expected_result.client_id);
@@ -50,5 +140,6 @@ mod tests {
         assert_eq!(COUNTRY, expected_result.country);
         assert_eq!(DOCUMENT_NUMBER, expected_result.document_number);
         assert_eq!(CLIENT_NAME, expected_result.client_name);
+        assert_eq!(INITIAL_CLIENT_VERSION, expected_result.version);
     }
 }
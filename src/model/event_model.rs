@@ -0,0 +1,185 @@
+use crate::model::client_model::{ClientStatus, ClosureReason};
+use crate::model::hold_model::HoldStatus;
+use crate::model::transaction_model::TransactionKind;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Change applied by the client service, saved in the event log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Event {
+    // new client registered with balance zero
+    ClientCreated {
+        client_id: i32,
+        client_name: String,
+        birth_date: NaiveDate,
+        document_number: String,
+        country: String,
+    },
+    // new profile of a client, its balance is not changed
+    ClientUpdated {
+        client_id: i32,
+        client_name: String,
+        birth_date: NaiveDate,
+        document_number: String,
+        country: String,
+        version: u64,
+    },
+    // new account status of a client, closings carry their reason code
+    ClientStatusChanged {
+        client_id: i32,
+        status: ClientStatus,
+        reason_code: Option<ClosureReason>,
+    },
+    // credit or debit on a client account, with the idempotency key of the request if it had one,
+    // missing in logs written before the keys were saved
+    TransactionPosted {
+        client_id: i32,
+        kind: TransactionKind,
+        amount: Decimal,
+        #[serde(default)]
+        idempotent_request: Option<IdempotentRequest>,
+    },
+    // money reserved on a client account until the hold is captured, released or expired
+    HoldPlaced {
+        hold_id: i32,
+        client_id: i32,
+        amount: Decimal,
+        expires_at: DateTime<Utc>,
+    },
+    // amount of the hold debited, the rest of it is released
+    HoldCaptured {
+        hold_id: i32,
+        amount: Decimal,
+    },
+    // hold released by a request or by its expiry, status released or expired
+    HoldReleased {
+        hold_id: i32,
+        status: HoldStatus,
+    },
+    // (client_id, kind, amount) of the applied items of a batch, in the batch order
+    TransactionBatchPosted {
+        transactions: Vec<(i32, TransactionKind, Decimal)>,
+    },
+    // linked entry compensating amount of a posted transaction, on the same client account
+    TransactionReversed {
+        transaction_id: i32,
+        amount: Decimal,
+    },
+    // money moved between two client accounts
+    TransferPosted {
+        from_client_id: i32,
+        to_client_id: i32,
+        amount: Decimal,
+    },
+    // (client_id, balance) pairs stored in a balance file, subtracted from the client balances,
+    // with the file name and the first transaction id left out of it, missing in logs written before reconciliation
+    BalancesReset {
+        stored_balances: Vec<(i32, Decimal)>,
+        #[serde(default)]
+        file_name: Option<String>,
+        #[serde(default)]
+        ledger_cut: Option<i32>,
+    },
+    // (client_id, balance) pairs of a balance file added as opening balances
+    OpeningBalancesLoaded {
+        file_name: String,
+        opening_balances: Vec<(i32, Decimal)>,
+    },
+}
+
+/// Idempotency key of a credit or debit request and the fingerprint of its payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotentRequest {
+    // key sent by the client
+    pub idempotency_key: String,
+    // kind, client id and amount of the request
+    pub fingerprint: String,
+}
+
+/// Event log line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    // when the event was applied
+    pub created_at: DateTime<Utc>,
+    // applied event
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_ID: i32 = 1;
+
+    /// Scenario:
+    /// Serialize and deserialize an [EventRecord]
+    /// Expectation:
+    /// The same record should be returned, with the event name next to its fields
+    #[test]
+    fn when_event_record_is_serialized_should_deserialize_the_same_record() {
+        let record = EventRecord {
+            created_at: Utc::now(),
+            event: Event::TransactionPosted {
+                client_id: CLIENT_ID,
+                kind: TransactionKind::Credit,
+                amount: Decimal::new(1050, 2),
+                idempotent_request: Some(IdempotentRequest {
+                    idempotency_key: "some-idempotency-key".to_string(),
+                    fingerprint: "Credit:1:10.5".to_string(),
+                }),
+            },
+        };
+
+        let line = serde_json::to_string(&record).unwrap();
+        let expected_result: EventRecord = serde_json::from_str(&line).unwrap();
+
+        assert!(line.contains(r#""event":"TRANSACTION_POSTED""#));
+        assert!(line.contains(r#""amount":"10.50""#));
+        assert_eq!(record, expected_result);
+    }
+
+    /// Scenario:
+    /// Deserialize a balances reset written before the balance file reconciliation
+    /// Expectation:
+    /// The event should be read without file name and ledger cut
+    #[test]
+    fn when_balances_reset_without_file_name_is_deserialized_should_default_to_none() {
+        let line = r#"{"created_at":"2025-07-03T10:00:00Z","event":"BALANCES_RESET","stored_balances":[[1,"10.50"]]}"#;
+
+        let expected_result: EventRecord = serde_json::from_str(line).unwrap();
+
+        assert_eq!(
+            Event::BalancesReset {
+                stored_balances: vec![(CLIENT_ID, Decimal::new(1050, 2))],
+                file_name: None,
+                ledger_cut: None,
+            },
+            expected_result.event
+        );
+    }
+
+    /// Scenario:
+    /// Deserialize a transaction posted before the idempotency keys were saved
+    /// Expectation:
+    /// The event should be read without idempotent request
+    #[test]
+    fn when_transaction_posted_without_idempotent_request_is_deserialized_should_default_to_none() {
+        let line = r#"{"created_at":"2025-07-03T10:00:00Z","event":"TRANSACTION_POSTED","client_id":1,"kind":"CREDIT","amount":"10.50"}"#;
+
+        let expected_result: EventRecord = serde_json::from_str(line).unwrap();
+
+        assert_eq!(
+            Event::TransactionPosted {
+                client_id: CLIENT_ID,
+                kind: TransactionKind::Credit,
+                amount: Decimal::new(1050, 2),
+                idempotent_request: None,
+            },
+            expected_result.event
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Hold status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HoldStatus {
+    // money reserved, waiting for a capture or a release
+    Active,
+    // fully or partially debited
+    Captured,
+    // released before its expiry
+    Released,
+    // released by the expiry task
+    Expired,
+}
+
+/// Hold model, money reserved on a client account without posting it
+#[derive(Debug, Clone)]
+pub struct Hold {
+    // hold id
+    pub hold_id: i32,
+    // client id
+    pub client_id: i32,
+    // money reserved
+    pub amount: Decimal,
+    // money debited by the capture, zero until captured
+    pub captured_amount: Decimal,
+    // hold status
+    pub status: HoldStatus,
+    // hold date
+    pub created_at: DateTime<Utc>,
+    // when an active hold is released automatically
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Implementation Hold
+impl Hold {
+    /// Whether the hold is still active at [DateTime] now
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.status == HoldStatus::Active && now < self.expires_at
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// Scenario:
+    /// Check an active hold before and after its expiry and a captured hold
+    /// Expectation:
+    /// Only the active hold before its expiry should be active
+    #[test]
+    fn when_is_active_should_check_status_and_expiry() {
+        let now = Utc::now();
+        let mut hold = Hold {
+            hold_id: 1,
+            client_id: 1,
+            amount: Decimal::new(100, 2),
+            captured_amount: Decimal::ZERO,
+            status: HoldStatus::Active,
+            created_at: now,
+            expires_at: now + Duration::minutes(1),
+        };
+
+        assert!(hold.is_active(now));
+        assert!(!hold.is_active(now + Duration::minutes(1)));
+
+        hold.status = HoldStatus::Captured;
+        assert!(!hold.is_active(now));
+    }
+}
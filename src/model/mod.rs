@@ -1 +1,6 @@
+pub mod client_cursor_model;
 pub mod client_model;
+pub mod event_model;
+pub mod hold_model;
+pub mod overdraft_model;
+pub mod transaction_model;
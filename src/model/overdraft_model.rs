@@ -0,0 +1,142 @@
+use crate::errors::common_error::CommonError;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How far below zero the balance of a client can go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum OverdraftLimit {
+    // the balance can go arbitrarily negative
+    #[default]
+    Unlimited,
+    // max negative balance, zero for prepaid accounts
+    Limited(Decimal),
+}
+
+/// Implementation Overdraft limit
+impl OverdraftLimit {
+    /// Amount that can still be debited from [Decimal] balance, None when unlimited
+    pub fn available_balance(&self, balance: Decimal) -> Option<Decimal> {
+        match self {
+            OverdraftLimit::Unlimited => None,
+            OverdraftLimit::Limited(limit) => Some((balance + limit).max(Decimal::ZERO)),
+        }
+    }
+}
+
+/// Read an [OverdraftLimit] from `unlimited` or a non negative amount
+impl std::str::FromStr for OverdraftLimit {
+    type Err = CommonError;
+
+    fn from_str(overdraft_limit: &str) -> Result<Self, Self::Err> {
+        let overdraft_limit = overdraft_limit.trim();
+        if overdraft_limit.eq_ignore_ascii_case("unlimited") {
+            return Ok(OverdraftLimit::Unlimited);
+        }
+        match overdraft_limit.parse::<Decimal>() {
+            Ok(limit) if limit >= Decimal::ZERO => Ok(OverdraftLimit::Limited(limit)),
+            _ => Err(CommonError::InvalidField {
+                field: "overdraft_limit".to_string(),
+                reason: "must be unlimited or a non negative amount".to_string(),
+            }),
+        }
+    }
+}
+
+/// Read an [OverdraftLimit] from a config value
+impl TryFrom<String> for OverdraftLimit {
+    type Error = CommonError;
+
+    fn try_from(overdraft_limit: String) -> Result<Self, Self::Error> {
+        overdraft_limit.parse()
+    }
+}
+
+/// Same text as accepted by [OverdraftLimit::from_str]
+impl fmt::Display for OverdraftLimit {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverdraftLimit::Unlimited => write!(formatter, "unlimited"),
+            OverdraftLimit::Limited(limit) => write!(formatter, "{limit}"),
+        }
+    }
+}
+
+/// Overdraft limit of every client
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OverdraftLimits {
+    // limit of the clients without their own limit
+    pub default_limit: OverdraftLimit,
+    // limit of each client, by client id
+    pub client_limits: HashMap<i32, OverdraftLimit>,
+}
+
+/// Implementation Overdraft limits
+impl OverdraftLimits {
+    /// Limit of [i32] client_id, the default limit if it has not its own
+    pub fn limit_of(&self, client_id: i32) -> OverdraftLimit {
+        self.client_limits
+            .get(&client_id)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scenario:
+    /// Parse overdraft limits from valid and invalid values
+    /// Expectation:
+    /// `unlimited` and non negative amounts should be accepted, anything else rejected
+    #[test]
+    fn when_parse_overdraft_limit_should_accept_unlimited_or_non_negative_amount() {
+        assert_eq!(
+            OverdraftLimit::Unlimited,
+            "Unlimited".parse::<OverdraftLimit>().unwrap()
+        );
+        assert_eq!(
+            OverdraftLimit::Limited(Decimal::new(5000, 2)),
+            "50.00".parse::<OverdraftLimit>().unwrap()
+        );
+        assert_eq!(
+            OverdraftLimit::Limited(Decimal::ZERO),
+            "0".parse::<OverdraftLimit>().unwrap()
+        );
+        assert!("-1".parse::<OverdraftLimit>().is_err());
+        assert!("none".parse::<OverdraftLimit>().is_err());
+    }
+
+    /// Scenario:
+    /// Get the available balance and the limit of clients with and without their own limit
+    /// Expectation:
+    /// The client limit should win over the default one and the available balance should not be negative
+    #[test]
+    fn when_limit_of_should_prefer_client_limit_over_default_limit() {
+        let overdraft_limits = OverdraftLimits {
+            default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+            client_limits: HashMap::from([(2, OverdraftLimit::Unlimited)]),
+        };
+
+        let limit = overdraft_limits.limit_of(1);
+        assert_eq!(OverdraftLimit::Limited(Decimal::ZERO), limit);
+        assert_eq!(
+            Some(Decimal::new(100, 2)),
+            limit.available_balance(Decimal::new(100, 2))
+        );
+        assert_eq!(
+            Some(Decimal::ZERO),
+            limit.available_balance(Decimal::new(-100, 2))
+        );
+        assert_eq!(
+            None,
+            overdraft_limits
+                .limit_of(2)
+                .available_balance(Decimal::new(-100, 2))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Transaction kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionKind {
+    // money credited
+    Credit,
+    // money debited
+    Debit,
+    // balance stored in a balance file and taken out of the account by the reset
+    Settlement,
+    // balance of a balance file loaded back into the account
+    OpeningBalance,
+}
+
+/// Implementation Transaction kind
+impl TransactionKind {
+    /// Kind of the entry compensating a transaction of this kind, None if it cannot be reversed
+    pub fn opposite(&self) -> Option<TransactionKind> {
+        match self {
+            TransactionKind::Credit => Some(TransactionKind::Debit),
+            TransactionKind::Debit => Some(TransactionKind::Credit),
+            TransactionKind::Settlement | TransactionKind::OpeningBalance => None,
+        }
+    }
+
+    /// Money added to the client balance by a transaction of this kind moving [Decimal] amount,
+    /// negative when the money is taken out
+    pub fn balance_change(&self, amount: Decimal) -> Decimal {
+        match self {
+            TransactionKind::Credit | TransactionKind::OpeningBalance => amount,
+            TransactionKind::Debit | TransactionKind::Settlement => -amount,
+        }
+    }
+}
+
+/// Transaction model
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    // transaction id
+    pub transaction_id: i32,
+    // client id
+    pub client_id: i32,
+    // credit or debit
+    pub kind: TransactionKind,
+    // money moved by the transaction
+    pub amount: Decimal,
+    // client balance after the transaction
+    pub balance: Decimal,
+    // transaction date
+    pub created_at: DateTime<Utc>,
+    // transaction id compensated by this entry, None if it is not a reversal
+    pub reversal_of: Option<i32>,
+    // money of this transaction already compensated by reversals
+    pub reversed_amount: Decimal,
+}
+
+/// Implementation Transaction
+impl Transaction {
+    /// Money of this transaction that can still be reversed
+    pub fn reversible_amount(&self) -> Decimal {
+        self.amount - self.reversed_amount
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSACTION_ID: i32 = i32::MAX;
+    const CLIENT_ID: i32 = 1;
+
+    /// Get Transaction model
+    #[test]
+    fn test_transaction_model() {
+        let amount = Decimal::new(10, 2);
+        let created_at = Utc::now();
+
+        let expected_result = Transaction {
+            transaction_id: TRANSACTION_ID,
+            client_id: CLIENT_ID,
+            kind: TransactionKind::Credit,
+            amount,
+            balance: amount,
+            created_at,
+            reversal_of: None,
+            reversed_amount: Decimal::ZERO,
+        };
+
+        assert_eq!(TRANSACTION_ID, expected_result.transaction_id);
+        assert_eq!(CLIENT_ID, expected_result.client_id);
+        assert_eq!(TransactionKind::Credit, expected_result.kind);
+        assert_eq!(amount, expected_result.amount);
+        assert_eq!(amount, expected_result.balance);
+        assert_eq!(created_at, expected_result.created_at);
+        assert_eq!(amount, expected_result.reversible_amount());
+        assert_eq!(
+            Some(TransactionKind::Debit),
+            expected_result.kind.opposite()
+        );
+        assert_eq!(amount, expected_result.kind.balance_change(amount));
+    }
+
+    /// Scenario:
+    /// Gets the opposite kind and the balance change of a settlement
+    /// Expectation:
+    /// A settlement should not be reversible and should take its amount out of the balance
+    #[test]
+    fn when_transaction_kind_is_settlement_should_not_have_opposite_and_take_the_amount_out() {
+        let amount = Decimal::new(10, 2);
+
+        assert_eq!(None, TransactionKind::Settlement.opposite());
+        assert_eq!(-amount, TransactionKind::Settlement.balance_change(amount));
+        assert_eq!(None, TransactionKind::OpeningBalance.opposite());
+        assert_eq!(
+            amount,
+            TransactionKind::OpeningBalance.balance_change(amount)
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::errors::common_error::CommonError;
+use crate::model::client_model::{Client, ClientStatus};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Storage of the clients used by the client service
+pub trait ClientRepository {
+    /// Get the [Client] with [i32] client_id, [None] if it has not existed
+    /// Returns a [CommonError] if the storage cannot be read
+    fn get(&self, client_id: i32) -> Result<Option<Client>, CommonError>;
+
+    /// Insert [Client] client, checking its document number is not used by another client in the same step
+    /// Returns a [CommonError] if the document number already exists or the storage cannot be written
+    fn insert(&self, client: Client) -> Result<(), CommonError>;
+
+    /// Set [Decimal] balance on the client with [i32] client_id
+    /// Returns a [CommonError] if client_id has not existed or the storage cannot be written
+    fn update_balance(&self, client_id: i32, balance: Decimal) -> Result<(), CommonError>;
+
+    /// Set the name, birth date, document number, country and version of [Client] client, keeping its balance
+    /// Returns a [CommonError] if client_id has not existed, the document number already exists or the storage cannot be written
+    fn update_profile(&self, client: &Client) -> Result<(), CommonError>;
+
+    /// Set [ClientStatus] status on the client with [i32] client_id
+    /// Returns a [CommonError] if client_id has not existed or the storage cannot be written
+    fn update_status(&self, client_id: i32, status: ClientStatus) -> Result<(), CommonError>;
+
+    /// Set [Decimal] held_balance, the money reserved by the active holds, on the client with [i32] client_id
+    /// Returns a [CommonError] if client_id has not existed or the storage cannot be written
+    fn update_held_balance(&self, client_id: i32, held_balance: Decimal)
+        -> Result<(), CommonError>;
+
+    /// Set [Decimal] balance and [Decimal] held_balance on the client with [i32] client_id in a single write
+    /// Returns a [CommonError] if client_id has not existed or the storage cannot be written
+    fn update_balance_and_held_balance(
+        &self,
+        client_id: i32,
+        balance: Decimal,
+        held_balance: Decimal,
+    ) -> Result<(), CommonError>;
+
+    /// Set every (client_id, balance) pair of balances at once, either all of them or none are saved
+    /// Returns a [CommonError] if any client_id has not existed or the storage cannot be written
+    fn update_balances(&self, balances: &[(i32, Decimal)]) -> Result<(), CommonError>;
+
+    /// Get the [Client] with [str] document_number, [None] if it has not existed
+    /// Implementations look it up through an index on the document number, not by scanning the clients
+    /// Returns a [CommonError] if the storage cannot be read
+    fn find_by_document(&self, document_number: &str) -> Result<Option<Client>, CommonError>;
+
+    /// Get all the clients sorted by client id
+    /// Returns a [CommonError] if the storage cannot be read
+    fn list(&self) -> Result<Vec<Client>, CommonError>;
+}
+
+/// Client repository trait dyn type
+pub type DynClientRepository = Arc<dyn ClientRepository + Send + Sync>;
@@ -0,0 +1,410 @@
+use crate::errors::common_error::CommonError;
+use crate::model::client_model::{Client, ClientStatus};
+use crate::repository::client_repository::ClientRepository;
+use log::error;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Client repository kept in memory, shared with [crate::state::app_state::AppState] clients
+/// The clients must be changed through the repository once it is created, so the document index stays in sync
+pub struct InMemoryClientRepository {
+    // hashmap of clients
+    pub clients: Arc<RwLock<HashMap<i32, Client>>>,
+    // client id per document number, always locked after the clients
+    documents: RwLock<HashMap<String, i32>>,
+}
+
+/// Initialization
+impl InMemoryClientRepository {
+    /// Repository over [HashMap] clients, indexing the documents of the clients already there
+    pub fn new(clients: Arc<RwLock<HashMap<i32, Client>>>) -> Self {
+        let documents = clients
+            .read()
+            .map(|clients_map| {
+                clients_map
+                    .values()
+                    .map(|client| (client.document_number.clone(), client.client_id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        InMemoryClientRepository {
+            clients,
+            documents: RwLock::new(documents),
+        }
+    }
+}
+
+/// In memory client repository logic
+impl ClientRepository for InMemoryClientRepository {
+    fn get(&self, client_id: i32) -> Result<Option<Client>, CommonError> {
+        Ok(self.read_clients()?.get(&client_id).cloned())
+    }
+
+    fn insert(&self, client: Client) -> Result<(), CommonError> {
+        let mut clients_map = self.write_clients()?;
+        let mut documents = self.write_documents()?;
+        if documents
+            .get(&client.document_number)
+            .is_some_and(|client_id| *client_id != client.client_id)
+        {
+            error!(
+                "insert - error - the document must be unique - document number: {}",
+                client.document_number
+            );
+            return Err(CommonError::DocumentAlreadyExists(client.document_number));
+        }
+        if let Some(replaced) = clients_map.get(&client.client_id) {
+            documents.remove(&replaced.document_number);
+        }
+        documents.insert(client.document_number.clone(), client.client_id);
+        clients_map.insert(client.client_id, client);
+        Ok(())
+    }
+
+    fn update_balance(&self, client_id: i32, balance: Decimal) -> Result<(), CommonError> {
+        self.update_balances(&[(client_id, balance)])
+    }
+
+    fn update_profile(&self, client: &Client) -> Result<(), CommonError> {
+        let mut clients_map = self.write_clients()?;
+        let mut documents = self.write_documents()?;
+        let Some(saved_client) = clients_map.get_mut(&client.client_id) else {
+            error!(
+                "update_profile - error - client id not found - client id: {}",
+                client.client_id
+            );
+            return Err(CommonError::ClientNotFound(client.client_id));
+        };
+        if documents
+            .get(&client.document_number)
+            .is_some_and(|client_id| *client_id != client.client_id)
+        {
+            error!(
+                "update_profile - error - the document must be unique - document number: {}",
+                client.document_number
+            );
+            return Err(CommonError::DocumentAlreadyExists(
+                client.document_number.clone(),
+            ));
+        }
+        documents.remove(&saved_client.document_number);
+        documents.insert(client.document_number.clone(), client.client_id);
+        saved_client.client_name = client.client_name.clone();
+        saved_client.birth_date = client.birth_date;
+        saved_client.document_number = client.document_number.clone();
+        saved_client.country = client.country.clone();
+        saved_client.version = client.version;
+        Ok(())
+    }
+
+    fn update_status(&self, client_id: i32, status: ClientStatus) -> Result<(), CommonError> {
+        match self.write_clients()?.get_mut(&client_id) {
+            Some(client) => {
+                client.status = status;
+                Ok(())
+            }
+            None => {
+                error!("update_status - error - client id not found - client id: {client_id}");
+                Err(CommonError::ClientNotFound(client_id))
+            }
+        }
+    }
+
+    fn update_held_balance(
+        &self,
+        client_id: i32,
+        held_balance: Decimal,
+    ) -> Result<(), CommonError> {
+        match self.write_clients()?.get_mut(&client_id) {
+            Some(client) => {
+                client.held_balance = held_balance;
+                Ok(())
+            }
+            None => {
+                error!(
+                    "update_held_balance - error - client id not found - client id: {client_id}"
+                );
+                Err(CommonError::ClientNotFound(client_id))
+            }
+        }
+    }
+
+    fn update_balance_and_held_balance(
+        &self,
+        client_id: i32,
+        balance: Decimal,
+        held_balance: Decimal,
+    ) -> Result<(), CommonError> {
+        match self.write_clients()?.get_mut(&client_id) {
+            Some(client) => {
+                client.balance = balance;
+                client.held_balance = held_balance;
+                Ok(())
+            }
+            None => {
+                error!("update_balance_and_held_balance - error - client id not found - client id: {client_id}");
+                Err(CommonError::ClientNotFound(client_id))
+            }
+        }
+    }
+
+    fn update_balances(&self, balances: &[(i32, Decimal)]) -> Result<(), CommonError> {
+        let mut clients_map = self.write_clients()?;
+
+        // every client must exist before touching any balance
+        if let Some((client_id, _)) = balances
+            .iter()
+            .find(|(client_id, _)| !clients_map.contains_key(client_id))
+        {
+            error!("update_balances - error - client id not found - client id: {client_id}");
+            return Err(CommonError::ClientNotFound(*client_id));
+        }
+        for (client_id, balance) in balances {
+            if let Some(client) = clients_map.get_mut(client_id) {
+                client.balance = *balance;
+            }
+        }
+        Ok(())
+    }
+
+    fn find_by_document(&self, document_number: &str) -> Result<Option<Client>, CommonError> {
+        let clients_map = self.read_clients()?;
+        let documents = self.read_documents()?;
+        Ok(documents
+            .get(document_number)
+            .and_then(|client_id| clients_map.get(client_id))
+            .cloned())
+    }
+
+    fn list(&self) -> Result<Vec<Client>, CommonError> {
+        let mut clients: Vec<Client> = self.read_clients()?.values().cloned().collect();
+        clients.sort_by_key(|client| client.client_id);
+        Ok(clients)
+    }
+}
+
+/// In memory client repository "private" logic
+impl InMemoryClientRepository {
+    /// Take the read lock of the clients
+    /// Returns a [CommonError] if the RwLock cannot be read
+    fn read_clients(&self) -> Result<RwLockReadGuard<'_, HashMap<i32, Client>>, CommonError> {
+        self.clients.read().map_err(|error| {
+            error!(
+                "read_clients - error when reading clients - error: {:?}",
+                error
+            );
+            CommonError::LockReadFailed
+        })
+    }
+
+    /// Take the write lock of the clients
+    /// Returns a [CommonError] if the RwLock cannot be written
+    fn write_clients(&self) -> Result<RwLockWriteGuard<'_, HashMap<i32, Client>>, CommonError> {
+        self.clients.write().map_err(|error| {
+            error!(
+                "write_clients - error when writing clients - error: {:?}",
+                error
+            );
+            CommonError::LockWriteFailed
+        })
+    }
+
+    /// Take the read lock of the document index
+    /// Returns a [CommonError] if the RwLock cannot be read
+    fn read_documents(&self) -> Result<RwLockReadGuard<'_, HashMap<String, i32>>, CommonError> {
+        self.documents.read().map_err(|error| {
+            error!(
+                "read_documents - error when reading documents - error: {:?}",
+                error
+            );
+            CommonError::LockReadFailed
+        })
+    }
+
+    /// Take the write lock of the document index
+    /// Returns a [CommonError] if the RwLock cannot be written
+    fn write_documents(&self) -> Result<RwLockWriteGuard<'_, HashMap<String, i32>>, CommonError> {
+        self.documents.write().map_err(|error| {
+            error!(
+                "write_documents - error when writing documents - error: {:?}",
+                error
+            );
+            CommonError::LockWriteFailed
+        })
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::new_client_mapper::map_client;
+    use crate::stub::new_client_stub::stub::create_new_client_stub;
+
+    const CLIENT_ID: i32 = 1;
+    const OTHER_CLIENT_ID: i32 = 2;
+
+    /// Build a repository with two clients
+    fn create_repository() -> InMemoryClientRepository {
+        let repository = InMemoryClientRepository::new(Arc::new(RwLock::new(HashMap::new())));
+        let mut other_client = map_client(create_new_client_stub(), OTHER_CLIENT_ID);
+        other_client.document_number = "some-other-document-number".to_string();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+        repository.insert(other_client).unwrap();
+        repository
+    }
+
+    /// Scenario:
+    /// Execute get and find_by_document when the client exists
+    /// Expectation:
+    /// The client should be returned by both
+    #[test]
+    fn when_get_and_find_by_document_with_existing_client_should_return_it() {
+        let repository = create_repository();
+        let client = create_new_client_stub();
+
+        let by_id = repository.get(CLIENT_ID).unwrap().unwrap();
+        let by_document = repository
+            .find_by_document(&client.document_number)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(CLIENT_ID, by_id.client_id);
+        assert_eq!(CLIENT_ID, by_document.client_id);
+        assert!(repository.get(3).unwrap().is_none());
+        assert!(repository.find_by_document("unknown").unwrap().is_none());
+    }
+
+    /// Scenario:
+    /// Execute update_balances when one of the client ids has not existed
+    /// Expectation:
+    /// A [CommonError] should be returned and no balance should be changed
+    #[test]
+    fn when_update_balances_with_invalid_client_id_should_not_change_any_balance() {
+        let repository = create_repository();
+
+        let expected_result =
+            repository.update_balances(&[(CLIENT_ID, Decimal::new(500, 2)), (3, Decimal::ONE)]);
+
+        assert_eq!(CommonError::ClientNotFound(3), expected_result.unwrap_err());
+        let clients = repository.list().unwrap();
+        assert_eq!(
+            vec![CLIENT_ID, OTHER_CLIENT_ID],
+            clients
+                .iter()
+                .map(|client| client.client_id)
+                .collect::<Vec<_>>()
+        );
+        assert!(clients.iter().all(|client| client.balance.is_zero()));
+    }
+
+    /// Scenario:
+    /// Execute update_balance_and_held_balance on an existing client and on a missing one
+    /// Expectation:
+    /// Both balances should be saved together and a [CommonError] returned for the missing client
+    #[test]
+    fn when_update_balance_and_held_balance_should_save_both() {
+        let repository = create_repository();
+
+        repository
+            .update_balance_and_held_balance(CLIENT_ID, Decimal::new(600, 2), Decimal::new(150, 2))
+            .unwrap();
+
+        let client = repository.get(CLIENT_ID).unwrap().unwrap();
+        assert_eq!(Decimal::new(600, 2), client.balance);
+        assert_eq!(Decimal::new(150, 2), client.held_balance);
+        assert_eq!(
+            CommonError::ClientNotFound(3),
+            repository
+                .update_balance_and_held_balance(3, Decimal::ONE, Decimal::ONE)
+                .unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Create a repository over clients already in the hashmap and replace one of them
+    /// Expectation:
+    /// find_by_document should find the existing client and only the new document of the replaced one
+    #[test]
+    fn when_repository_is_created_over_existing_clients_should_index_their_documents() {
+        let client = map_client(create_new_client_stub(), CLIENT_ID);
+        let document_number = client.document_number.clone();
+        let repository = InMemoryClientRepository::new(Arc::new(RwLock::new(HashMap::from([(
+            CLIENT_ID, client,
+        )]))));
+
+        let found = repository.find_by_document(&document_number).unwrap();
+        let mut replaced = map_client(create_new_client_stub(), CLIENT_ID);
+        replaced.document_number = "replaced-document-number".to_string();
+        repository.insert(replaced).unwrap();
+
+        assert_eq!(Some(CLIENT_ID), found.map(|client| client.client_id));
+        assert!(repository
+            .find_by_document(&document_number)
+            .unwrap()
+            .is_none());
+        assert!(repository
+            .find_by_document("replaced-document-number")
+            .unwrap()
+            .is_some());
+    }
+
+    /// Scenario:
+    /// Execute insert with a new client id and a document number already registered
+    /// Expectation:
+    /// A [CommonError::DocumentAlreadyExists] should be returned and the client should not be saved
+    #[test]
+    fn when_insert_with_existing_document_number_should_return_common_error() {
+        let repository = create_repository();
+        let client = map_client(create_new_client_stub(), 3);
+        let document_number = client.document_number.clone();
+
+        let expected_result = repository.insert(client);
+
+        assert_eq!(
+            CommonError::DocumentAlreadyExists(document_number),
+            expected_result.unwrap_err()
+        );
+        assert!(repository.get(3).unwrap().is_none());
+    }
+
+    /// Scenario:
+    /// Execute update_profile changing the document number of a client with a balance
+    /// Expectation:
+    /// The profile should be changed, the balance kept and the document index moved to the new document
+    #[test]
+    fn when_update_profile_should_keep_balance_and_move_document() {
+        let repository = create_repository();
+        repository
+            .update_balance(CLIENT_ID, Decimal::new(1050, 2))
+            .unwrap();
+        let mut client = repository.get(CLIENT_ID).unwrap().unwrap();
+        let old_document_number = client.document_number.clone();
+        client.document_number = "some-new-document-number".to_string();
+        client.balance = Decimal::ZERO;
+        client.version += 1;
+
+        repository.update_profile(&client).unwrap();
+        let mut duplicated = client.clone();
+        duplicated.client_id = OTHER_CLIENT_ID;
+        let duplicated_result = repository.update_profile(&duplicated);
+
+        let saved_client = repository
+            .find_by_document("some-new-document-number")
+            .unwrap()
+            .unwrap();
+        assert_eq!(Decimal::new(1050, 2), saved_client.balance);
+        assert_eq!(2, saved_client.version);
+        assert!(repository
+            .find_by_document(&old_document_number)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            CommonError::DocumentAlreadyExists("some-new-document-number".to_string()),
+            duplicated_result.unwrap_err()
+        );
+    }
+}
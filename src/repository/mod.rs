@@ -0,0 +1,4 @@
+pub mod client_repository;
+pub mod in_memory_client_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_client_repository;
@@ -0,0 +1,492 @@
+use crate::errors::common_error::CommonError;
+use crate::model::client_model::{Client, ClientStatus};
+use crate::repository::client_repository::ClientRepository;
+use chrono::NaiveDate;
+use log::error;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use rust_decimal::Decimal;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Mutex, MutexGuard};
+
+const CREATE_CLIENTS_TABLE: &str = "CREATE TABLE IF NOT EXISTS clients (
+    client_id INTEGER PRIMARY KEY,
+    client_name TEXT NOT NULL,
+    birth_date TEXT NOT NULL,
+    document_number TEXT NOT NULL UNIQUE,
+    country TEXT NOT NULL,
+    balance TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'ACTIVE',
+    held_balance TEXT NOT NULL DEFAULT '0'
+)";
+// (column, definition) pairs added after the first release, missing in older databases
+const ADDED_COLUMNS: [(&str, &str); 3] = [
+    ("version", "INTEGER NOT NULL DEFAULT 1"),
+    ("status", "TEXT NOT NULL DEFAULT 'ACTIVE'"),
+    ("held_balance", "TEXT NOT NULL DEFAULT '0'"),
+];
+const SELECT_CLIENT: &str =
+    "SELECT client_id, client_name, birth_date, document_number, country, balance, version, status, held_balance FROM clients";
+
+/// Client repository saved in a SQLite database, it survives restarts
+pub struct SqliteClientRepository {
+    // database connection
+    connection: Mutex<Connection>,
+}
+
+/// Initialization
+impl SqliteClientRepository {
+    /// Open or create the database in [Path] path
+    /// Returns a [CommonError] if the database cannot be opened
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CommonError> {
+        let connection = Connection::open(path).map_err(storage_failed)?;
+        Self::from_connection(connection)
+    }
+
+    /// Open a database that only lives in memory
+    /// Returns a [CommonError] if the database cannot be opened
+    pub fn open_in_memory() -> Result<Self, CommonError> {
+        let connection = Connection::open_in_memory().map_err(storage_failed)?;
+        Self::from_connection(connection)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, CommonError> {
+        connection
+            .execute(CREATE_CLIENTS_TABLE, [])
+            .map_err(storage_failed)?;
+        for (column, definition) in ADDED_COLUMNS {
+            let has_column = connection
+                .prepare(&format!("SELECT {column} FROM clients LIMIT 0"))
+                .is_ok();
+            if !has_column {
+                connection
+                    .execute(
+                        &format!("ALTER TABLE clients ADD COLUMN {column} {definition}"),
+                        [],
+                    )
+                    .map_err(storage_failed)?;
+            }
+        }
+        Ok(SqliteClientRepository {
+            connection: Mutex::new(connection),
+        })
+    }
+}
+
+/// SQLite client repository logic
+impl ClientRepository for SqliteClientRepository {
+    fn get(&self, client_id: i32) -> Result<Option<Client>, CommonError> {
+        self.lock_connection()?
+            .query_row(
+                &format!("{SELECT_CLIENT} WHERE client_id = ?1"),
+                params![client_id],
+                map_row,
+            )
+            .optional()
+            .map_err(storage_failed)
+    }
+
+    fn insert(&self, client: Client) -> Result<(), CommonError> {
+        self.lock_connection()?
+            .execute(
+                "INSERT INTO clients (client_id, client_name, birth_date, document_number, country, balance, version, status, held_balance)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    client.client_id,
+                    client.client_name,
+                    client.birth_date.to_string(),
+                    client.document_number,
+                    client.country,
+                    client.balance.to_string(),
+                    client.version as i64,
+                    client.status.as_str(),
+                    client.held_balance.to_string()
+                ],
+            )
+            .map_err(|error| document_failed(error, &client.document_number))?;
+        Ok(())
+    }
+
+    fn update_balance(&self, client_id: i32, balance: Decimal) -> Result<(), CommonError> {
+        self.update_balances(&[(client_id, balance)])
+    }
+
+    fn update_profile(&self, client: &Client) -> Result<(), CommonError> {
+        let updated_rows = self
+            .lock_connection()?
+            .execute(
+                "UPDATE clients SET client_name = ?1, birth_date = ?2, document_number = ?3, country = ?4, version = ?5
+                 WHERE client_id = ?6",
+                params![
+                    client.client_name,
+                    client.birth_date.to_string(),
+                    client.document_number,
+                    client.country,
+                    client.version as i64,
+                    client.client_id
+                ],
+            )
+            .map_err(|error| document_failed(error, &client.document_number))?;
+        if updated_rows == 0 {
+            error!(
+                "update_profile - error - client id not found - client id: {}",
+                client.client_id
+            );
+            return Err(CommonError::ClientNotFound(client.client_id));
+        }
+        Ok(())
+    }
+
+    fn update_status(&self, client_id: i32, status: ClientStatus) -> Result<(), CommonError> {
+        let updated_rows = self
+            .lock_connection()?
+            .execute(
+                "UPDATE clients SET status = ?1 WHERE client_id = ?2",
+                params![status.as_str(), client_id],
+            )
+            .map_err(storage_failed)?;
+        if updated_rows == 0 {
+            error!("update_status - error - client id not found - client id: {client_id}");
+            return Err(CommonError::ClientNotFound(client_id));
+        }
+        Ok(())
+    }
+
+    fn update_held_balance(
+        &self,
+        client_id: i32,
+        held_balance: Decimal,
+    ) -> Result<(), CommonError> {
+        let updated_rows = self
+            .lock_connection()?
+            .execute(
+                "UPDATE clients SET held_balance = ?1 WHERE client_id = ?2",
+                params![held_balance.to_string(), client_id],
+            )
+            .map_err(storage_failed)?;
+        if updated_rows == 0 {
+            error!("update_held_balance - error - client id not found - client id: {client_id}");
+            return Err(CommonError::ClientNotFound(client_id));
+        }
+        Ok(())
+    }
+
+    fn update_balance_and_held_balance(
+        &self,
+        client_id: i32,
+        balance: Decimal,
+        held_balance: Decimal,
+    ) -> Result<(), CommonError> {
+        let updated_rows = self
+            .lock_connection()?
+            .execute(
+                "UPDATE clients SET balance = ?1, held_balance = ?2 WHERE client_id = ?3",
+                params![balance.to_string(), held_balance.to_string(), client_id],
+            )
+            .map_err(storage_failed)?;
+        if updated_rows == 0 {
+            error!("update_balance_and_held_balance - error - client id not found - client id: {client_id}");
+            return Err(CommonError::ClientNotFound(client_id));
+        }
+        Ok(())
+    }
+
+    fn update_balances(&self, balances: &[(i32, Decimal)]) -> Result<(), CommonError> {
+        let mut connection = self.lock_connection()?;
+        let transaction = connection.transaction().map_err(storage_failed)?;
+        for (client_id, balance) in balances {
+            let updated_rows = transaction
+                .execute(
+                    "UPDATE clients SET balance = ?1 WHERE client_id = ?2",
+                    params![balance.to_string(), client_id],
+                )
+                .map_err(storage_failed)?;
+            if updated_rows == 0 {
+                error!("update_balances - error - client id not found - client id: {client_id}");
+                // dropping the transaction rolls back the previous updates
+                return Err(CommonError::ClientNotFound(*client_id));
+            }
+        }
+        transaction.commit().map_err(storage_failed)
+    }
+
+    fn find_by_document(&self, document_number: &str) -> Result<Option<Client>, CommonError> {
+        self.lock_connection()?
+            .query_row(
+                &format!("{SELECT_CLIENT} WHERE document_number = ?1"),
+                params![document_number],
+                map_row,
+            )
+            .optional()
+            .map_err(storage_failed)
+    }
+
+    fn list(&self) -> Result<Vec<Client>, CommonError> {
+        let connection = self.lock_connection()?;
+        let mut statement = connection
+            .prepare(&format!("{SELECT_CLIENT} ORDER BY client_id"))
+            .map_err(storage_failed)?;
+        let clients = statement
+            .query_map([], map_row)
+            .map_err(storage_failed)?
+            .collect::<Result<Vec<Client>, _>>()
+            .map_err(storage_failed)?;
+        Ok(clients)
+    }
+}
+
+/// SQLite client repository "private" logic
+impl SqliteClientRepository {
+    /// Take the lock of the database connection
+    /// Returns a [CommonError] if the Mutex cannot be locked
+    fn lock_connection(&self) -> Result<MutexGuard<'_, Connection>, CommonError> {
+        self.connection.lock().map_err(|error| {
+            error!(
+                "lock_connection - error when locking the connection - error: {:?}",
+                error
+            );
+            CommonError::LockWriteFailed
+        })
+    }
+}
+
+/// Map a clients table [Row] row to [Client]
+fn map_row(row: &Row) -> rusqlite::Result<Client> {
+    let birth_date: String = row.get(2)?;
+    let balance: String = row.get(5)?;
+    let status: String = row.get(7)?;
+    let held_balance: String = row.get(8)?;
+    Ok(Client {
+        client_id: row.get(0)?,
+        client_name: row.get(1)?,
+        birth_date: NaiveDate::from_str(&birth_date).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, error.into())
+        })?,
+        document_number: row.get(3)?,
+        country: row.get(4)?,
+        balance: Decimal::from_str(&balance).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, error.into())
+        })?,
+        version: row.get::<_, i64>(6)? as u64,
+        status: ClientStatus::from_str(&status).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(
+                7,
+                rusqlite::types::Type::Text,
+                error.to_string().into(),
+            )
+        })?,
+        held_balance: Decimal::from_str(&held_balance).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, error.into())
+        })?,
+    })
+}
+
+/// Map a [rusqlite::Error] of a statement saving [str] document_number to [CommonError]
+/// The UNIQUE constraint checks the document number in the same statement
+fn document_failed(error: rusqlite::Error, document_number: &str) -> CommonError {
+    match error {
+        rusqlite::Error::SqliteFailure(failure, Some(message))
+            if failure.code == rusqlite::ErrorCode::ConstraintViolation
+                && message.contains("clients.document_number") =>
+        {
+            error!("document_failed - error - the document must be unique - document number: {document_number}");
+            CommonError::DocumentAlreadyExists(document_number.to_string())
+        }
+        error => storage_failed(error),
+    }
+}
+
+/// Map a [rusqlite::Error] to [CommonError]
+fn storage_failed(error: rusqlite::Error) -> CommonError {
+    error!("storage_failed - error: {:?}", error);
+    CommonError::StorageFailed(error.to_string())
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::new_client_mapper::map_client;
+    use crate::stub::new_client_stub::stub::create_new_client_stub;
+
+    const CLIENT_ID: i32 = 1;
+
+    /// Scenario:
+    /// Execute insert, update_balance and get on a database file that is opened again
+    /// Expectation:
+    /// The client and its balance should survive the reopening
+    #[test]
+    fn when_database_is_reopened_should_keep_the_clients() {
+        let path = std::env::temp_dir().join(format!("prex-{}.sqlite", uuid::Uuid::new_v4()));
+        let balance = Decimal::new(1050, 2);
+        {
+            let repository = SqliteClientRepository::open(&path).unwrap();
+            repository
+                .insert(map_client(create_new_client_stub(), CLIENT_ID))
+                .unwrap();
+            repository.update_balance(CLIENT_ID, balance).unwrap();
+        }
+
+        let repository = SqliteClientRepository::open(&path).unwrap();
+        let client = repository.get(CLIENT_ID).unwrap().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(balance, client.balance);
+        assert_eq!(create_new_client_stub().birth_date, client.birth_date);
+        assert_eq!(
+            Some(CLIENT_ID),
+            repository
+                .find_by_document(&client.document_number)
+                .unwrap()
+                .map(|client| client.client_id)
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_balances when one of the client ids has not existed
+    /// Expectation:
+    /// A [CommonError] should be returned and the update should be rolled back
+    #[test]
+    fn when_update_balances_with_invalid_client_id_should_roll_back() {
+        let repository = SqliteClientRepository::open_in_memory().unwrap();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+
+        let expected_result =
+            repository.update_balances(&[(CLIENT_ID, Decimal::ONE), (3, Decimal::ONE)]);
+
+        assert_eq!(CommonError::ClientNotFound(3), expected_result.unwrap_err());
+        assert!(repository.list().unwrap()[0].balance.is_zero());
+    }
+
+    /// Scenario:
+    /// Execute insert with a new client id and a document number already saved
+    /// Expectation:
+    /// A [CommonError::DocumentAlreadyExists] should be returned
+    #[test]
+    fn when_insert_with_existing_document_number_should_return_common_error() {
+        let repository = SqliteClientRepository::open_in_memory().unwrap();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+
+        let expected_result = repository.insert(map_client(create_new_client_stub(), 2));
+
+        assert_eq!(
+            CommonError::DocumentAlreadyExists(create_new_client_stub().document_number),
+            expected_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_profile on a client with a balance
+    /// Expectation:
+    /// The profile and version should be saved and the balance kept
+    #[test]
+    fn when_update_profile_should_keep_the_balance() {
+        let repository = SqliteClientRepository::open_in_memory().unwrap();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+        repository
+            .update_balance(CLIENT_ID, Decimal::new(1050, 2))
+            .unwrap();
+        let mut client = repository.get(CLIENT_ID).unwrap().unwrap();
+        client.country = "UY".to_string();
+        client.balance = Decimal::ZERO;
+        client.version += 1;
+
+        repository.update_profile(&client).unwrap();
+
+        let saved_client = repository.get(CLIENT_ID).unwrap().unwrap();
+        assert_eq!("UY", saved_client.country);
+        assert_eq!(2, saved_client.version);
+        assert_eq!(Decimal::new(1050, 2), saved_client.balance);
+    }
+
+    /// Scenario:
+    /// Execute update_status on a database file that is opened again
+    /// Expectation:
+    /// The status should survive the reopening
+    #[test]
+    fn when_update_status_should_keep_it_after_reopening() {
+        let path = std::env::temp_dir().join(format!("prex-{}.sqlite", uuid::Uuid::new_v4()));
+        {
+            let repository = SqliteClientRepository::open(&path).unwrap();
+            repository
+                .insert(map_client(create_new_client_stub(), CLIENT_ID))
+                .unwrap();
+            repository
+                .update_status(CLIENT_ID, ClientStatus::Blocked)
+                .unwrap();
+        }
+
+        let repository = SqliteClientRepository::open(&path).unwrap();
+        let client = repository.get(CLIENT_ID).unwrap().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(ClientStatus::Blocked, client.status);
+        assert_eq!(
+            CommonError::ClientNotFound(2),
+            repository
+                .update_status(2, ClientStatus::Closed)
+                .unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_held_balance and update_balance on the same client
+    /// Expectation:
+    /// Both balances should be saved independently
+    #[test]
+    fn when_update_held_balance_should_keep_the_balance() {
+        let repository = SqliteClientRepository::open_in_memory().unwrap();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+
+        repository
+            .update_balance(CLIENT_ID, Decimal::new(1000, 2))
+            .unwrap();
+        repository
+            .update_held_balance(CLIENT_ID, Decimal::new(250, 2))
+            .unwrap();
+
+        let client = repository.get(CLIENT_ID).unwrap().unwrap();
+        assert_eq!(Decimal::new(1000, 2), client.balance);
+        assert_eq!(Decimal::new(250, 2), client.held_balance);
+        assert_eq!(Decimal::new(750, 2), client.available_balance());
+        assert_eq!(
+            CommonError::ClientNotFound(2),
+            repository.update_held_balance(2, Decimal::ONE).unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_balance_and_held_balance on an existing client and on a missing one
+    /// Expectation:
+    /// Both balances should be saved together and a [CommonError] returned for the missing client
+    #[test]
+    fn when_update_balance_and_held_balance_should_save_both() {
+        let repository = SqliteClientRepository::open_in_memory().unwrap();
+        repository
+            .insert(map_client(create_new_client_stub(), CLIENT_ID))
+            .unwrap();
+
+        repository
+            .update_balance_and_held_balance(CLIENT_ID, Decimal::new(600, 2), Decimal::new(150, 2))
+            .unwrap();
+
+        let client = repository.get(CLIENT_ID).unwrap().unwrap();
+        assert_eq!(Decimal::new(600, 2), client.balance);
+        assert_eq!(Decimal::new(150, 2), client.held_balance);
+        assert_eq!(
+            CommonError::ClientNotFound(2),
+            repository
+                .update_balance_and_held_balance(2, Decimal::ONE, Decimal::ONE)
+                .unwrap_err()
+        );
+    }
+}
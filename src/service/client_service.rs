@@ -1,22 +1,72 @@
-use crate::constants::constants::{CLIENT_BALANCE_FOLDER, PREFIX_FILE};
+use crate::balance_file::balance_file_format::{parse_balance_file, render_balance_file};
+use crate::balance_file::balance_file_name_pattern::DATE_FORMAT;
+use crate::balance_file::balance_file_reconciliation::reconcile_balance_file;
+use crate::client_import::client_import_format::{
+    parse_client_import, ClientImportFormat, ClientImportRow,
+};
+use crate::constants::constants::{
+    DEFAULT_HOLD_EXPIRY_SECONDS, DEFAULT_PAGE_SIZE, INITIAL_CLIENT_VERSION, MAX_PAGE_SIZE,
+    TEMPORARY_FILE_SUFFIX,
+};
+use crate::dto::capture_hold_dto::CaptureHold;
+use crate::dto::client_filter_dto::{ClientFilter, ClientSortField};
+use crate::dto::client_import_report_dto::{ClientImportReport, ImportedClient, RejectedClientRow};
 use crate::dto::client_info_dto::ClientInfo;
+use crate::dto::client_page_dto::ClientPage;
+use crate::dto::client_statement_dto::ClientStatement;
+use crate::dto::close_client_dto::CloseClient;
+use crate::dto::hold_info_dto::HoldInfo;
 use crate::dto::new_client_dto::NewClient;
 use crate::dto::new_credit_transaction::NewCreditTransaction;
 use crate::dto::new_debit_transaction::NewDebitTransaction;
+use crate::dto::new_hold_dto::NewHold;
+use crate::dto::new_transfer_dto::NewTransfer;
+use crate::dto::opening_balances_report_dto::OpeningBalancesReport;
+use crate::dto::reconciliation_report_dto::ReconciliationReport;
+use crate::dto::reverse_transaction_dto::ReverseTransaction;
+use crate::dto::transaction_batch_dto::{BatchMode, BatchTransaction, NewTransactionBatch};
+use crate::dto::transaction_batch_report_dto::{
+    BatchItemResult, BatchItemStatus, TransactionBatchReport,
+};
+use crate::dto::transaction_filter_dto::TransactionFilter;
+use crate::dto::transaction_info_dto::TransactionInfo;
+use crate::dto::transaction_page_dto::TransactionPage;
+use crate::dto::transfer_info_dto::TransferInfo;
+use crate::dto::update_client_dto::UpdateClient;
 use crate::errors::common_error::CommonError;
 use crate::mapper::client_info_mapper::map_client_info;
+use crate::mapper::hold_info_mapper::map_hold_info;
 use crate::mapper::new_client_mapper::map_client;
-use crate::model::client_model::Client;
+use crate::mapper::statement_line_mapper::map_statement_line;
+use crate::mapper::transaction_info_mapper::map_transaction_info;
+use crate::model::client_cursor_model::{compare_by_name, ClientCursor};
+use crate::model::client_model::{Client, ClientStatus, ClosureReason};
+use crate::model::event_model::{Event, EventRecord, IdempotentRequest};
+use crate::model::hold_model::{Hold, HoldStatus};
+use crate::model::transaction_model::{Transaction, TransactionKind};
+use crate::repository::client_repository::DynClientRepository;
+use crate::repository::in_memory_client_repository::InMemoryClientRepository;
 use crate::state::app_state::AppState;
+use crate::state::idempotency_store::IdempotencyRecord;
+use crate::validator::client_validator::{validate_new_client, validate_update_client};
+use crate::validator::transaction_validator::{
+    validate_amount, validate_batch_transaction, validate_new_credit_transaction,
+    validate_new_debit_transaction, validate_new_hold, validate_new_transaction_batch,
+    validate_new_transfer,
+};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use log::{debug, error, info};
+#[cfg(test)]
 use mockall::automock;
 use rust_decimal::prelude::Zero;
 use rust_decimal::Decimal;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Instant;
 use tokio::fs::{self};
 use tokio::io::AsyncWriteExt;
 
@@ -25,9 +75,20 @@ use tokio::io::AsyncWriteExt;
 #[cfg_attr(test, automock)]
 pub trait ClientServiceTrait {
     /// Create new Client from [NewClient] new_client
-    /// Returns a [CommonError] if the document number already exists or service throws any error
+    /// Returns a [CommonError] if a field is invalid, the document number already exists or service throws any error
     async fn create_new_client(&self, new_client: NewClient) -> Result<i32, CommonError>;
 
+    /// Create a client from every row of [String] content written in [ClientImportFormat] format
+    /// Rows with an invalid field or a document number repeated in the file or already registered are rejected,
+    /// the rest are created as [NewClient]
+    /// Returns a [ClientImportReport] with the created ids and the rejected rows
+    /// Returns a [CommonError] if service throws any error
+    async fn import_clients(
+        &self,
+        content: String,
+        format: ClientImportFormat,
+    ) -> Result<ClientImportReport, CommonError>;
+
     /// Create a new credit transaction from [NewCreditTransaction] credit_transaction
     /// Returns a [CommonError] if client_id has not existed or service throws any error
     async fn create_new_credit_transaction(
@@ -42,24 +103,561 @@ pub trait ClientServiceTrait {
         debit_transaction: NewDebitTransaction,
     ) -> Result<Decimal, CommonError>;
 
+    /// Apply the credits and debits of [NewTransactionBatch] batch in order, under a single ledger lock
+    /// Returns a [TransactionBatchReport] with the result of every item
+    /// Returns a [CommonError] if the batch is empty or too big, an item is rejected in all or nothing mode
+    /// or service throws any error
+    async fn create_transaction_batch(
+        &self,
+        batch: NewTransactionBatch,
+    ) -> Result<TransactionBatchReport, CommonError>;
+
+    /// Post a linked entry compensating the transaction of [i32] transaction_id,
+    /// for the rest of it or the amount of [ReverseTransaction] reverse_transaction
+    /// Returns a [CommonError] if transaction_id has not existed, it is a reversal itself or already fully reversed,
+    /// the amount is greater than the rest of the transaction, the account is not active or service throws any error
+    async fn reverse_transaction(
+        &self,
+        transaction_id: i32,
+        reverse_transaction: ReverseTransaction,
+    ) -> Result<TransactionInfo, CommonError>;
+
+    /// Reserve money on a client account from [NewHold] new_hold, without posting it
+    /// Returns a [CommonError] if client_id has not existed, the account is not active,
+    /// the hold exceeds its overdraft limit or service throws any error
+    async fn create_hold(&self, new_hold: NewHold) -> Result<HoldInfo, CommonError>;
+
+    /// Debit the whole hold of [i32] hold_id or the amount of [CaptureHold] capture_hold, releasing the rest
+    /// Returns a [CommonError] if hold_id has not existed, the hold or its account is not active,
+    /// the amount is greater than the hold or service throws any error
+    async fn capture_hold(
+        &self,
+        hold_id: i32,
+        capture_hold: CaptureHold,
+    ) -> Result<HoldInfo, CommonError>;
+
+    /// Release the money reserved by the hold of [i32] hold_id without debiting it
+    /// Returns a [CommonError] if hold_id has not existed, the hold is not active or service throws any error
+    async fn release_hold(&self, hold_id: i32) -> Result<HoldInfo, CommonError>;
+
+    /// Create a transfer between two clients from [NewTransfer] transfer
+    /// Returns a [CommonError] if any client_id has not existed or service throws any error
+    async fn create_transfer(&self, transfer: NewTransfer) -> Result<TransferInfo, CommonError>;
+
     /// Generate file.DAT with all client's balances
     /// Returns a [CommonError] if the file cannot be generator or service throws any error
     async fn generate_file_with_all_clients_balances(&self) -> Result<(), CommonError>;
 
+    /// Check the balances of [String] file_name in the balance folder against the movements recorded before it was written
+    /// Returns a [ReconciliationReport] with the clients whose balance does not match
+    /// Returns a [CommonError] if the file name is invalid, the file was not written by a balance reset,
+    /// it cannot be read or service throws any error
+    async fn reconcile_balance_file(
+        &self,
+        file_name: String,
+    ) -> Result<ReconciliationReport, CommonError>;
+
     /// Get [ClientInfo] from [i32] client_id
     /// Returns a [CommonError] if client_id has not existed or service throws any error
     async fn get_client_balance(&self, client_id: i32) -> Result<ClientInfo, CommonError>;
+
+    /// Get [ClientInfo] from [String] document_number
+    /// Returns a [CommonError] if the document number has not existed or service throws any error
+    async fn get_client_by_document(
+        &self,
+        document_number: String,
+    ) -> Result<ClientInfo, CommonError>;
+
+    /// Get a [TransactionPage] with the transactions of [i32] client_id matching [TransactionFilter] filter
+    /// Returns a [CommonError] if client_id has not existed or service throws any error
+    async fn get_client_transactions(
+        &self,
+        client_id: i32,
+        filter: TransactionFilter,
+    ) -> Result<TransactionPage, CommonError>;
+
+    /// Get the [ClientStatement] of [i32] client_id from [NaiveDate] from to [NaiveDate] to, both days included
+    /// Returns a [CommonError] if from is after to, client_id has not existed or service throws any error
+    async fn get_client_statement(
+        &self,
+        client_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ClientStatement, CommonError>;
+
+    /// Change the informed fields of [UpdateClient] update_client on [i32] client_id, if its version is still [u64] version
+    /// Returns a [CommonError] if a field is invalid, client_id has not existed, the version has changed,
+    /// the new document number already exists or service throws any error
+    async fn update_client(
+        &self,
+        client_id: i32,
+        version: u64,
+        update_client: UpdateClient,
+    ) -> Result<ClientInfo, CommonError>;
+
+    /// Get a [ClientPage] with the clients matching [ClientFilter] filter
+    /// Returns a [CommonError] if the filter or the cursor is invalid or service throws any error
+    async fn list_clients(&self, filter: ClientFilter) -> Result<ClientPage, CommonError>;
+
+    /// Block the active account of [i32] client_id, credits and debits are rejected until it is unblocked
+    /// Returns a [CommonError] if client_id has not existed, the account is not active or service throws any error
+    async fn block_client(&self, client_id: i32) -> Result<ClientInfo, CommonError>;
+
+    /// Activate again the blocked account of [i32] client_id
+    /// Returns a [CommonError] if client_id has not existed, the account is not blocked or service throws any error
+    async fn unblock_client(&self, client_id: i32) -> Result<ClientInfo, CommonError>;
+
+    /// Close the account of [i32] client_id for the reason code of [CloseClient] close_client
+    /// Returns a [CommonError] if client_id has not existed, the account is already closed,
+    /// its balance is not zero, it has active holds or service throws any error
+    async fn close_client(
+        &self,
+        client_id: i32,
+        close_client: CloseClient,
+    ) -> Result<ClientInfo, CommonError>;
 }
 
 /// Client service implementation struct
 pub struct ClientService {
     pub app_state: Arc<AppState>,
+    pub client_repository: DynClientRepository,
 }
 
 /// Initialization
 impl ClientService {
+    /// Client service keeping the clients in the [AppState] clients hashmap
     pub fn new(app_state: Arc<AppState>) -> Self {
-        ClientService { app_state }
+        let client_repository = Arc::new(InMemoryClientRepository::new(Arc::clone(
+            &app_state.clients,
+        )));
+        ClientService::with_repository(app_state, client_repository)
+    }
+
+    /// Client service keeping the clients in [DynClientRepository] client_repository
+    pub fn with_repository(
+        app_state: Arc<AppState>,
+        client_repository: DynClientRepository,
+    ) -> Self {
+        ClientService {
+            app_state,
+            client_repository,
+        }
+    }
+
+    /// Apply every [EventRecord] records read from the event log, without writing them again
+    /// Client ids, transaction ids and hold ids keep going after the replayed ones
+    /// Returns the number of replayed records or a [CommonError] if any of them cannot be applied
+    pub fn restore_from_event_log(&self, records: Vec<EventRecord>) -> Result<usize, CommonError> {
+        info!("restore_from_event_log - start");
+
+        let mut transactions_map = self.write_transactions()?;
+        // idempotency keys of the replayed transactions, with the balance they returned and when they were posted
+        let mut idempotent_requests = Vec::new();
+        for record in &records {
+            match &record.event {
+                Event::ClientCreated {
+                    client_id,
+                    client_name,
+                    birth_date,
+                    document_number,
+                    country,
+                } => {
+                    self.client_repository.insert(Client {
+                        client_id: *client_id,
+                        client_name: client_name.clone(),
+                        birth_date: *birth_date,
+                        document_number: document_number.clone(),
+                        country: country.clone(),
+                        balance: Decimal::zero(),
+                        version: INITIAL_CLIENT_VERSION,
+                        status: ClientStatus::Active,
+                        held_balance: Decimal::zero(),
+                    })?;
+                    self.app_state
+                        .client_id_unique
+                        .fetch_max(client_id + 1, Ordering::SeqCst);
+                }
+                Event::ClientUpdated {
+                    client_id,
+                    client_name,
+                    birth_date,
+                    document_number,
+                    country,
+                    version,
+                } => {
+                    let mut client = self.get_existing_client(*client_id)?;
+                    client.client_name = client_name.clone();
+                    client.birth_date = *birth_date;
+                    client.document_number = document_number.clone();
+                    client.country = country.clone();
+                    client.version = *version;
+                    self.client_repository.update_profile(&client)?;
+                }
+                Event::ClientStatusChanged {
+                    client_id, status, ..
+                } => {
+                    self.client_repository.update_status(*client_id, *status)?;
+                }
+                Event::TransactionPosted {
+                    client_id,
+                    kind,
+                    amount,
+                    idempotent_request,
+                } => {
+                    let client = self.apply_transaction(
+                        &mut transactions_map,
+                        *client_id,
+                        *kind,
+                        *amount,
+                        record.created_at,
+                    )?;
+                    if let Some(idempotent_request) = idempotent_request {
+                        idempotent_requests.push((
+                            idempotent_request.clone(),
+                            client.balance,
+                            record.created_at,
+                        ));
+                    }
+                }
+                Event::HoldPlaced {
+                    hold_id,
+                    client_id,
+                    amount,
+                    expires_at,
+                } => {
+                    self.apply_hold_placed(
+                        *hold_id,
+                        *client_id,
+                        *amount,
+                        record.created_at,
+                        *expires_at,
+                    )?;
+                    self.app_state
+                        .hold_id_unique
+                        .fetch_max(hold_id + 1, Ordering::SeqCst);
+                }
+                Event::HoldCaptured { hold_id, amount } => {
+                    self.apply_hold_captured(
+                        &mut transactions_map,
+                        *hold_id,
+                        *amount,
+                        record.created_at,
+                    )?;
+                }
+                Event::HoldReleased { hold_id, status } => {
+                    self.apply_hold_released(*hold_id, *status)?;
+                }
+                Event::TransactionBatchPosted { transactions } => {
+                    self.apply_transaction_batch(
+                        &mut transactions_map,
+                        transactions,
+                        record.created_at,
+                    )?;
+                }
+                Event::TransactionReversed {
+                    transaction_id,
+                    amount,
+                } => {
+                    self.apply_transaction_reversed(
+                        &mut transactions_map,
+                        *transaction_id,
+                        *amount,
+                        record.created_at,
+                    )?;
+                }
+                Event::TransferPosted {
+                    from_client_id,
+                    to_client_id,
+                    amount,
+                } => {
+                    self.apply_transfer(
+                        &mut transactions_map,
+                        *from_client_id,
+                        *to_client_id,
+                        *amount,
+                        record.created_at,
+                    )?;
+                }
+                Event::BalancesReset {
+                    stored_balances,
+                    file_name,
+                    ledger_cut,
+                } => {
+                    self.apply_balance_entries(
+                        &mut transactions_map,
+                        TransactionKind::Settlement,
+                        stored_balances,
+                        record.created_at,
+                    )?;
+                    if let (Some(file_name), Some(ledger_cut)) = (file_name, ledger_cut) {
+                        self.register_balance_file(file_name, *ledger_cut)?;
+                    }
+                }
+                Event::OpeningBalancesLoaded {
+                    opening_balances, ..
+                } => {
+                    self.apply_balance_entries(
+                        &mut transactions_map,
+                        TransactionKind::OpeningBalance,
+                        opening_balances,
+                        record.created_at,
+                    )?;
+                }
+            }
+        }
+        // the idempotency keys are locked before the ledger when posting, so they are saved once it is released
+        drop(transactions_map);
+        self.restore_idempotency_records(idempotent_requests)?;
+
+        info!("restore_from_event_log - done - records: {}", records.len());
+        Ok(records.len())
+    }
+
+    /// Save the idempotency keys of the [IdempotentRequest] requests replayed from the event log,
+    /// with the balance they returned and when they were posted, leaving out the keys already expired
+    /// Returns a [CommonError] if the idempotency keys cannot be locked
+    fn restore_idempotency_records(
+        &self,
+        idempotent_requests: Vec<(IdempotentRequest, Decimal, DateTime<Utc>)>,
+    ) -> Result<(), CommonError> {
+        let idempotency_store = &self.app_state.idempotency_store;
+        let mut records = self.lock_idempotency_records()?;
+        let now = Utc::now();
+        for (idempotent_request, balance, created_at) in idempotent_requests {
+            let age = (now - created_at).to_std().unwrap_or_default();
+            if let Some(record) =
+                idempotency_store.restore_record(idempotent_request.fingerprint, balance, age)
+            {
+                records.insert(idempotent_request.idempotency_key, record);
+            }
+        }
+        debug!(
+            "restore_idempotency_records - done - idempotency keys: {}",
+            records.len()
+        );
+        Ok(())
+    }
+
+    /// Apply every [EventRecord] records read from the event log to the ledger, the holds, the balance files,
+    /// the idempotency keys and the ids of the [AppState], for repositories that already keep the clients,
+    /// which are not changed
+    /// Returns the clients as rebuilt by the records or a [CommonError] if any of them cannot be applied
+    pub fn restore_ledger_from_event_log(
+        &self,
+        records: Vec<EventRecord>,
+    ) -> Result<Vec<Client>, CommonError> {
+        info!("restore_ledger_from_event_log - start");
+
+        // the records are replayed on clients kept apart, sharing the ledger, the holds and the balance files
+        let replay_service = ClientService::new(Arc::new(AppState {
+            transactions: Arc::clone(&self.app_state.transactions),
+            transaction_positions: Arc::clone(&self.app_state.transaction_positions),
+            holds: Arc::clone(&self.app_state.holds),
+            balance_file_ledger_cuts: Arc::clone(&self.app_state.balance_file_ledger_cuts),
+            ..Default::default()
+        }));
+        let replay_state = &replay_service.app_state;
+        replay_service.restore_from_event_log(records)?;
+
+        // ids keep going after the replayed ones
+        for (id_unique, replayed_id_unique) in [
+            (
+                &self.app_state.client_id_unique,
+                &replay_state.client_id_unique,
+            ),
+            (
+                &self.app_state.transaction_id_unique,
+                &replay_state.transaction_id_unique,
+            ),
+            (&self.app_state.hold_id_unique, &replay_state.hold_id_unique),
+        ] {
+            id_unique.fetch_max(replayed_id_unique.load(Ordering::SeqCst), Ordering::SeqCst);
+        }
+
+        // idempotency keys are moved over, so retries after a restart are still replayed
+        let replayed_records = std::mem::take(&mut *replay_service.lock_idempotency_records()?);
+        self.lock_idempotency_records()?.extend(replayed_records);
+
+        let clients = replay_service.client_repository.list()?;
+        info!(
+            "restore_ledger_from_event_log - done - transaction id: {}",
+            self.app_state.transaction_id_unique.load(Ordering::SeqCst)
+        );
+        Ok(clients)
+    }
+
+    /// Release the active holds that expired before [DateTime] now
+    /// Returns the released hold ids or a [CommonError] if any of them cannot be released
+    pub fn release_expired_holds(&self, now: DateTime<Utc>) -> Result<Vec<i32>, CommonError> {
+        debug!("release_expired_holds - start");
+
+        // the ledger lock serializes every balance change
+        let _transactions_map = self.write_transactions()?;
+
+        let mut expired_hold_ids: Vec<i32> = self
+            .read_holds()?
+            .values()
+            .filter(|hold| hold.status == HoldStatus::Active && !hold.is_active(now))
+            .map(|hold| hold.hold_id)
+            .collect();
+        expired_hold_ids.sort_unstable();
+        for hold_id in &expired_hold_ids {
+            self.expire_hold(*hold_id, now)?;
+        }
+
+        debug!(
+            "release_expired_holds - done - hold ids: {:?}",
+            expired_hold_ids
+        );
+        Ok(expired_hold_ids)
+    }
+
+    /// Spawn a tokio task running [ClientService::release_expired_holds] every [Duration] period
+    pub fn spawn_hold_expiry_task(
+        self: Arc<Self>,
+        period: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                match self.release_expired_holds(Utc::now()) {
+                    Ok(hold_ids) if !hold_ids.is_empty() => {
+                        info!("hold expiry - released hold ids: {:?}", hold_ids)
+                    }
+                    Ok(_) => {}
+                    Err(error) => error!("hold expiry - error: {:?}", error),
+                }
+            }
+        })
+    }
+
+    /// Add the balances of the most recent balance file in the balance folder to the current clients
+    /// Files named in [HashSet] loaded_file_names are not loaded again,
+    /// nor files whose balances were already settled by a reset in the current state
+    /// Returns an [OpeningBalancesReport], None if there is no file to load,
+    /// or a [CommonError] if the file cannot be read or is invalid
+    pub async fn restore_from_latest_balance_file(
+        &self,
+        loaded_file_names: &HashSet<String>,
+    ) -> Result<Option<OpeningBalancesReport>, CommonError> {
+        info!("restore_from_latest_balance_file - start");
+
+        let Some(file_name) = self.find_latest_balance_file_name().await? else {
+            info!("restore_from_latest_balance_file - done - no balance file found");
+            return Ok(None);
+        };
+        if loaded_file_names.contains(&file_name) {
+            info!("restore_from_latest_balance_file - done - {file_name} was already loaded");
+            return Ok(None);
+        }
+        // the balances of a stored file are already part of the ledger replayed from the event log
+        match self.ledger_cut_of(&file_name) {
+            Ok(_) => {
+                info!("restore_from_latest_balance_file - done - {file_name} was already settled");
+                return Ok(None);
+            }
+            Err(CommonError::BalanceFileNotFound(_)) => {}
+            Err(error) => return Err(error),
+        }
+
+        let file_path = Path::new(&self.app_state.balance_folder).join(&file_name);
+        let content = fs::read_to_string(&file_path).await.map_err(|error| {
+            error!(
+                "restore_from_latest_balance_file - error when reading file error: {:?}",
+                error
+            );
+            CommonError::FileReadFailed(file_path.display().to_string())
+        })?;
+        let balance_file = parse_balance_file(&content)?;
+
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        let mut opening_balances = Vec::new();
+        let mut missing_client_ids = Vec::new();
+        for (client_id, balance) in balance_file.balances {
+            match self.client_repository.get(client_id)? {
+                Some(_client) => opening_balances.push((client_id, balance)),
+                None => {
+                    error!("restore_from_latest_balance_file - client id not found - file name: {file_name} - client id: {client_id}");
+                    missing_client_ids.push(client_id);
+                }
+            }
+        }
+        let restored_client_ids = opening_balances
+            .iter()
+            .map(|(client_id, _)| *client_id)
+            .collect();
+
+        let created_at = Utc::now();
+        self.write_ahead(
+            Event::OpeningBalancesLoaded {
+                file_name: file_name.clone(),
+                opening_balances: opening_balances.clone(),
+            },
+            created_at,
+        )?;
+        self.apply_balance_entries(
+            &mut transactions_map,
+            TransactionKind::OpeningBalance,
+            &opening_balances,
+            created_at,
+        )?;
+
+        info!("restore_from_latest_balance_file - done - file name: {file_name}");
+        Ok(Some(OpeningBalancesReport {
+            file_name,
+            restored_client_ids,
+            missing_client_ids,
+        }))
+    }
+
+    /// Check the balances of [str] content, the balance file named [str] file_name, against the movements of the ledger
+    /// The expected balance of a client is the sum of its movements recorded before the file was written: the balance
+    /// left by the previous file plus the credits and debits since then
+    /// Returns a [ReconciliationReport] with the clients whose balance does not match
+    /// Returns a [CommonError] if the file was not written by a balance reset, it is invalid or the RwLock cannot be read
+    pub fn reconcile_balance_content(
+        &self,
+        file_name: &str,
+        content: &str,
+    ) -> Result<ReconciliationReport, CommonError> {
+        let ledger_cut = self.ledger_cut_of(file_name)?;
+        let balance_file = parse_balance_file(content)?;
+
+        let transactions_map = self.app_state.transactions.read().map_err(|error| {
+            error!(
+                "reconcile_balance_content - error when reading transactions - error: {:?}",
+                error
+            );
+            CommonError::LockReadFailed
+        })?;
+        // the ledger of each client is sorted by transaction id
+        let expected_balances: BTreeMap<i32, Decimal> = transactions_map
+            .iter()
+            .filter(|(_, transactions)| {
+                transactions
+                    .first()
+                    .is_some_and(|transaction| transaction.transaction_id < ledger_cut)
+            })
+            .map(|(client_id, transactions)| {
+                let expected_balance = transactions
+                    .iter()
+                    .take_while(|transaction| transaction.transaction_id < ledger_cut)
+                    .map(|transaction| transaction.kind.balance_change(transaction.amount))
+                    .sum();
+                (*client_id, expected_balance)
+            })
+            .collect();
+
+        let (checked_clients, mismatches) =
+            reconcile_balance_file(&balance_file, &expected_balances);
+        Ok(ReconciliationReport {
+            file_name: file_name.to_string(),
+            checked_clients,
+            mismatches,
+        })
     }
 }
 
@@ -67,40 +665,95 @@ impl ClientService {
 #[async_trait]
 impl ClientServiceTrait for ClientService {
     /// Create new Client from [NewClient] new_client
-    /// Returns a [CommonError] if the document number already exists or service throws any error
+    /// Returns a [CommonError] if a field is invalid, the document number already exists or service throws any error
     async fn create_new_client(&self, new_client: NewClient) -> Result<i32, CommonError> {
         info!("create_new_client - start");
-        let document_number = &new_client.document_number;
 
-        // verify if client document exists
-        match self.validate_client_document(document_number) {
-            Ok(_exist) => {
-                // Generate unique id for each client
-                let client_id = self.generate_client_id();
-
-                // map Client from NewClient
-                let populate_new_client = map_client(new_client, client_id);
-
-                match self.app_state.clients.write() {
-                    Ok(mut app_state) => {
-                        app_state.insert(client_id, populate_new_client);
-                        info!("create_new_client - done");
-                        Ok(client_id)
-                    }
-                    Err(_) => {
-                        error!("create_new_client - error - has occurred an error while try write in app_state");
-                        Err(CommonError::LockWriteFailed)
-                    }
-                }
+        // held until the client is inserted, a concurrent registration of the same document waits and then fails the check
+        let _registration_guard =
+            self.app_state
+                .client_registration_lock
+                .lock()
+                .map_err(|error| {
+                    error!(
+                    "create_new_client - error when locking the client registration - error: {:?}",
+                    error
+                );
+                    CommonError::LockWriteFailed
+                })?;
+
+        match self.register_client(new_client, Local::now().date_naive()) {
+            Ok(client_id) => {
+                info!("create_new_client - done");
+                Ok(client_id)
             }
             Err(error) => {
                 error!("create_new_client - error - error: {:?}", error);
-                error!("create_new_client - error - document_number: {document_number}");
                 Err(error)
             }
         }
     }
 
+    /// Parse [String] content as [ClientImportFormat] format and register its rows one by one,
+    /// holding the registration lock for the whole file so no concurrent registration takes one of its documents
+    /// Returns a [ClientImportReport] with the created ids and the rejected rows, or a [CommonError] if the lock fails
+    async fn import_clients(
+        &self,
+        content: String,
+        format: ClientImportFormat,
+    ) -> Result<ClientImportReport, CommonError> {
+        info!("import_clients - start - format: {:?}", format);
+
+        let rows = parse_client_import(&content, format);
+
+        // held for the whole file, a concurrent registration cannot take a document of the file
+        let _registration_guard =
+            self.app_state
+                .client_registration_lock
+                .lock()
+                .map_err(|error| {
+                    error!(
+                        "import_clients - error when locking the client registration - error: {:?}",
+                        error
+                    );
+                    CommonError::LockWriteFailed
+                })?;
+
+        let today = Local::now().date_naive();
+        // line where each document number of the file was first seen
+        let mut document_lines = HashMap::new();
+        let mut report = ClientImportReport::default();
+        for row in rows {
+            let line = row.line;
+            let document_number = row
+                .new_client
+                .as_ref()
+                .ok()
+                .map(|new_client| new_client.document_number.clone());
+            match self.import_client_row(row, today, &mut document_lines) {
+                Ok(client_id) => report.created.push(ImportedClient {
+                    line,
+                    client_id,
+                    document_number: document_number.unwrap_or_default(),
+                }),
+                Err(error) => report.rejected.push(RejectedClientRow {
+                    line,
+                    document_number,
+                    error_code: error.code().to_string(),
+                    reason: error.to_string(),
+                }),
+            }
+        }
+        report.created_count = report.created.len();
+        report.rejected_count = report.rejected.len();
+
+        info!(
+            "import_clients - done - created: {} - rejected: {}",
+            report.created_count, report.rejected_count
+        );
+        Ok(report)
+    }
+
     /// Create a new transaction from [NewCreditTransaction] credit_transaction
     /// Returns a [CommonError] if client_id has not existed or service throws any error
     async fn create_new_credit_transaction(
@@ -110,12 +763,30 @@ impl ClientServiceTrait for ClientService {
         info!("create_new_credit_transaction - start");
         let client_id = transaction.client_id;
 
+        validate_new_credit_transaction(&transaction, &self.app_state.transaction_limits)?;
+
         // validate if client id exists
         match self.validate_client_id(client_id) {
             Ok(client) => {
-                // update client balance
-                match self.new_credit_on_client_account(client.client_id, transaction.credit_amount)
-                {
+                let fingerprint = format!(
+                    "{:?}:{}:{}",
+                    TransactionKind::Credit,
+                    client.client_id,
+                    transaction.credit_amount.normalize()
+                );
+
+                // update client balance, only once per idempotency key
+                match self.apply_once_per_idempotency_key(
+                    transaction.idempotency_key.as_deref(),
+                    fingerprint,
+                    |idempotent_request| {
+                        self.new_credit_on_client_account(
+                            client.client_id,
+                            transaction.credit_amount,
+                            idempotent_request,
+                        )
+                    },
+                ) {
                     Ok(balance) => {
                         info!("create_new_credit_transaction - done");
                         Ok(balance)
@@ -143,11 +814,30 @@ impl ClientServiceTrait for ClientService {
 
         let client_id = transaction.client_id;
 
+        validate_new_debit_transaction(&transaction, &self.app_state.transaction_limits)?;
+
         // validate if client id exists
         match self.validate_client_id(client_id) {
             Ok(client) => {
-                // update client balance
-                match self.new_debit_on_client_account(client.client_id, transaction.debit_amount) {
+                let fingerprint = format!(
+                    "{:?}:{}:{}",
+                    TransactionKind::Debit,
+                    client.client_id,
+                    transaction.debit_amount.normalize()
+                );
+
+                // update client balance, only once per idempotency key
+                match self.apply_once_per_idempotency_key(
+                    transaction.idempotency_key.as_deref(),
+                    fingerprint,
+                    |idempotent_request| {
+                        self.new_debit_on_client_account(
+                            client.client_id,
+                            transaction.debit_amount,
+                            idempotent_request,
+                        )
+                    },
+                ) {
                     Ok(balance) => {
                         info!("create_new_debit_transaction - done");
                         Ok(balance)
@@ -165,13 +855,57 @@ impl ClientServiceTrait for ClientService {
         }
     }
 
+    /// Create a transfer between two clients from [NewTransfer] transfer
+    /// Returns a [CommonError] if any client_id has not existed or service throws any error
+    async fn create_transfer(&self, transfer: NewTransfer) -> Result<TransferInfo, CommonError> {
+        info!("create_transfer - start");
+
+        let from_client_id = transfer.from_client_id;
+        let to_client_id = transfer.to_client_id;
+
+        validate_new_transfer(&transfer, &self.app_state.transaction_limits)?;
+
+        // validate if both client ids exist
+        if let Err(error) = self
+            .validate_client_id(from_client_id)
+            .and_then(|_| self.validate_client_id(to_client_id))
+        {
+            error!("create_transfer - error: {:?}", error);
+            return Err(error);
+        }
+
+        // update both balances at once
+        match self.new_transfer_between_client_accounts(
+            from_client_id,
+            to_client_id,
+            transfer.transfer_amount,
+        ) {
+            Ok((from_balance, to_balance)) => {
+                info!("create_transfer - done");
+                Ok(TransferInfo {
+                    from_client_id,
+                    from_balance,
+                    to_client_id,
+                    to_balance,
+                })
+            }
+            Err(error) => {
+                error!("create_transfer - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
     /// Generate file.txt with all client's balances
     /// Returns a [CommonError] if the file cannot be generator or service throws any error
     async fn generate_file_with_all_clients_balances(&self) -> Result<(), CommonError> {
         info!("generate_file_with_all_clients_balances - start");
 
+        // the stored balances are subtracted once the file is written, so two files cannot share a snapshot
+        let _balance_file_lock = self.app_state.balance_file_lock.lock().await;
+
         let date = Local::now();
-        let date_to_string = date.format("%d%m%Y").to_string();
+        let date_to_string = date.format(DATE_FORMAT).to_string();
 
         match self.generate_next_balance_file_name(date_to_string).await {
             Ok(file_name) => {
@@ -202,6 +936,50 @@ impl ClientServiceTrait for ClientService {
         }
     }
 
+    async fn reconcile_balance_file(
+        &self,
+        file_name: String,
+    ) -> Result<ReconciliationReport, CommonError> {
+        info!("reconcile_balance_file - start");
+
+        let file_name_pattern = &self.app_state.balance_file_name_pattern;
+        if file_name_pattern.parse_file_name(&file_name).is_none() {
+            error!("reconcile_balance_file - error - invalid file name - file name: {file_name}");
+            return Err(CommonError::InvalidField {
+                field: "file_name".to_string(),
+                reason: format!("must match the pattern {}", file_name_pattern.as_str()),
+            });
+        }
+
+        // a file that was not written by a balance reset is not read at all
+        if let Err(error) = self.ledger_cut_of(&file_name) {
+            error!("reconcile_balance_file - error: {:?}", error);
+            return Err(error);
+        }
+        let file_path = Path::new(&self.app_state.balance_folder).join(&file_name);
+        let content = fs::read_to_string(&file_path).await.map_err(|error| {
+            error!(
+                "reconcile_balance_file - error when reading file error: {:?}",
+                error
+            );
+            CommonError::FileReadFailed(file_path.display().to_string())
+        })?;
+
+        match self.reconcile_balance_content(&file_name, &content) {
+            Ok(report) => {
+                info!(
+                    "reconcile_balance_file - done - file name: {file_name} - mismatches: {}",
+                    report.mismatches.len()
+                );
+                Ok(report)
+            }
+            Err(error) => {
+                error!("reconcile_balance_file - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
     /// Get [ClientInfo] from [i32] client_id
     /// Returns a [CommonError] if client_id has not existed or service throws any error
     async fn get_client_balance(&self, client_id: i32) -> Result<ClientInfo, CommonError> {
@@ -219,661 +997,4916 @@ impl ClientServiceTrait for ClientService {
             }
         }
     }
-}
-/// Client service "private" implement logic
-impl ClientService {
-    /// Validate if the client document number exists based on [String] document_number
-    /// Returns a [CommonError] if RwLock cannot be read or the document number already exists
-    fn validate_client_document(&self, document_number: &str) -> Result<bool, CommonError> {
-        debug!("validate_client_document - start");
 
-        match self.app_state.clients.read() {
-            Ok(clients_map) => {
-                // get a document if exists
-                let validate_document = clients_map
-                    .values()
-                    .any(|client| client.document_number == document_number);
-
-                if !validate_document {
-                    debug!("validate_client_document - document unique - done");
-                    Ok(true)
-                } else {
-                    error!("validate_client_id - error - the document must be unique");
-                    Err(CommonError::Forbiden)
-                }
+    /// Validate [UpdateClient] update_client, then check [u64] version and the new document number
+    /// under the registration lock, and write ahead the new profile before saving it with the next version
+    /// Returns a [CommonError] if the validation, the version check or the document check fails
+    async fn update_client(
+        &self,
+        client_id: i32,
+        version: u64,
+        update_client: UpdateClient,
+    ) -> Result<ClientInfo, CommonError> {
+        info!("update_client - start");
+
+        if let Err(error) = validate_update_client(&update_client, Local::now().date_naive()) {
+            error!("update_client - error: {:?}", error);
+            return Err(error);
+        }
+
+        // the same lock as the registrations, so the version check, the document check and the update are one step
+        let _registration_guard =
+            self.app_state
+                .client_registration_lock
+                .lock()
+                .map_err(|error| {
+                    error!(
+                        "update_client - error when locking the client registration - error: {:?}",
+                        error
+                    );
+                    CommonError::LockWriteFailed
+                })?;
+
+        let mut client = self.get_existing_client(client_id)?;
+        if client.version != version {
+            let error = CommonError::VersionMismatch {
+                client_id,
+                version: client.version,
+            };
+            error!("update_client - error: {:?}", error);
+            return Err(error);
+        }
+        if let Some(document_number) = update_client.document_number {
+            if document_number != client.document_number {
+                self.validate_client_document(&document_number)?;
+                client.document_number = document_number;
             }
-            Err(_) => {
-                error!(
-                    "validate_client_id - error -has occurred an error while try read in app_state"
-                );
-                Err(CommonError::LockReadFailed)
+        }
+        if let Some(client_name) = update_client.client_name {
+            client.client_name = client_name;
+        }
+        if let Some(birth_date) = update_client.birth_date {
+            client.birth_date = birth_date;
+        }
+        if let Some(country) = update_client.country {
+            client.country = country;
+        }
+        client.version += 1;
+
+        // write ahead the new profile before saving it
+        let client_updated = Event::ClientUpdated {
+            client_id,
+            client_name: client.client_name.clone(),
+            birth_date: client.birth_date,
+            document_number: client.document_number.clone(),
+            country: client.country.clone(),
+            version: client.version,
+        };
+        match self
+            .write_ahead(client_updated, Utc::now())
+            .and_then(|_| self.client_repository.update_profile(&client))
+            .and_then(|_| self.get_existing_client(client_id))
+        {
+            Ok(updated_client) => {
+                debug!("update_client - done - version: {}", updated_client.version);
+                Ok(map_client_info(updated_client))
+            }
+            Err(error) => {
+                error!("update_client - error: {:?}", error);
+                Err(error)
             }
         }
     }
-    /// Generate a client id unique
-    fn generate_client_id(&self) -> i32 {
-        self.app_state
-            .client_id_unique
-            .fetch_add(1, Ordering::SeqCst)
-    }
 
-    /// Validate if client id exists based on [Decimal] client_id
-    /// Returns a [CommonError] if the RwLock cannot be read or cannot find the Client
-    fn validate_client_id(&self, client_id: i32) -> Result<Client, CommonError> {
-        debug!("validate_client_id - start");
+    async fn block_client(&self, client_id: i32) -> Result<ClientInfo, CommonError> {
+        info!("block_client - start");
 
-        match self.app_state.clients.read() {
-            Ok(clients_map) => {
-                // get a client id if exists
-                match clients_map.get(&client_id) {
-                    Some(client) => {
-                        debug!("validate_client_id - done");
-                        Ok(client.clone())
-                    }
-                    None => {
-                        error!(
-                            "validate_client_id - error - client id not found - client id: {}",
-                            client_id
-                        );
-                        Err(CommonError::NotFound)
-                    }
-                }
+        match self.change_client_status(client_id, ClientStatus::Blocked, None) {
+            Ok(client) => {
+                info!("block_client - done - client id: {client_id}");
+                Ok(map_client_info(client))
             }
-            Err(_) => {
-                error!(
-                    "validate_client_id - error -has occurred an error while try read in app_state"
-                );
-                Err(CommonError::LockReadFailed)
+            Err(error) => {
+                error!("block_client - error: {:?}", error);
+                Err(error)
             }
         }
     }
 
-    /// Create a new credit on a client account from [Decimal] credit_amount based on [i32] client_id
-    /// Returns a [CommonError] if the RwLock cannot be written or cannot find the Client
-    fn new_credit_on_client_account(
-        &self,
-        client_id: i32,
-        credit_amount: Decimal,
-    ) -> Result<Decimal, CommonError> {
-        debug!("new_credit_on_client_account - start");
+    async fn unblock_client(&self, client_id: i32) -> Result<ClientInfo, CommonError> {
+        info!("unblock_client - start");
 
-        match self.app_state.clients.write() {
-            Ok(mut clients_map) => {
-                match clients_map.get_mut(&client_id) {
-                    Some(client) => {
-                        client.balance += credit_amount;
-                        debug!("new_credit_on_client_account - done");
-                        debug!("new_credit_on_client_account - Client {:?}", client);
-                        Ok(client.balance)
-                    }
-                    None => {
-                        error!("new_credit_on_client_account - error - client id not found- client id:{}",client_id);
-                        Err(CommonError::NotFound)
-                    }
-                }
+        match self.change_client_status(client_id, ClientStatus::Active, None) {
+            Ok(client) => {
+                info!("unblock_client - done - client id: {client_id}");
+                Ok(map_client_info(client))
             }
-            Err(_) => {
-                error!("new_credit_on_client_account - error -has occurred an error while try write in app_state");
-                Err(CommonError::LockWriteFailed)
+            Err(error) => {
+                error!("unblock_client - error: {:?}", error);
+                Err(error)
             }
         }
     }
 
-    /// Create new debit on a client account from [Decimal] debit_amount based on [i32] client_id
-    /// Returns a [CommonError] if the RwLock cannot be written or cannot find the Client
-    fn new_debit_on_client_account(
+    async fn close_client(
         &self,
         client_id: i32,
-        debit_amount: Decimal,
-    ) -> Result<Decimal, CommonError> {
-        debug!("new_debit_on_client_account - start");
+        close_client: CloseClient,
+    ) -> Result<ClientInfo, CommonError> {
+        info!("close_client - start");
 
-        match self.app_state.clients.write() {
-            Ok(mut clients_map) => {
-                // get client and update balance
-                match clients_map.get_mut(&client_id) {
-                    Some(client) => {
-                        client.balance -= debit_amount;
-                        debug!("new_debit_on_client_account - done");
-                        debug!("new_debit_on_client_account - Client {:?}", client);
-                        Ok(client.balance)
-                    }
-                    None => {
-                        error!("new_debit_on_client_account - error - client id not found- client id:{}", client_id);
-                        Err(CommonError::NotFound)
-                    }
-                }
+        match self.change_client_status(
+            client_id,
+            ClientStatus::Closed,
+            Some(close_client.reason_code),
+        ) {
+            Ok(client) => {
+                info!(
+                    "close_client - done - client id: {client_id} - reason code: {:?}",
+                    close_client.reason_code
+                );
+                Ok(map_client_info(client))
             }
-            Err(_) => {
-                error!("new_debit_on_client_account - error -has occurred an error while try write in app_state");
-                Err(CommonError::LockWriteFailed)
+            Err(error) => {
+                error!("close_client - error: {:?}", error);
+                Err(error)
             }
         }
     }
-    /// Generates the full path for the next client balance file in the format `DDMMYYYY_N.DAT`, based on how many files already exist in the storage folder
-    /// Returns a [CommonError] if throws any error
-    async fn generate_next_balance_file_name(&self, date: String) -> Result<String, CommonError> {
-        debug!("generate_next_balance_file_name - start");
 
-        let prefix_file_name = format!("{}_{}", date, "");
-        let mut file_counter = 1;
+    async fn create_transaction_batch(
+        &self,
+        batch: NewTransactionBatch,
+    ) -> Result<TransactionBatchReport, CommonError> {
+        info!("create_transaction_batch - start");
 
-        // create folder
-        if !Path::new(CLIENT_BALANCE_FOLDER).exists() {
-            fs::create_dir(CLIENT_BALANCE_FOLDER)
-                .await
-                .map_err(|error| {
-                    error!(
-                        "generate_next_balance_file_name - error when creating folder error: {:?}",
-                        error
-                    );
-                    CommonError::FolderCreationFailed
-                })?;
+        validate_new_transaction_batch(&batch)?;
+
+        match self.post_transaction_batch(batch.mode, &batch.items) {
+            Ok(report) => {
+                info!(
+                    "create_transaction_batch - done - applied items: {} - rejected items: {}",
+                    report.applied_items, report.rejected_items
+                );
+                Ok(report)
+            }
+            Err(error) => {
+                error!("create_transaction_batch - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    async fn reverse_transaction(
+        &self,
+        transaction_id: i32,
+        reverse_transaction: ReverseTransaction,
+    ) -> Result<TransactionInfo, CommonError> {
+        info!("reverse_transaction - start");
+
+        if let Some(amount) = reverse_transaction.amount {
+            validate_amount("amount", amount, &self.app_state.transaction_limits)?;
         }
 
-        // read folder
-        let mut read_folder = fs::read_dir(CLIENT_BALANCE_FOLDER).await.map_err(|error| {
+        match self.reverse_posted_transaction(transaction_id, reverse_transaction.amount) {
+            Ok(reversal) => {
+                info!(
+                    "reverse_transaction - done - transaction id: {transaction_id} - reversal id: {}",
+                    reversal.transaction_id
+                );
+                Ok(map_transaction_info(reversal))
+            }
+            Err(error) => {
+                error!("reverse_transaction - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    async fn create_hold(&self, new_hold: NewHold) -> Result<HoldInfo, CommonError> {
+        info!("create_hold - start");
+
+        validate_new_hold(&new_hold, &self.app_state.transaction_limits)?;
+        let expires_in = chrono::Duration::seconds(
+            new_hold
+                .expires_in_seconds
+                .unwrap_or(DEFAULT_HOLD_EXPIRY_SECONDS),
+        );
+
+        match self.place_hold(new_hold.client_id, new_hold.hold_amount, expires_in) {
+            Ok((hold, client)) => {
+                info!("create_hold - done - hold id: {}", hold.hold_id);
+                Ok(map_hold_info(hold, &client))
+            }
+            Err(error) => {
+                error!("create_hold - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    async fn capture_hold(
+        &self,
+        hold_id: i32,
+        capture_hold: CaptureHold,
+    ) -> Result<HoldInfo, CommonError> {
+        info!("capture_hold - start");
+
+        if let Some(capture_amount) = capture_hold.capture_amount {
+            validate_amount(
+                "capture_amount",
+                capture_amount,
+                &self.app_state.transaction_limits,
+            )?;
+        }
+
+        match self.capture_active_hold(hold_id, capture_hold.capture_amount, Utc::now()) {
+            Ok((hold, client)) => {
+                info!(
+                    "capture_hold - done - hold id: {hold_id} - captured amount: {}",
+                    hold.captured_amount
+                );
+                Ok(map_hold_info(hold, &client))
+            }
+            Err(error) => {
+                error!("capture_hold - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    async fn release_hold(&self, hold_id: i32) -> Result<HoldInfo, CommonError> {
+        info!("release_hold - start");
+
+        match self.release_active_hold(hold_id, Utc::now()) {
+            Ok((hold, client)) => {
+                info!("release_hold - done - hold id: {hold_id}");
+                Ok(map_hold_info(hold, &client))
+            }
+            Err(error) => {
+                error!("release_hold - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    async fn get_client_by_document(
+        &self,
+        document_number: String,
+    ) -> Result<ClientInfo, CommonError> {
+        info!("get_client_by_document - start");
+
+        match self.client_repository.find_by_document(&document_number) {
+            Ok(Some(client)) => {
+                debug!("get_client_by_document - done");
+                Ok(map_client_info(client))
+            }
+            Ok(None) => {
+                let error = CommonError::DocumentNotFound(document_number);
+                error!("get_client_by_document - error: {:?}", error);
+                Err(error)
+            }
+            Err(error) => {
+                error!("get_client_by_document - error: {:?}", error);
+                Err(error)
+            }
+        }
+    }
+
+    /// Get a [TransactionPage] with the transactions of [i32] client_id matching [TransactionFilter] filter
+    /// Returns a [CommonError] if client_id has not existed or service throws any error
+    async fn get_client_transactions(
+        &self,
+        client_id: i32,
+        filter: TransactionFilter,
+    ) -> Result<TransactionPage, CommonError> {
+        info!("get_client_transactions - start");
+
+        if let Err(error) = self.validate_client_id(client_id) {
+            error!("get_client_transactions - error: {:?}", error);
+            return Err(error);
+        }
+
+        let page = filter.page.unwrap_or(1).max(1);
+        let page_size = filter
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let transactions_map = self.app_state.transactions.read().map_err(|error| {
             error!(
-                "generate_next_balance_file_name - error when reading folder error: {:?}",
+                "get_client_transactions - error when reading transactions - error: {:?}",
                 error
             );
-            CommonError::FolderReadFailed
+            CommonError::LockReadFailed
         })?;
-        while let Some(file) = read_folder.next_entry().await.map_err(|error| {
+
+        // keep the transactions inside the date range, both days included
+        let matching_transactions: Vec<&Transaction> = transactions_map
+            .get(&client_id)
+            .map(|transactions| {
+                transactions
+                    .iter()
+                    .filter(|transaction| {
+                        let day = transaction.created_at.date_naive();
+                        filter.from.is_none_or(|from| day >= from)
+                            && filter.to.is_none_or(|to| day <= to)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let total_items = matching_transactions.len();
+        let items = matching_transactions
+            .into_iter()
+            .skip((page - 1).saturating_mul(page_size))
+            .take(page_size)
+            .cloned()
+            .map(map_transaction_info)
+            .collect();
+
+        debug!("get_client_transactions - done");
+        Ok(TransactionPage {
+            items,
+            page,
+            page_size,
+            total_items,
+        })
+    }
+
+    async fn get_client_statement(
+        &self,
+        client_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ClientStatement, CommonError> {
+        info!("get_client_statement - start");
+
+        if from > to {
+            error!("get_client_statement - error - from is after to - from: {from} - to: {to}");
+            return Err(CommonError::InvalidField {
+                field: "from".to_string(),
+                reason: format!("must not be after {to}"),
+            });
+        }
+
+        let transactions_map = self.app_state.transactions.read().map_err(|error| {
             error!(
-                "generate_next_balance_file_name - error when reading file error: {:?}",
+                "get_client_statement - error when reading transactions - error: {:?}",
                 error
             );
-            CommonError::FolderReadFailed
-        })? {
-            let get_file_name = file.file_name();
-            let file_name = get_file_name.to_string_lossy();
-            if file_name.starts_with(&prefix_file_name) && file_name.ends_with(PREFIX_FILE) {
-                file_counter += 1;
+            CommonError::LockReadFailed
+        })?;
+        // read under the ledger lock, so the balance matches the last movement
+        let client = self.validate_client_id(client_id).map_err(|error| {
+            error!("get_client_statement - error: {:?}", error);
+            error
+        })?;
+
+        let transactions = transactions_map
+            .get(&client_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        // every balance change is in the ledger, settlements and opening balances included
+        let opening_balance = transactions
+            .iter()
+            .take_while(|transaction| transaction.created_at.date_naive() < from)
+            .last()
+            .map_or(Decimal::ZERO, |transaction| transaction.balance);
+        let movements: Vec<_> = transactions
+            .iter()
+            .filter(|transaction| {
+                let day = transaction.created_at.date_naive();
+                day >= from && day <= to
+            })
+            .map(map_statement_line)
+            .collect();
+        let closing_balance = movements
+            .last()
+            .map_or(opening_balance, |movement| movement.balance);
+
+        info!("get_client_statement - done");
+        Ok(ClientStatement {
+            client: map_client_info(client),
+            from,
+            to,
+            opening_balance,
+            closing_balance,
+            movements,
+        })
+    }
+
+    async fn list_clients(&self, filter: ClientFilter) -> Result<ClientPage, CommonError> {
+        info!("list_clients - start");
+
+        let sort_by = filter.sort_by.unwrap_or_default();
+        let page_size = filter
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        if let (Some(min_balance), Some(max_balance)) = (filter.min_balance, filter.max_balance) {
+            if min_balance > max_balance {
+                let error = CommonError::InvalidField {
+                    field: "min_balance".to_string(),
+                    reason: "must not be greater than max_balance".to_string(),
+                };
+                error!("list_clients - error: {:?}", error);
+                return Err(error);
             }
         }
-        let format_file_name = format!(
-            "{}/{}_{}{}",
-            CLIENT_BALANCE_FOLDER, date, file_counter, PREFIX_FILE
-        );
 
-        Ok(format_file_name)
+        let cursor = match filter.cursor.as_deref() {
+            Some(cursor) => match ClientCursor::decode(cursor, sort_by) {
+                Ok(cursor) => Some(cursor),
+                Err(error) => {
+                    error!("list_clients - error: {:?}", error);
+                    return Err(error);
+                }
+            },
+            None => None,
+        };
+
+        let name = filter.name.as_deref().map(str::to_lowercase);
+        let mut clients: Vec<Client> = self
+            .client_repository
+            .list()?
+            .into_iter()
+            .filter(|client| {
+                filter
+                    .country
+                    .as_deref()
+                    .is_none_or(|country| client.country.eq_ignore_ascii_case(country))
+                    && filter
+                        .document_number
+                        .as_deref()
+                        .is_none_or(|document_number| client.document_number == document_number)
+                    && filter
+                        .min_balance
+                        .is_none_or(|min_balance| client.balance >= min_balance)
+                    && filter
+                        .max_balance
+                        .is_none_or(|max_balance| client.balance <= max_balance)
+                    && name
+                        .as_deref()
+                        .is_none_or(|name| client.client_name.to_lowercase().contains(name))
+            })
+            .collect();
+
+        // the repository lists the clients by id
+        if sort_by == ClientSortField::Name {
+            clients
+                .sort_by(|left, right| compare_by_name(&left.client_name, left.client_id, right));
+        }
+
+        // one client more than the page tells whether there is a next page
+        let mut page_clients: Vec<Client> = clients
+            .into_iter()
+            .filter(|client| {
+                cursor
+                    .as_ref()
+                    .is_none_or(|cursor| cursor.is_before(client))
+            })
+            .take(page_size + 1)
+            .collect();
+        let next_cursor = if page_clients.len() > page_size {
+            page_clients.truncate(page_size);
+            page_clients
+                .last()
+                .map(|client| ClientCursor::after(client, sort_by).encode())
+        } else {
+            None
+        };
+
+        debug!("list_clients - done");
+        Ok(ClientPage {
+            items: page_clients.into_iter().map(map_client_info).collect(),
+            page_size,
+            next_cursor,
+        })
     }
-    /// Create a text file and save client balances
-    /// Returns a [CommonError] if throws any error
-    async fn write_in_the_file_the_balance_of_the_clients(
-        &self,
-        format_file_name: String,
-    ) -> Result<(), CommonError> {
-        debug!("write_in_the_file_the_balance_of_the_clients - start");
+}
+/// Client service "private" implement logic
+impl ClientService {
+    /// Register [NewClient] new_client with a new client id if it is valid on [NaiveDate] today,
+    /// the client registration lock must be held
+    /// Returns the new client id
+    /// Returns a [CommonError] if a field is invalid, the document number already exists, the event cannot be written
+    /// or the client cannot be saved
+    fn register_client(&self, new_client: NewClient, today: NaiveDate) -> Result<i32, CommonError> {
+        validate_new_client(&new_client, today)?;
 
-        let mut temporal_client_data: Vec<(i32, Decimal)> = Vec::new();
-        {
-            let clients_map = self.app_state.clients.read().map_err(|error| {
-                error!("write_in_the_file_the_balance_of_the_clients - error when reading app_state - error: {:?}",error);
-                CommonError::LockReadFailed
+        // verify if client document exists
+        self.validate_client_document(&new_client.document_number)
+            .inspect_err(|_| {
+                error!(
+                    "register_client - error - document_number: {}",
+                    new_client.document_number
+                )
             })?;
-            for client in clients_map.values() {
-                temporal_client_data.push((client.client_id, client.balance));
+
+        // Generate unique id for each client
+        let client_id = self.generate_client_id();
+
+        // map Client from NewClient
+        let populate_new_client = map_client(new_client, client_id);
+
+        // write ahead the new client before saving it
+        let client_created = Event::ClientCreated {
+            client_id,
+            client_name: populate_new_client.client_name.clone(),
+            birth_date: populate_new_client.birth_date,
+            document_number: populate_new_client.document_number.clone(),
+            country: populate_new_client.country.clone(),
+        };
+        self.write_ahead(client_created, Utc::now())?;
+        self.client_repository.insert(populate_new_client)?;
+        Ok(client_id)
+    }
+
+    /// Register the client of [ClientImportRow] row if it is valid on [NaiveDate] today
+    /// and its document number was not seen before in [HashMap] document_lines, where it is added
+    /// Returns the new client id
+    /// Returns a [CommonError] if the row cannot be read, a field is invalid,
+    /// the document number is repeated in the file or already exists or the client cannot be saved
+    fn import_client_row(
+        &self,
+        row: ClientImportRow,
+        today: NaiveDate,
+        document_lines: &mut HashMap<String, usize>,
+    ) -> Result<i32, CommonError> {
+        let new_client = row.new_client?;
+        // every row with the document is rejected after the first one, even if the first one is invalid
+        if let Some(first_line) = document_lines.get(&new_client.document_number) {
+            error!(
+                "import_client_row - error - document number repeated in the file - line: {} - first line: {first_line}",
+                row.line
+            );
+            return Err(CommonError::InvalidField {
+                field: "document_number".to_string(),
+                reason: format!("is repeated in the file, first seen on line {first_line}"),
+            });
+        }
+        document_lines.insert(new_client.document_number.clone(), row.line);
+
+        self.register_client(new_client, today)
+    }
+
+    /// Validate if the client document number exists based on [String] document_number
+    /// The repository answers through its document index, so the check does not depend on the number of clients
+    /// Returns a [CommonError] if RwLock cannot be read or the document number already exists
+    fn validate_client_document(&self, document_number: &str) -> Result<bool, CommonError> {
+        debug!("validate_client_document - start");
+
+        match self.client_repository.find_by_document(document_number) {
+            Ok(None) => {
+                debug!("validate_client_document - document unique - done");
+                Ok(true)
+            }
+            Ok(Some(_client)) => {
+                error!("validate_client_id - error - the document must be unique");
+                Err(CommonError::DocumentAlreadyExists(
+                    document_number.to_string(),
+                ))
+            }
+            Err(error) => {
+                error!(
+                    "validate_client_id - error -has occurred an error while try read the clients - error: {:?}",
+                    error
+                );
+                Err(error)
             }
         }
-        // sort client id in ascending order
-        temporal_client_data.sort_by_key(|(client_id, _)| *client_id);
+    }
+    /// Generate a client id unique
+    fn generate_client_id(&self) -> i32 {
+        self.app_state
+            .client_id_unique
+            .fetch_add(1, Ordering::SeqCst)
+    }
 
-        let mut new_file = fs::File::create(&format_file_name).await.map_err(|error| {
-            error!("write_in_the_file_the_balance_of_the_clients - error when creating file error: {:?}",error);
-            CommonError::FileCreationFailed
-        })?;
+    /// Validate if client id exists based on [Decimal] client_id
+    /// Returns a [CommonError] if the RwLock cannot be read or cannot find the Client
+    fn validate_client_id(&self, client_id: i32) -> Result<Client, CommonError> {
+        debug!("validate_client_id - start");
 
-        for (client_id, balance) in temporal_client_data {
-            // format client id and balance
-            let each_client = format!("{:02} {:.2}\n", client_id, balance);
-            new_file.write_all(each_client.as_bytes()).await.map_err(|error| {
-                error!("write_in_the_file_the_balance_of_the_clients - error when writing to the file - file name: {format_file_name} - error: {:?}",error);              
-                CommonError::FileWriteFailed
-            })?;
+        match self.client_repository.get(client_id) {
+            Ok(Some(client)) => {
+                debug!("validate_client_id - done");
+                Ok(client)
+            }
+            Ok(None) => {
+                error!(
+                    "validate_client_id - error - client id not found - client id: {}",
+                    client_id
+                );
+                Err(CommonError::ClientNotFound(client_id))
+            }
+            Err(error) => {
+                error!(
+                    "validate_client_id - error -has occurred an error while try read the clients - error: {:?}",
+                    error
+                );
+                Err(error)
+            }
         }
-        // update balances in app_state
-        let mut clients_map = self.app_state.clients.write().map_err(|error| {
-            error!("write_in_the_file_the_balance_of_the_clients - error when writing app_state - error: {:?}",error);
-            CommonError::LockWriteFailed
-        })?;
-        for client in clients_map.values_mut() {
-            client.balance = Decimal::zero();
+    }
+
+    /// Create a new credit on a client account from [Decimal] credit_amount based on [i32] client_id
+    /// [IdempotentRequest] idempotent_request is written with it to the event log
+    /// Returns a [CommonError] if the RwLock cannot be written or cannot find the Client
+    fn new_credit_on_client_account(
+        &self,
+        client_id: i32,
+        credit_amount: Decimal,
+        idempotent_request: Option<IdempotentRequest>,
+    ) -> Result<Decimal, CommonError> {
+        debug!("new_credit_on_client_account - start");
+
+        let client = self.post_transaction(
+            client_id,
+            TransactionKind::Credit,
+            credit_amount,
+            idempotent_request,
+        )?;
+
+        debug!("new_credit_on_client_account - done");
+        debug!("new_credit_on_client_account - Client {:?}", client);
+        Ok(client.balance)
+    }
+
+    /// Create new debit on a client account from [Decimal] debit_amount based on [i32] client_id
+    /// [IdempotentRequest] idempotent_request is written with it to the event log
+    /// Returns a [CommonError] if the RwLock cannot be written or cannot find the Client
+    fn new_debit_on_client_account(
+        &self,
+        client_id: i32,
+        debit_amount: Decimal,
+        idempotent_request: Option<IdempotentRequest>,
+    ) -> Result<Decimal, CommonError> {
+        debug!("new_debit_on_client_account - start");
+
+        let client = self.post_transaction(
+            client_id,
+            TransactionKind::Debit,
+            debit_amount,
+            idempotent_request,
+        )?;
+
+        debug!("new_debit_on_client_account - done");
+        debug!("new_debit_on_client_account - Client {:?}", client);
+        Ok(client.balance)
+    }
+
+    /// Move [Decimal] transfer_amount from [i32] from_client_id to [i32] to_client_id under a single write lock
+    /// Returns both balances after the transfer
+    /// Returns a [CommonError] if the RwLock cannot be written or cannot find any Client
+    fn new_transfer_between_client_accounts(
+        &self,
+        from_client_id: i32,
+        to_client_id: i32,
+        transfer_amount: Decimal,
+    ) -> Result<(Decimal, Decimal), CommonError> {
+        debug!("new_transfer_between_client_accounts - start");
+
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        // both clients must exist and be active before touching any balance
+        let from_client = self.get_active_client(from_client_id)?;
+        self.get_active_client(to_client_id)?;
+        self.check_overdraft_limit(&from_client, transfer_amount)?;
+
+        let created_at = Utc::now();
+        self.write_ahead(
+            Event::TransferPosted {
+                from_client_id,
+                to_client_id,
+                amount: transfer_amount,
+            },
+            created_at,
+        )?;
+        let (from_client, to_client) = self.apply_transfer(
+            &mut transactions_map,
+            from_client_id,
+            to_client_id,
+            transfer_amount,
+            created_at,
+        )?;
+
+        debug!("new_transfer_between_client_accounts - done");
+        Ok((from_client.balance, to_client.balance))
+    }
+
+    /// Write ahead, with [IdempotentRequest] idempotent_request, and apply a [TransactionKind] transaction
+    /// of [Decimal] amount on [i32] client_id
+    /// Returns the updated [Client]
+    /// Returns a [CommonError] if the RwLock cannot be written, cannot find the Client or cannot save the balance
+    fn post_transaction(
+        &self,
+        client_id: i32,
+        kind: TransactionKind,
+        amount: Decimal,
+        idempotent_request: Option<IdempotentRequest>,
+    ) -> Result<Client, CommonError> {
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        let client = self.get_active_client(client_id)?;
+        if kind == TransactionKind::Debit {
+            self.check_overdraft_limit(&client, amount)?;
         }
-        debug!("write_in_the_file_the_balance_of_the_clients - done");
-        Ok(())
+
+        let created_at = Utc::now();
+        self.write_ahead(
+            Event::TransactionPosted {
+                client_id,
+                kind,
+                amount,
+                idempotent_request,
+            },
+            created_at,
+        )?;
+        self.apply_transaction(&mut transactions_map, client_id, kind, amount, created_at)
     }
-}
 
-/// Client service trait dyn type
-pub type DynClientService = Arc<dyn ClientServiceTrait + Send + Sync>;
+    /// Apply a [TransactionKind] transaction of [Decimal] amount on [i32] client_id and record it in the ledger
+    /// Returns the updated [Client]
+    /// Returns a [CommonError] if cannot find the Client or cannot save the balance
+    fn apply_transaction(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        client_id: i32,
+        kind: TransactionKind,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+    ) -> Result<Client, CommonError> {
+        let mut client = self.get_existing_client(client_id)?;
+        client.balance += kind.balance_change(amount);
+        self.client_repository
+            .update_balance(client.client_id, client.balance)?;
+        self.record_transaction(transactions_map, &client, kind, amount, created_at, None)?;
+        Ok(client)
+    }
 
-/// Unit tests cases
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::model::client_model::Client;
-    use crate::service::client_service::ClientService;
-    use crate::stub::client_info_stub::stub::create_client_info_stub;
-    use crate::stub::new_client_stub::stub::create_new_client_stub;
-    use crate::stub::new_credit_transaction_stub::stub::create_new_credit_transaction_stub;
-    use crate::stub::new_debit_transaction_stub::stub::create_new_debit_transaction_stub;
-    use serial_test::serial;
-    use std::collections::HashMap;
-    use std::fs::File;
-    use std::sync::atomic::AtomicI32;
-    use std::sync::RwLock;
+    /// Check every [BatchTransaction] of items against the balances left by the previous ones,
+    /// then write ahead and apply the accepted items under a single ledger lock
+    /// Returns a [TransactionBatchReport] with the result of every item
+    /// Returns a [CommonError] if the RwLock cannot be written, an item is rejected in [BatchMode::AllOrNothing]
+    /// or the event cannot be written
+    fn post_transaction_batch(
+        &self,
+        mode: BatchMode,
+        items: &[BatchTransaction],
+    ) -> Result<TransactionBatchReport, CommonError> {
+        // the ledger lock serializes every balance change, taken once for the whole batch
+        let mut transactions_map = self.write_transactions()?;
+
+        // clients touched by the batch, with the balance left by the items checked so far
+        let mut batch_clients = HashMap::new();
+        let mut transactions = Vec::with_capacity(items.len());
+        let mut results = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let mut result = BatchItemResult {
+                index,
+                client_id: item.client_id(),
+                kind: item.kind(),
+                amount: item.amount(),
+                status: BatchItemStatus::Applied,
+                balance: None,
+                error_code: None,
+                error_message: None,
+            };
+            match self.check_batch_transaction(item, &mut batch_clients) {
+                Ok(()) => transactions.push((item.client_id(), item.kind(), item.amount())),
+                Err(error) if mode == BatchMode::AllOrNothing => {
+                    error!("post_transaction_batch - error - item rejected - index: {index} - error: {:?}", error);
+                    return Err(CommonError::BatchItemRejected {
+                        index,
+                        error: Box::new(error),
+                    });
+                }
+                Err(error) => {
+                    result.status = BatchItemStatus::Rejected;
+                    result.error_code = Some(error.code().to_string());
+                    result.error_message = Some(error.to_string());
+                }
+            }
+            results.push(result);
+        }
+
+        let applied_items = transactions.len();
+        if applied_items > 0 {
+            let created_at = Utc::now();
+            self.write_ahead(
+                Event::TransactionBatchPosted {
+                    transactions: transactions.clone(),
+                },
+                created_at,
+            )?;
+            let clients =
+                self.apply_transaction_batch(&mut transactions_map, &transactions, created_at)?;
+            let applied_results = results
+                .iter_mut()
+                .filter(|result| result.status == BatchItemStatus::Applied);
+            for (result, client) in applied_results.zip(clients) {
+                result.balance = Some(client.balance);
+            }
+        }
+
+        Ok(TransactionBatchReport {
+            mode,
+            applied_items,
+            rejected_items: results.len() - applied_items,
+            items: results,
+        })
+    }
+
+    /// Check [BatchTransaction] item on its client in [HashMap] batch_clients, loaded from the repository the first time,
+    /// and leave there the balance after the item
+    /// Returns a [CommonError] if the item is invalid, cannot find the Client, the account is not active
+    /// or the debit exceeds its overdraft limit
+    fn check_batch_transaction(
+        &self,
+        item: &BatchTransaction,
+        batch_clients: &mut HashMap<i32, Client>,
+    ) -> Result<(), CommonError> {
+        validate_batch_transaction(item, &self.app_state.transaction_limits)?;
+
+        let client_id = item.client_id();
+        let client = match batch_clients.entry(client_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.get_active_client(client_id)?),
+        };
+        if item.kind() == TransactionKind::Debit {
+            self.check_overdraft_limit(client, item.amount())?;
+        }
+        client.balance += item.kind().balance_change(item.amount());
+        Ok(())
+    }
+
+    /// Apply the (client_id, kind, amount) transactions of a batch in order and record them in the ledger
+    /// Returns the updated [Client] of every transaction
+    /// Returns a [CommonError] if cannot find any Client or cannot save a balance
+    fn apply_transaction_batch(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        transactions: &[(i32, TransactionKind, Decimal)],
+        created_at: DateTime<Utc>,
+    ) -> Result<Vec<Client>, CommonError> {
+        transactions
+            .iter()
+            .map(|(client_id, kind, amount)| {
+                self.apply_transaction(transactions_map, *client_id, *kind, *amount, created_at)
+            })
+            .collect()
+    }
+
+    /// Write ahead and apply a reversal of [Decimal] amount, the rest of the transaction when None, of [i32] transaction_id
+    /// Returns the compensating [Transaction]
+    /// Returns a [CommonError] if the RwLock cannot be written, cannot find the Transaction, it cannot be reversed,
+    /// the account is not active or the compensating debit exceeds its overdraft limit
+    fn reverse_posted_transaction(
+        &self,
+        transaction_id: i32,
+        amount: Option<Decimal>,
+    ) -> Result<Transaction, CommonError> {
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        let transaction = self
+            .find_transaction(&mut transactions_map, transaction_id)?
+            .clone();
+        let Some(kind) = transaction.kind.opposite() else {
+            error!("reverse_posted_transaction - error - transaction is not a credit or a debit - transaction id: {transaction_id}");
+            return Err(Self::not_reversible_kind());
+        };
+        if transaction.reversal_of.is_some() {
+            error!("reverse_posted_transaction - error - transaction is a reversal - transaction id: {transaction_id}");
+            return Err(CommonError::InvalidField {
+                field: "transaction_id".to_string(),
+                reason: "a reversal cannot be reversed".to_string(),
+            });
+        }
+        let reversible_amount = transaction.reversible_amount();
+        if reversible_amount.is_zero() {
+            error!("reverse_posted_transaction - error - transaction already reversed - transaction id: {transaction_id}");
+            return Err(CommonError::TransactionAlreadyReversed(transaction_id));
+        }
+        let amount = amount.unwrap_or(reversible_amount);
+        if amount > reversible_amount {
+            error!(
+                "reverse_posted_transaction - error - amount greater than the reversible amount - transaction id: {transaction_id} - amount: {amount}"
+            );
+            return Err(CommonError::InvalidField {
+                field: "amount".to_string(),
+                reason: format!(
+                    "must not be greater than the reversible amount {reversible_amount}"
+                ),
+            });
+        }
+
+        let client = self.get_active_client(transaction.client_id)?;
+        if kind == TransactionKind::Debit {
+            self.check_overdraft_limit(&client, amount)?;
+        }
+
+        let created_at = Utc::now();
+        self.write_ahead(
+            Event::TransactionReversed {
+                transaction_id,
+                amount,
+            },
+            created_at,
+        )?;
+        self.apply_transaction_reversed(&mut transactions_map, transaction_id, amount, created_at)
+    }
+
+    /// Apply on its client the entry compensating [Decimal] amount of [i32] transaction_id and record it in the ledger
+    /// Returns the compensating [Transaction]
+    /// Returns a [CommonError] if cannot find the Transaction or the Client or cannot save the balance
+    fn apply_transaction_reversed(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        transaction_id: i32,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+    ) -> Result<Transaction, CommonError> {
+        let transaction = self.find_transaction(transactions_map, transaction_id)?;
+        let kind = transaction
+            .kind
+            .opposite()
+            .ok_or_else(Self::not_reversible_kind)?;
+        transaction.reversed_amount += amount;
+
+        let mut client = self.get_existing_client(transaction.client_id)?;
+        client.balance += kind.balance_change(amount);
+        self.client_repository
+            .update_balance(client.client_id, client.balance)?;
+        self.record_transaction(
+            transactions_map,
+            &client,
+            kind,
+            amount,
+            created_at,
+            Some(transaction_id),
+        )
+    }
+
+    /// Error for a reversal of a settlement or an opening balance, only credits and debits can be reversed
+    fn not_reversible_kind() -> CommonError {
+        CommonError::InvalidField {
+            field: "transaction_id".to_string(),
+            reason: "only credits and debits can be reversed".to_string(),
+        }
+    }
+
+    /// Find the [Transaction] with [i32] transaction_id in the ledger of its client, at the position kept when it was recorded
+    /// Returns a [CommonError] if the RwLock cannot be read or cannot find the Transaction
+    fn find_transaction<'a>(
+        &self,
+        transactions_map: &'a mut HashMap<i32, Vec<Transaction>>,
+        transaction_id: i32,
+    ) -> Result<&'a mut Transaction, CommonError> {
+        let position = self
+            .app_state
+            .transaction_positions
+            .read()
+            .map_err(|error| {
+                error!(
+                    "find_transaction - error when reading transaction positions - error: {:?}",
+                    error
+                );
+                CommonError::LockReadFailed
+            })?
+            .get(&transaction_id)
+            .copied();
+        position
+            .and_then(|(client_id, index)| transactions_map.get_mut(&client_id)?.get_mut(index))
+            .ok_or_else(|| {
+                error!("find_transaction - error - transaction id not found - transaction id: {transaction_id}");
+                CommonError::TransactionNotFound(transaction_id)
+            })
+    }
+
+    /// Apply a transfer of [Decimal] amount from [i32] from_client_id to [i32] to_client_id and record it in the ledger
+    /// Returns both updated clients
+    /// Returns a [CommonError] if cannot find any Client or cannot save the balances
+    fn apply_transfer(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        from_client_id: i32,
+        to_client_id: i32,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+    ) -> Result<(Client, Client), CommonError> {
+        let mut from_client = self.get_existing_client(from_client_id)?;
+        let mut to_client = self.get_existing_client(to_client_id)?;
+        from_client.balance -= amount;
+        to_client.balance += amount;
+
+        self.client_repository.update_balances(&[
+            (from_client.client_id, from_client.balance),
+            (to_client.client_id, to_client.balance),
+        ])?;
+        self.record_transaction(
+            transactions_map,
+            &from_client,
+            TransactionKind::Debit,
+            amount,
+            created_at,
+            None,
+        )?;
+        self.record_transaction(
+            transactions_map,
+            &to_client,
+            TransactionKind::Credit,
+            amount,
+            created_at,
+            None,
+        )?;
+        Ok((from_client, to_client))
+    }
+
+    /// Take a consistent copy of every (client_id, balance) pair sorted by client id
+    /// No transaction can be applied while the copy is taken
+    /// Returns the pairs and the first transaction id left out of them
+    /// Returns a [CommonError] if the RwLock cannot be read or the clients cannot be read
+    fn snapshot_balances(&self) -> Result<(Vec<(i32, Decimal)>, i32), CommonError> {
+        // the ledger lock serializes every balance change
+        let _transactions_map = self.app_state.transactions.read().map_err(|error| {
+            error!(
+                "snapshot_balances - error when reading transactions - error: {:?}",
+                error
+            );
+            CommonError::LockReadFailed
+        })?;
+
+        let balances = self
+            .client_repository
+            .list()?
+            .into_iter()
+            .map(|client| (client.client_id, client.balance))
+            .collect();
+        let ledger_cut = self.app_state.transaction_id_unique.load(Ordering::SeqCst);
+        Ok((balances, ledger_cut))
+    }
+
+    /// Write ahead and subtract every (client_id, balance) pair of [Decimal] stored_balances from the client balances,
+    /// and keep [i32] ledger_cut, the first transaction id left out of [String] file_name, to reconcile it later
+    /// Transactions applied after the snapshot was taken stay in the new balance
+    /// Returns a [CommonError] if the RwLock cannot be written or cannot save the balances
+    fn reset_balances(
+        &self,
+        stored_balances: Vec<(i32, Decimal)>,
+        file_name: String,
+        ledger_cut: i32,
+    ) -> Result<(), CommonError> {
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        let created_at = Utc::now();
+        self.write_ahead(
+            Event::BalancesReset {
+                stored_balances: stored_balances.clone(),
+                file_name: Some(file_name.clone()),
+                ledger_cut: Some(ledger_cut),
+            },
+            created_at,
+        )?;
+        self.apply_balance_entries(
+            &mut transactions_map,
+            TransactionKind::Settlement,
+            &stored_balances,
+            created_at,
+        )?;
+        self.register_balance_file(&file_name, ledger_cut)
+    }
+
+    /// First transaction id left out of the balance file [str] file_name
+    /// Returns a [CommonError] if the file was not written by a balance reset or the RwLock cannot be read
+    fn ledger_cut_of(&self, file_name: &str) -> Result<i32, CommonError> {
+        self.app_state
+            .balance_file_ledger_cuts
+            .read()
+            .map_err(|error| {
+                error!(
+                    "ledger_cut_of - error when reading balance files - error: {:?}",
+                    error
+                );
+                CommonError::LockReadFailed
+            })?
+            .get(file_name)
+            .copied()
+            .ok_or_else(|| CommonError::BalanceFileNotFound(file_name.to_string()))
+    }
+
+    /// Keep [i32] ledger_cut, the first transaction id left out of [str] file_name
+    /// Returns a [CommonError] if the RwLock cannot be written
+    fn register_balance_file(&self, file_name: &str, ledger_cut: i32) -> Result<(), CommonError> {
+        self.app_state
+            .balance_file_ledger_cuts
+            .write()
+            .map_err(|error| {
+                error!(
+                    "register_balance_file - error when writing balance files - error: {:?}",
+                    error
+                );
+                CommonError::LockWriteFailed
+            })?
+            .insert(file_name.to_string(), ledger_cut);
+        Ok(())
+    }
+
+    /// Reconcile [str] content, just written as [str] file_name, and log an alert if it does not match the ledger
+    /// A failed reconciliation does not undo the balance file
+    fn alert_balance_file_mismatches(&self, file_name: &str, content: &str) {
+        match self.reconcile_balance_content(file_name, content) {
+            Ok(report) if report.is_balanced() => info!(
+                "alert_balance_file_mismatches - balanced - file name: {file_name} - clients: {}",
+                report.checked_clients
+            ),
+            Ok(report) => error!(
+                "alert_balance_file_mismatches - ALERT - balance file does not match the ledger - file name: {file_name} - mismatches: {:?}",
+                report.mismatches
+            ),
+            Err(error) => error!(
+                "alert_balance_file_mismatches - ALERT - balance file cannot be reconciled - file name: {file_name} - error: {:?}",
+                error
+            ),
+        }
+    }
+
+    /// Apply every (client_id, balance) pair of [Decimal] balances as a [TransactionKind] kind entry at once,
+    /// a settlement subtracts the balance and an opening balance adds it
+    /// Entries moving money are recorded in the ledger, so the statements show them instead of a jump in the balance
+    /// Returns a [CommonError] if cannot find any Client or cannot save the balances
+    fn apply_balance_entries(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        kind: TransactionKind,
+        balances: &[(i32, Decimal)],
+        created_at: DateTime<Utc>,
+    ) -> Result<(), CommonError> {
+        let mut updated_clients = Vec::with_capacity(balances.len());
+        for (client_id, balance) in balances {
+            let mut client = self.get_existing_client(*client_id)?;
+            client.balance += kind.balance_change(*balance);
+            updated_clients.push((client, *balance));
+        }
+        let new_balances: Vec<(i32, Decimal)> = updated_clients
+            .iter()
+            .map(|(client, _)| (client.client_id, client.balance))
+            .collect();
+        self.client_repository.update_balances(&new_balances)?;
+
+        for (client, balance) in updated_clients
+            .iter()
+            .filter(|(_, balance)| !balance.is_zero())
+        {
+            self.record_transaction(transactions_map, client, kind, *balance, created_at, None)?;
+        }
+        Ok(())
+    }
+
+    /// Find in the balance folder the most recent file named with the balance file name pattern
+    /// Files are ordered by the date and then the counter in their name
+    /// Returns the file name, None if there is no file, or a [CommonError] if the folder cannot be read
+    async fn find_latest_balance_file_name(&self) -> Result<Option<String>, CommonError> {
+        let balance_folder = &self.app_state.balance_folder;
+        if !Path::new(balance_folder).exists() {
+            return Ok(None);
+        }
+
+        let mut read_folder = fs::read_dir(balance_folder).await.map_err(|error| {
+            error!(
+                "find_latest_balance_file_name - error when reading folder error: {:?}",
+                error
+            );
+            CommonError::FolderReadFailed(balance_folder.to_string())
+        })?;
+        let mut latest_file: Option<((NaiveDate, u32), String)> = None;
+        while let Some(file) = read_folder.next_entry().await.map_err(|error| {
+            error!(
+                "find_latest_balance_file_name - error when reading file error: {:?}",
+                error
+            );
+            CommonError::FolderReadFailed(balance_folder.to_string())
+        })? {
+            let file_name = file.file_name().to_string_lossy().to_string();
+            if let Some(order) = self
+                .app_state
+                .balance_file_name_pattern
+                .parse_file_name(&file_name)
+            {
+                if latest_file
+                    .as_ref()
+                    .is_none_or(|(latest_order, _)| order > *latest_order)
+                {
+                    latest_file = Some((order, file_name));
+                }
+            }
+        }
+        Ok(latest_file.map(|(_, file_name)| file_name))
+    }
+
+    /// Append [Event] event to the event log before applying it, so it can be replayed after a crash
+    /// Does nothing when the event log is disabled
+    /// Returns a [CommonError] if the event cannot be written
+    fn write_ahead(&self, event: Event, created_at: DateTime<Utc>) -> Result<(), CommonError> {
+        match &self.app_state.event_log {
+            Some(event_log) => event_log
+                .append(&EventRecord { created_at, event })
+                .map_err(|error| {
+                    error!(
+                        "write_ahead - error when writing the event log - error: {:?}",
+                        error
+                    );
+                    error
+                }),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the [Client] with [i32] client_id from the repository
+    /// Returns a [CommonError] if the repository cannot be read or cannot find the Client
+    fn get_existing_client(&self, client_id: i32) -> Result<Client, CommonError> {
+        self.client_repository.get(client_id)?.ok_or_else(|| {
+            error!("get_existing_client - error - client id not found - client id: {client_id}");
+            CommonError::ClientNotFound(client_id)
+        })
+    }
+
+    /// Get the [Client] with [i32] client_id from the repository if its account is active
+    /// Returns a [CommonError] if the repository cannot be read, cannot find the Client or the account is blocked or closed
+    fn get_active_client(&self, client_id: i32) -> Result<Client, CommonError> {
+        let client = self.get_existing_client(client_id)?;
+        if client.status != ClientStatus::Active {
+            error!(
+                "get_active_client - error - account not active - client id: {client_id} - status: {}",
+                client.status.as_str()
+            );
+            return Err(CommonError::AccountNotActive {
+                client_id,
+                status: client.status,
+            });
+        }
+        Ok(client)
+    }
+
+    /// Check that debiting [Decimal] amount keeps the balance of [Client] client within its overdraft limit
+    /// Returns a [CommonError] with the available balance if the debit exceeds the limit
+    fn check_overdraft_limit(&self, client: &Client, amount: Decimal) -> Result<(), CommonError> {
+        let overdraft_limit = self.app_state.overdraft_limits.limit_of(client.client_id);
+        // the held money cannot be debited again
+        match overdraft_limit.available_balance(client.available_balance()) {
+            Some(available_balance) if amount > available_balance => {
+                error!(
+                    "check_overdraft_limit - error - insufficient funds - client id: {} - available balance: {available_balance} - amount: {amount}",
+                    client.client_id
+                );
+                Err(CommonError::InsufficientFunds {
+                    client_id: client.client_id,
+                    available_balance,
+                    amount,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reserve [Decimal] amount on the account of [i32] client_id until [Duration] expires_in has passed
+    /// Returns the new [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Client, the account is not active,
+    /// the hold exceeds its overdraft limit or the held balance cannot be saved
+    fn place_hold(
+        &self,
+        client_id: i32,
+        amount: Decimal,
+        expires_in: chrono::Duration,
+    ) -> Result<(Hold, Client), CommonError> {
+        // the ledger lock serializes every balance change
+        let _transactions_map = self.write_transactions()?;
+
+        let client = self.get_active_client(client_id)?;
+        self.check_overdraft_limit(&client, amount)?;
+
+        let hold_id = self.app_state.hold_id_unique.fetch_add(1, Ordering::SeqCst);
+        let created_at = Utc::now();
+        let expires_at = created_at + expires_in;
+        self.write_ahead(
+            Event::HoldPlaced {
+                hold_id,
+                client_id,
+                amount,
+                expires_at,
+            },
+            created_at,
+        )?;
+        self.apply_hold_placed(hold_id, client_id, amount, created_at, expires_at)
+    }
+
+    /// Debit [Decimal] capture_amount, the whole hold if None, from the hold of [i32] hold_id active at [DateTime] now
+    /// Returns the captured [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Hold, it is not active, the account is not active,
+    /// the amount is greater than the hold or the balances cannot be saved
+    fn capture_active_hold(
+        &self,
+        hold_id: i32,
+        capture_amount: Option<Decimal>,
+        now: DateTime<Utc>,
+    ) -> Result<(Hold, Client), CommonError> {
+        // the ledger lock serializes every balance change
+        let mut transactions_map = self.write_transactions()?;
+
+        let hold = self.get_active_hold(hold_id, now)?;
+        self.get_active_client(hold.client_id)?;
+        let amount = capture_amount.unwrap_or(hold.amount);
+        if amount > hold.amount {
+            error!(
+                "capture_active_hold - error - capture amount greater than the hold - hold id: {hold_id} - amount: {amount}"
+            );
+            return Err(CommonError::InvalidField {
+                field: "capture_amount".to_string(),
+                reason: format!("must not be greater than the held amount {}", hold.amount),
+            });
+        }
+
+        self.write_ahead(Event::HoldCaptured { hold_id, amount }, now)?;
+        self.apply_hold_captured(&mut transactions_map, hold_id, amount, now)
+    }
+
+    /// Release the hold of [i32] hold_id active at [DateTime] now
+    /// Returns the released [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Hold, it is not active or the held balance cannot be saved
+    fn release_active_hold(
+        &self,
+        hold_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<(Hold, Client), CommonError> {
+        // the ledger lock serializes every balance change
+        let _transactions_map = self.write_transactions()?;
+
+        self.get_active_hold(hold_id, now)?;
+        self.write_ahead(
+            Event::HoldReleased {
+                hold_id,
+                status: HoldStatus::Released,
+            },
+            now,
+        )?;
+        self.apply_hold_released(hold_id, HoldStatus::Released)
+    }
+
+    /// Get the [Hold] with [i32] hold_id if it is active at [DateTime] now
+    /// An active hold past its expiry is released as expired before returning the error
+    /// Returns a [CommonError] if cannot find the Hold or it is not active
+    fn get_active_hold(&self, hold_id: i32, now: DateTime<Utc>) -> Result<Hold, CommonError> {
+        let hold = self.get_existing_hold(hold_id)?;
+        if hold.is_active(now) {
+            return Ok(hold);
+        }
+
+        let status = match hold.status {
+            HoldStatus::Active => {
+                self.expire_hold(hold_id, now)?;
+                HoldStatus::Expired
+            }
+            status => status,
+        };
+        error!(
+            "get_active_hold - error - hold not active - hold id: {hold_id} - status: {:?}",
+            status
+        );
+        Err(CommonError::HoldNotActive { hold_id, status })
+    }
+
+    /// Write ahead and release the hold of [i32] hold_id as expired at [DateTime] now
+    /// Returns a [CommonError] if the event cannot be written or the held balance cannot be saved
+    fn expire_hold(&self, hold_id: i32, now: DateTime<Utc>) -> Result<(Hold, Client), CommonError> {
+        self.write_ahead(
+            Event::HoldReleased {
+                hold_id,
+                status: HoldStatus::Expired,
+            },
+            now,
+        )?;
+        self.apply_hold_released(hold_id, HoldStatus::Expired)
+    }
+
+    /// Add a hold of [Decimal] amount to the held balance of [i32] client_id and save it as [i32] hold_id
+    /// Returns the new [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Client or cannot save the held balance
+    fn apply_hold_placed(
+        &self,
+        hold_id: i32,
+        client_id: i32,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(Hold, Client), CommonError> {
+        let mut client = self.get_existing_client(client_id)?;
+        client.held_balance += amount;
+        self.client_repository
+            .update_held_balance(client_id, client.held_balance)?;
+
+        let hold = Hold {
+            hold_id,
+            client_id,
+            amount,
+            captured_amount: Decimal::zero(),
+            status: HoldStatus::Active,
+            created_at,
+            expires_at,
+        };
+        self.write_holds()?.insert(hold_id, hold.clone());
+        Ok((hold, client))
+    }
+
+    /// Release the hold of [i32] hold_id, debit [Decimal] amount and record the debit in the ledger
+    /// Returns the captured [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Hold or the Client or cannot save the balances
+    fn apply_hold_captured(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        hold_id: i32,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+    ) -> Result<(Hold, Client), CommonError> {
+        let mut hold = self.get_existing_hold(hold_id)?;
+        let mut client = self.get_existing_client(hold.client_id)?;
+        client.held_balance -= hold.amount;
+        client.balance -= amount;
+        self.client_repository.update_balance_and_held_balance(
+            client.client_id,
+            client.balance,
+            client.held_balance,
+        )?;
+        self.record_transaction(
+            transactions_map,
+            &client,
+            TransactionKind::Debit,
+            amount,
+            created_at,
+            None,
+        )?;
+
+        hold.status = HoldStatus::Captured;
+        hold.captured_amount = amount;
+        self.write_holds()?.insert(hold_id, hold.clone());
+        Ok((hold, client))
+    }
+
+    /// Remove the hold of [i32] hold_id from the held balance of its client and set [HoldStatus] status
+    /// Returns the released [Hold] and the updated [Client]
+    /// Returns a [CommonError] if cannot find the Hold or the Client or cannot save the held balance
+    fn apply_hold_released(
+        &self,
+        hold_id: i32,
+        status: HoldStatus,
+    ) -> Result<(Hold, Client), CommonError> {
+        let mut hold = self.get_existing_hold(hold_id)?;
+        let mut client = self.get_existing_client(hold.client_id)?;
+        client.held_balance -= hold.amount;
+        self.client_repository
+            .update_held_balance(client.client_id, client.held_balance)?;
+
+        hold.status = status;
+        self.write_holds()?.insert(hold_id, hold.clone());
+        Ok((hold, client))
+    }
+
+    /// Get the [Hold] with [i32] hold_id
+    /// Returns a [CommonError] if the RwLock cannot be read or cannot find the Hold
+    fn get_existing_hold(&self, hold_id: i32) -> Result<Hold, CommonError> {
+        self.read_holds()?.get(&hold_id).cloned().ok_or_else(|| {
+            error!("get_existing_hold - error - hold id not found - hold id: {hold_id}");
+            CommonError::HoldNotFound(hold_id)
+        })
+    }
+
+    /// Take the read lock of the holds
+    /// Returns a [CommonError] if the RwLock cannot be read
+    fn read_holds(&self) -> Result<RwLockReadGuard<'_, HashMap<i32, Hold>>, CommonError> {
+        self.app_state.holds.read().map_err(|error| {
+            error!("read_holds - error when reading holds - error: {:?}", error);
+            CommonError::LockReadFailed
+        })
+    }
+
+    /// Take the write lock of the holds
+    /// Returns a [CommonError] if the RwLock cannot be written
+    fn write_holds(&self) -> Result<RwLockWriteGuard<'_, HashMap<i32, Hold>>, CommonError> {
+        self.app_state.holds.write().map_err(|error| {
+            error!(
+                "write_holds - error when writing holds - error: {:?}",
+                error
+            );
+            CommonError::LockWriteFailed
+        })
+    }
+
+    /// Take the lock of the idempotency keys
+    /// Returns a [CommonError] if the Mutex cannot be locked
+    fn lock_idempotency_records(
+        &self,
+    ) -> Result<MutexGuard<'_, HashMap<String, IdempotencyRecord>>, CommonError> {
+        self.app_state
+            .idempotency_store
+            .records
+            .lock()
+            .map_err(|error| {
+                error!(
+                    "lock_idempotency_records - error when locking idempotency keys - error: {:?}",
+                    error
+                );
+                CommonError::LockWriteFailed
+            })
+    }
+
+    /// Change the account of [i32] client_id to [ClientStatus] status, closings carry [ClosureReason] reason_code
+    /// Returns the updated [Client]
+    /// Returns a [CommonError] if cannot find the Client, the transition is not allowed,
+    /// a closed account would keep a balance or active holds, or the status cannot be saved
+    fn change_client_status(
+        &self,
+        client_id: i32,
+        status: ClientStatus,
+        reason_code: Option<ClosureReason>,
+    ) -> Result<Client, CommonError> {
+        // the ledger lock keeps credits and debits out while the status and the balance are checked
+        let _transactions_map = self.write_transactions()?;
+
+        let client = self.get_existing_client(client_id)?;
+        if !client.status.can_change_to(status) {
+            error!(
+                "change_client_status - error - invalid transition - client id: {client_id} - from: {} - to: {}",
+                client.status.as_str(),
+                status.as_str()
+            );
+            return Err(CommonError::InvalidStatusTransition {
+                client_id,
+                from: client.status,
+                to: status,
+            });
+        }
+        if status == ClientStatus::Closed && !client.balance.is_zero() {
+            error!(
+                "change_client_status - error - balance not zero - client id: {client_id} - balance: {}",
+                client.balance
+            );
+            return Err(CommonError::BalanceNotZero {
+                client_id,
+                balance: client.balance,
+            });
+        }
+        if status == ClientStatus::Closed && !client.held_balance.is_zero() {
+            error!(
+                "change_client_status - error - held balance not zero - client id: {client_id} - held balance: {}",
+                client.held_balance
+            );
+            return Err(CommonError::HeldBalanceNotZero {
+                client_id,
+                held_balance: client.held_balance,
+            });
+        }
+
+        self.write_ahead(
+            Event::ClientStatusChanged {
+                client_id,
+                status,
+                reason_code,
+            },
+            Utc::now(),
+        )?;
+        self.client_repository.update_status(client_id, status)?;
+        self.get_existing_client(client_id)
+    }
+
+    /// Run [FnOnce] apply_transaction only the first time [str] idempotency_key is received with [String] fingerprint
+    /// Replays return the balance of the first execution, without a key the transaction is always applied
+    /// apply_transaction receives the [IdempotentRequest] to write to the event log, [None] without a key
+    /// Returns a [CommonError] if the key was used with another fingerprint or apply_transaction fails
+    fn apply_once_per_idempotency_key(
+        &self,
+        idempotency_key: Option<&str>,
+        fingerprint: String,
+        apply_transaction: impl FnOnce(Option<IdempotentRequest>) -> Result<Decimal, CommonError>,
+    ) -> Result<Decimal, CommonError> {
+        let idempotency_key = match idempotency_key {
+            Some(idempotency_key) if !idempotency_key.is_empty() => idempotency_key,
+            _ => return apply_transaction(None),
+        };
+        debug!("apply_once_per_idempotency_key - start - idempotency_key: {idempotency_key}");
+
+        let idempotency_store = &self.app_state.idempotency_store;
+        // keep the lock while applying, so concurrent retries wait for the first one
+        let mut records = self.lock_idempotency_records()?;
+        idempotency_store.remove_expired_records(&mut records);
+
+        if let Some(record) = records.get(idempotency_key) {
+            return if record.fingerprint == fingerprint {
+                info!(
+                    "apply_once_per_idempotency_key - replay - idempotency_key: {idempotency_key}"
+                );
+                Ok(record.balance)
+            } else {
+                error!("apply_once_per_idempotency_key - error - idempotency key used with another payload - idempotency_key: {idempotency_key}");
+                Err(CommonError::IdempotencyKeyConflict(
+                    idempotency_key.to_string(),
+                ))
+            };
+        }
+
+        // failed transactions are not saved, so they can be retried
+        let balance = apply_transaction(Some(IdempotentRequest {
+            idempotency_key: idempotency_key.to_string(),
+            fingerprint: fingerprint.clone(),
+        }))?;
+        records.insert(
+            idempotency_key.to_string(),
+            IdempotencyRecord {
+                fingerprint,
+                balance,
+                created_at: Instant::now(),
+            },
+        );
+        debug!("apply_once_per_idempotency_key - done");
+        Ok(balance)
+    }
+
+    /// Take the write lock of the transactions ledger
+    /// Returns a [CommonError] if the RwLock cannot be written
+    fn write_transactions(
+        &self,
+    ) -> Result<RwLockWriteGuard<'_, HashMap<i32, Vec<Transaction>>>, CommonError> {
+        self.app_state.transactions.write().map_err(|error| {
+            error!(
+                "write_transactions - error when writing transactions - error: {:?}",
+                error
+            );
+            CommonError::LockWriteFailed
+        })
+    }
+
+    /// Record in the ledger a [TransactionKind] transaction of [Decimal] amount already applied on [Client] client,
+    /// linked to the transaction it compensates if it is a reversal, and keep its position in the ledger
+    /// Returns a [CommonError] if the RwLock of the transaction positions cannot be written
+    fn record_transaction(
+        &self,
+        transactions_map: &mut HashMap<i32, Vec<Transaction>>,
+        client: &Client,
+        kind: TransactionKind,
+        amount: Decimal,
+        created_at: DateTime<Utc>,
+        reversal_of: Option<i32>,
+    ) -> Result<Transaction, CommonError> {
+        let transaction = Transaction {
+            transaction_id: self
+                .app_state
+                .transaction_id_unique
+                .fetch_add(1, Ordering::SeqCst),
+            client_id: client.client_id,
+            kind,
+            amount,
+            balance: client.balance,
+            created_at,
+            reversal_of,
+            reversed_amount: Decimal::ZERO,
+        };
+        let transactions = transactions_map.entry(client.client_id).or_default();
+        self.app_state
+            .transaction_positions
+            .write()
+            .map_err(|error| {
+                error!(
+                    "record_transaction - error when writing transaction positions - error: {:?}",
+                    error
+                );
+                CommonError::LockWriteFailed
+            })?
+            .insert(
+                transaction.transaction_id,
+                (client.client_id, transactions.len()),
+            );
+        transactions.push(transaction.clone());
+        debug!("record_transaction - Transaction {:?}", transaction);
+        Ok(transaction)
+    }
+
+    /// Generates the full path for the next client balance file, by default in the format `DDMMYYYY_N.DAT`, based on how many files already exist in the storage folder
+    /// Returns a [CommonError] if throws any error
+    async fn generate_next_balance_file_name(&self, date: String) -> Result<String, CommonError> {
+        debug!("generate_next_balance_file_name - start");
+
+        let balance_folder = &self.app_state.balance_folder;
+        let file_name_pattern = &self.app_state.balance_file_name_pattern;
+        let mut file_counter = 1;
+
+        // create folder
+        if !Path::new(balance_folder).exists() {
+            fs::create_dir_all(balance_folder).await.map_err(|error| {
+                error!(
+                    "generate_next_balance_file_name - error when creating folder error: {:?}",
+                    error
+                );
+                CommonError::FolderCreationFailed(balance_folder.to_string())
+            })?;
+        }
+
+        // read folder
+        let mut read_folder = fs::read_dir(balance_folder).await.map_err(|error| {
+            error!(
+                "generate_next_balance_file_name - error when reading folder error: {:?}",
+                error
+            );
+            CommonError::FolderReadFailed(balance_folder.to_string())
+        })?;
+        while let Some(file) = read_folder.next_entry().await.map_err(|error| {
+            error!(
+                "generate_next_balance_file_name - error when reading file error: {:?}",
+                error
+            );
+            CommonError::FolderReadFailed(balance_folder.to_string())
+        })? {
+            let get_file_name = file.file_name();
+            let file_name = get_file_name.to_string_lossy();
+            if file_name_pattern.counter(&file_name, &date).is_some() {
+                file_counter += 1;
+            }
+        }
+        let format_file_name = Path::new(balance_folder)
+            .join(file_name_pattern.file_name(&date, file_counter))
+            .display()
+            .to_string();
+
+        Ok(format_file_name)
+    }
+    /// Create a text file and save client balances
+    /// Returns a [CommonError] if throws any error
+    async fn write_in_the_file_the_balance_of_the_clients(
+        &self,
+        format_file_name: String,
+    ) -> Result<(), CommonError> {
+        debug!("write_in_the_file_the_balance_of_the_clients - start");
+
+        // clients sorted by client id in ascending order
+        let (temporal_client_data, ledger_cut) = self.snapshot_balances().map_err(|error| {
+            error!("write_in_the_file_the_balance_of_the_clients - error when reading the clients - error: {:?}",error);
+            error
+        })?;
+
+        // format client id and balance, with control records unless the legacy format is used
+        let file_content = render_balance_file(
+            &temporal_client_data,
+            self.app_state.balance_file_format,
+            Utc::now(),
+        );
+        self.write_file_atomically(&format_file_name, file_content.as_bytes())
+            .await?;
+
+        // remove the stored balances, keeping what was credited or debited meanwhile
+        let file_name = Path::new(&format_file_name)
+            .file_name()
+            .map_or(format_file_name.clone(), |file_name| {
+                file_name.to_string_lossy().to_string()
+            });
+        self.reset_balances(temporal_client_data, file_name.clone(), ledger_cut)
+        .map_err(|error| {
+                error!("write_in_the_file_the_balance_of_the_clients - error when resetting the balances - error: {:?}",error);
+                error
+            })?;
+        // prove the file matches the movements recorded before it
+        self.alert_balance_file_mismatches(&file_name, &file_content);
+        debug!("write_in_the_file_the_balance_of_the_clients - done");
+        Ok(())
+    }
+    /// Write [u8] content in [String] format_file_name through a temporary file in the same folder
+    /// The temporary file is fsynced and renamed, so format_file_name is either missing or complete
+    /// Returns a [CommonError] if the file cannot be created, written or renamed
+    async fn write_file_atomically(
+        &self,
+        format_file_name: &str,
+        content: &[u8],
+    ) -> Result<(), CommonError> {
+        debug!("write_file_atomically - start");
+
+        let temporary_file_name = format!("{format_file_name}{TEMPORARY_FILE_SUFFIX}");
+        let result = self
+            .write_and_rename(&temporary_file_name, format_file_name, content)
+            .await;
+        if result.is_err() {
+            // a failed attempt must not leave anything behind
+            let _ = fs::remove_file(&temporary_file_name).await;
+        }
+
+        debug!("write_file_atomically - done");
+        result
+    }
+
+    /// Write [u8] content in [str] temporary_file_name, fsync it and rename it to [str] format_file_name
+    /// Returns a [CommonError] if the file cannot be created, written or renamed
+    async fn write_and_rename(
+        &self,
+        temporary_file_name: &str,
+        format_file_name: &str,
+        content: &[u8],
+    ) -> Result<(), CommonError> {
+        let mut new_file = fs::File::create(temporary_file_name)
+            .await
+            .map_err(|error| {
+                error!(
+                    "write_and_rename - error when creating file error: {:?}",
+                    error
+                );
+                CommonError::FileCreationFailed(format_file_name.to_string())
+            })?;
+        new_file.write_all(content).await.map_err(|error| {
+            error!("write_and_rename - error when writing to the file - file name: {temporary_file_name} - error: {:?}",error);
+            CommonError::FileWriteFailed(format_file_name.to_string())
+        })?;
+        new_file.sync_all().await.map_err(|error| {
+            error!("write_and_rename - error when syncing the file - file name: {temporary_file_name} - error: {:?}",error);
+            CommonError::FileWriteFailed(format_file_name.to_string())
+        })?;
+        drop(new_file);
+
+        fs::rename(temporary_file_name, format_file_name).await.map_err(|error| {
+            error!("write_and_rename - error when renaming the file - file name: {format_file_name} - error: {:?}",error);
+            CommonError::FileWriteFailed(format_file_name.to_string())
+        })?;
+        // persist the rename itself
+        if let Some(folder) = Path::new(format_file_name).parent() {
+            if let Ok(folder) = fs::File::open(folder).await {
+                let _ = folder.sync_all().await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Client service trait dyn type
+pub type DynClientService = Arc<dyn ClientServiceTrait + Send + Sync>;
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance_file::balance_file_format::BalanceFileFormat;
+    use crate::balance_file::balance_file_name_pattern::BalanceFileNamePattern;
+    use crate::constants::constants::{CLIENT_BALANCE_FOLDER, PREFIX_FILE};
+    use crate::dto::reconciliation_report_dto::BalanceMismatch;
+    use crate::model::client_model::Client;
+    use crate::model::overdraft_model::{OverdraftLimit, OverdraftLimits};
+    use crate::service::client_service::ClientService;
+    use crate::state::event_log::EventLog;
+    use crate::state::idempotency_store::IdempotencyStore;
+    use crate::stub::client_info_stub::stub::create_client_info_stub;
+    use crate::stub::new_client_stub::stub::{create_new_client_stub, CLIENT_DOCUMENT_NUMBER};
+    use crate::stub::new_credit_transaction_stub::stub::create_new_credit_transaction_stub;
+    use crate::stub::new_debit_transaction_stub::stub::create_new_debit_transaction_stub;
+    use crate::stub::new_hold_stub::stub::{create_new_hold_stub, HOLD_AMOUNT};
+    use crate::stub::new_transfer_stub::stub::{create_new_transfer_stub, TO_CLIENT_ID};
+    use crate::validator::transaction_validator::TransactionLimits;
+    use chrono::NaiveDate;
+    use serial_test::serial;
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::sync::atomic::AtomicI32;
+    use std::sync::RwLock;
+    use std::time::Duration;
+
+    const MOCK_CLIENT_ID: i32 = 1;
+    const IDEMPOTENCY_KEY: &str = "some-idempotency-key";
+
+    /// Scenario:
+    /// Execute create_new_client when [NewClient] is valid
+    /// Expectation:
+    /// A client id should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_client_with_valid_values_should_return_client_id() {
+        let new_client = create_new_client_stub();
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(HashMap::new())),
+            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.create_new_client(new_client).await.unwrap();
+
+        assert_eq!(MOCK_CLIENT_ID, expected_result);
+    }
+
+    /// Scenario:
+    /// Execute create_new_client when [NewClient] has a blank name or a birth date in the future
+    /// Expectation:
+    /// A [CommonError] naming the field should be returned, as for an imported row, and no client id should be used
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_client_with_invalid_fields_should_return_common_error() {
+        let app_state = Arc::new(AppState {
+            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+        let mut blank_name = create_new_client_stub();
+        blank_name.client_name = " ".to_string();
+        let mut future_birth_date = create_new_client_stub();
+        future_birth_date.birth_date = Local::now().date_naive() + chrono::Days::new(1);
+
+        let blank_name_result = client_service.create_new_client(blank_name).await;
+        let future_birth_date_result = client_service.create_new_client(future_birth_date).await;
+
+        assert!(matches!(
+            blank_name_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "client_name"
+        ));
+        assert!(matches!(
+            future_birth_date_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "birth_date"
+        ));
+        assert_eq!(
+            MOCK_CLIENT_ID,
+            app_state.client_id_unique.load(Ordering::SeqCst)
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_new_client when [NewClient] is valid but write RwLock failed
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_client_with_valid_values_and_rwlock_failed_should_return_common_error()
+    {
+        let new_client = create_new_client_stub();
+
+        let client = Arc::new(RwLock::new(HashMap::<i32, Client>::new()));
+
+        let clients_ref = Arc::clone(&client);
+
+        {
+            let _ = std::thread::spawn(move || {
+                let _guard = clients_ref.write().unwrap();
+                panic!("error trying write");
+            })
+            .join();
+        }
+
+        let app_state = Arc::new(AppState {
+            clients: client,
+            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.create_new_client(new_client).await;
+
+        assert_eq!(CommonError::LockReadFailed, expected_result.unwrap_err());
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction when [NewCreditTransaction] is valid
+    /// Expectation:
+    /// A client id should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_credit_transaction_with_valid_values_should_return_client_id() {
+        let new_credit = create_new_credit_transaction_stub();
+        let client_stub = create_new_client_stub();
+        let balance = Decimal::new(100, 2);
+
+        let client = Client {
+            client_id: new_credit.client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+
+        let mut hashmap = HashMap::new();
+        hashmap.insert(new_credit.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(new_credit.client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+
+        assert_eq!(balance, expected_result);
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction when [NewCreditTransaction] is invalid client id
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_credit_transaction_with_invalid_values_should_return_common_error() {
+        let new_credit = create_new_credit_transaction_stub();
+        let client_stub = create_new_client_stub();
+
+        let client = Client {
+            client_id: new_credit.client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+
+        let mut hashmap = HashMap::new();
+        hashmap.insert(new_credit.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(new_credit.client_id),
+            ..Default::default()
+        });
+
+        {
+            let read_lock = app_state.clients.read().unwrap();
+            assert!(read_lock.contains_key(&new_credit.client_id));
+        }
+        {
+            let mut write_lock = app_state.clients.write().unwrap();
+            write_lock.remove(&new_credit.client_id);
+            write_lock.get(&new_credit.client_id);
+            assert!(write_lock.get(&new_credit.client_id).is_none());
+        }
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .create_new_credit_transaction(new_credit)
+            .await;
+
+        assert_eq!(
+            CommonError::ClientNotFound(MOCK_CLIENT_ID),
+            expected_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_new_debit_transaction when [NewDebitTransaction] is valid
+    /// Expectation:
+    /// A client id should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_debit_transaction_with_valid_values_should_return_client_id() {
+        let new_debit = create_new_debit_transaction_stub();
+        let client_stub = create_new_client_stub();
+        let balance = Decimal::new(100, 2);
+        let updated_balance = Decimal::new(0, 2);
+
+        let client = Client {
+            client_id: new_debit.client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance,
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(new_debit.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(new_debit.client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .create_new_debit_transaction(new_debit)
+            .await
+            .unwrap();
+
+        assert_eq!(updated_balance, expected_result);
+    }
+
+    /// Scenario:
+    /// Execute create_new_debit_transaction when [NewDebitTransaction] is valid but write RwLock failed
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_debit_transaction_with_valid_values_and_rwlock_failed_should_return_common_error(
+    ) {
+        let new_debit = create_new_debit_transaction_stub();
+
+        let client = Arc::new(RwLock::new(HashMap::<i32, Client>::new()));
+
+        let _ = std::panic::catch_unwind(|| {
+            let _readlock = client.read().unwrap();
+            panic!("error trying read");
+        });
+
+        let app_state = Arc::new(AppState {
+            clients: client,
+            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.create_new_debit_transaction(new_debit).await;
+
+        assert_eq!(
+            CommonError::ClientNotFound(MOCK_CLIENT_ID),
+            expected_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute get_client_balance when [client_id] is valid
+    /// Expectation:
+    /// A [ClientInfo] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_balance_with_valid_values_and_rwlock_failed_should_return_common_error(
+    ) {
+        let client = create_client_info_stub();
+        let result_client_info = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.get_client_balance(client_id).await.unwrap();
+
+        assert_eq!(result_client_info, expected_result);
+    }
+    /// Scenario:
+    /// Execute get_client_balance when [client_id] is invalid by client id
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_balance_with_invalid_client_id_and_rwlock_failed_should_return_common_error(
+    ) {
+        let client = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.get_client_balance(3).await;
+
+        assert_eq!(CommonError::ClientNotFound(3), expected_result.unwrap_err());
+    }
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances with exit
+    /// Expectation:
+    /// A [Ok()] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_with_exit_should_return_ok() {
+        let client = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service
+            .generate_file_with_all_clients_balances()
+            .await;
+        assert!(expected_result.is_ok());
+
+        let folder = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
+            .unwrap()
+            .filter_map(|file| file.ok())
+            .collect::<Vec<_>>();
+        assert!(!folder.is_empty());
+
+        let read_lock = app_state.clients.read().unwrap();
+        let client = read_lock.get(&client_id).unwrap();
+        assert_eq!(client.balance, Decimal::zero());
+    }
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances and create folder failed
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_and_create_folder_failed_should_return_common_error(
+    ) {
+        let client = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let _ = std::fs::remove_file(CLIENT_BALANCE_FOLDER);
+        let _ = std::fs::remove_dir_all(CLIENT_BALANCE_FOLDER);
+
+        File::create(CLIENT_BALANCE_FOLDER).expect("Cannot create file");
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service
+            .generate_file_with_all_clients_balances()
+            .await;
+
+        assert_eq!(
+            CommonError::FolderReadFailed(CLIENT_BALANCE_FOLDER.to_string()),
+            expected_result.unwrap_err()
+        );
+
+        let _ = std::fs::remove_file(CLIENT_BALANCE_FOLDER);
+    }
+
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances and failed when try write in lock
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_and_failed_try_write_in_lock_should_return_common_error(
+    ) {
+        let client = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+        let app_state_clone = app_state.clone();
+        let _ = std::thread::spawn(move || {
+            let _write_lock = app_state_clone.clients.write().unwrap();
+            panic!("error");
+        })
+        .join();
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service
+            .generate_file_with_all_clients_balances()
+            .await;
+
+        let folder = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
+            .unwrap()
+            .filter_map(|file| file.ok())
+            .collect::<Vec<_>>();
+        assert!(folder.is_empty());
+
+        assert_eq!(CommonError::LockReadFailed, expected_result.unwrap_err());
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction and create_new_debit_transaction when the client exists
+    /// Expectation:
+    /// Both transactions should be recorded in the ledger with the resulting balance
+    #[tokio::test]
+    #[serial]
+    async fn when_create_credit_and_debit_transactions_should_record_them_in_the_ledger() {
+        let new_credit = create_new_credit_transaction_stub();
+        let new_debit = create_new_debit_transaction_stub();
+        let client_stub = create_new_client_stub();
+        let client_id = new_credit.client_id;
+
+        let client = Client {
+            client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let credit_amount = new_credit.credit_amount;
+        let debit_amount = new_debit.debit_amount;
+        client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+        client_service
+            .create_new_debit_transaction(new_debit)
+            .await
+            .unwrap();
+
+        let transactions_map = app_state.transactions.read().unwrap();
+        let transactions = transactions_map.get(&client_id).unwrap();
+        assert_eq!(2, transactions.len());
+        assert_eq!(1, transactions[0].transaction_id);
+        assert_eq!(TransactionKind::Credit, transactions[0].kind);
+        assert_eq!(credit_amount, transactions[0].amount);
+        assert_eq!(credit_amount, transactions[0].balance);
+        assert_eq!(2, transactions[1].transaction_id);
+        assert_eq!(TransactionKind::Debit, transactions[1].kind);
+        assert_eq!(debit_amount, transactions[1].amount);
+        assert_eq!(credit_amount - debit_amount, transactions[1].balance);
+    }
+
+    /// Scenario:
+    /// Execute get_client_transactions with a date range and a page size
+    /// Expectation:
+    /// Only the transactions inside the range of the requested page should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_transactions_with_filter_should_return_requested_page() {
+        let client = create_client_info_stub();
+        let client_id = client.client_id;
+
+        let client = Client {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            birth_date: client.birth_date,
+            document_number: client.document_number,
+            country: client.country,
+            balance: client.balance,
+            version: client.version,
+            status: client.status,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client.client_id, client);
+
+        // one transaction per day, from 2025-07-01 to 2025-07-05
+        let transactions = (1..=5)
+            .map(|day| Transaction {
+                transaction_id: day,
+                client_id,
+                kind: TransactionKind::Credit,
+                amount: Decimal::new(100, 2),
+                balance: Decimal::new(100 * day as i64, 2),
+                created_at: NaiveDate::from_ymd_opt(2025, 7, day as u32)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+                    .and_utc(),
+                reversal_of: None,
+                reversed_amount: Decimal::ZERO,
+            })
+            .collect::<Vec<_>>();
+        let mut transactions_map = HashMap::new();
+        transactions_map.insert(client_id, transactions);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            transactions: Arc::new(RwLock::new(transactions_map)),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let filter = TransactionFilter {
+            from: NaiveDate::from_ymd_opt(2025, 7, 2),
+            to: NaiveDate::from_ymd_opt(2025, 7, 4),
+            page: Some(2),
+            page_size: Some(2),
+        };
+
+        let expected_result = client_service
+            .get_client_transactions(client_id, filter)
+            .await
+            .unwrap();
+
+        assert_eq!(3, expected_result.total_items);
+        assert_eq!(2, expected_result.page);
+        assert_eq!(2, expected_result.page_size);
+        assert_eq!(1, expected_result.items.len());
+        assert_eq!(4, expected_result.items[0].transaction_id);
+    }
+
+    /// Scenario:
+    /// Execute get_client_transactions when [client_id] is invalid
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_transactions_with_invalid_client_id_should_return_common_error() {
+        let app_state = Arc::new(AppState::default());
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .get_client_transactions(MOCK_CLIENT_ID, TransactionFilter::default())
+            .await;
+
+        assert_eq!(
+            CommonError::ClientNotFound(MOCK_CLIENT_ID),
+            expected_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction twice with the same idempotency key and payload
+    /// Expectation:
+    /// The credit should be applied once and the replay should return the original balance
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_credit_transaction_is_replayed_should_apply_it_once() {
+        let client_stub = create_new_client_stub();
+        let client_id = create_new_credit_transaction_stub().client_id;
+
+        let client = Client {
+            client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let first_result = client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+
+        let mut replayed_credit = create_new_credit_transaction_stub();
+        replayed_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let replay_result = client_service
+            .create_new_credit_transaction(replayed_credit)
+            .await
+            .unwrap();
+
+        assert_eq!(first_result, replay_result);
+        let clients_map = app_state.clients.read().unwrap();
+        assert_eq!(first_result, clients_map.get(&client_id).unwrap().balance);
+        let transactions_map = app_state.transactions.read().unwrap();
+        assert_eq!(1, transactions_map.get(&client_id).unwrap().len());
+    }
+
+    /// Scenario:
+    /// Execute create_new_debit_transaction with an idempotency key already used by a credit
+    /// Expectation:
+    /// A [CommonError] should be returned and the balance should not change
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_debit_transaction_reuses_idempotency_key_with_another_payload_should_return_common_error(
+    ) {
+        let client_stub = create_new_client_stub();
+        let client_id = create_new_credit_transaction_stub().client_id;
+
+        let client = Client {
+            client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let balance = client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+
+        let mut new_debit = create_new_debit_transaction_stub();
+        new_debit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let expected_result = client_service.create_new_debit_transaction(new_debit).await;
+
+        assert_eq!(
+            CommonError::IdempotencyKeyConflict(IDEMPOTENCY_KEY.to_string()),
+            expected_result.unwrap_err()
+        );
+        let clients_map = app_state.clients.read().unwrap();
+        assert_eq!(balance, clients_map.get(&client_id).unwrap().balance);
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction twice with the same idempotency key when the key already expired
+    /// Expectation:
+    /// The credit should be applied twice
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_credit_transaction_is_replayed_after_key_expired_should_apply_it_again(
+    ) {
+        let client_stub = create_new_client_stub();
+        let new_credit = create_new_credit_transaction_stub();
+        let client_id = new_credit.client_id;
+        let credit_amount = new_credit.credit_amount;
+
+        let client = Client {
+            client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(client_id),
+            idempotency_store: IdempotencyStore::new(Duration::ZERO),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        for _ in 0..2 {
+            let mut new_credit = create_new_credit_transaction_stub();
+            new_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+            client_service
+                .create_new_credit_transaction(new_credit)
+                .await
+                .unwrap();
+        }
+
+        let clients_map = app_state.clients.read().unwrap();
+        assert_eq!(
+            credit_amount + credit_amount,
+            clients_map.get(&client_id).unwrap().balance
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction with an idempotency key and the event log enabled,
+    /// then replay the event log on an empty AppState and retry the credit and a debit with the same key
+    /// Expectation:
+    /// The retried credit should return the original balance without applying it again
+    /// and the debit should be rejected as a reuse of the key
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_with_idempotency_key_should_replay_retries() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.client_id = client_id;
+        new_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let balance = client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+
+        let restored_service = ClientService::new(Arc::new(AppState::default()));
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let mut retried_credit = create_new_credit_transaction_stub();
+        retried_credit.client_id = client_id;
+        retried_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let expected_result = restored_service
+            .create_new_credit_transaction(retried_credit)
+            .await
+            .unwrap();
+        let mut new_debit = create_new_debit_transaction_stub();
+        new_debit.client_id = client_id;
+        new_debit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+
+        assert_eq!(balance, expected_result);
+        assert_eq!(
+            CommonError::IdempotencyKeyConflict(IDEMPOTENCY_KEY.to_string()),
+            restored_service
+                .create_new_debit_transaction(new_debit)
+                .await
+                .unwrap_err()
+        );
+        assert_eq!(
+            balance,
+            restored_service
+                .get_client_balance(client_id)
+                .await
+                .unwrap()
+                .balance
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_transfer when both clients exist
+    /// Expectation:
+    /// The amount should move from one client to the other and both movements should be recorded
+    #[tokio::test]
+    #[serial]
+    async fn when_create_transfer_with_valid_values_should_update_both_balances() {
+        let new_transfer = create_new_transfer_stub();
+        let transfer_amount = new_transfer.transfer_amount;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service.create_transfer(new_transfer).await.unwrap();
+
+        assert_eq!(MOCK_CLIENT_ID, expected_result.from_client_id);
+        assert_eq!(-transfer_amount, expected_result.from_balance);
+        assert_eq!(TO_CLIENT_ID, expected_result.to_client_id);
+        assert_eq!(transfer_amount, expected_result.to_balance);
+
+        let transactions_map = app_state.transactions.read().unwrap();
+        let from_transactions = transactions_map.get(&MOCK_CLIENT_ID).unwrap();
+        assert_eq!(TransactionKind::Debit, from_transactions[0].kind);
+        let to_transactions = transactions_map.get(&TO_CLIENT_ID).unwrap();
+        assert_eq!(TransactionKind::Credit, to_transactions[0].kind);
+    }
+
+    /// Scenario:
+    /// Execute create_transfer when the credited client does not exist
+    /// Expectation:
+    /// A [CommonError] should be returned and no balance should change
+    #[tokio::test]
+    #[serial]
+    async fn when_create_transfer_with_invalid_client_id_should_return_common_error() {
+        let mut new_transfer = create_new_transfer_stub();
+        new_transfer.to_client_id = 3;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service.create_transfer(new_transfer).await;
+
+        assert_eq!(CommonError::ClientNotFound(3), expected_result.unwrap_err());
+        let clients_map = app_state.clients.read().unwrap();
+        assert!(clients_map
+            .values()
+            .all(|client| client.balance == Decimal::zero()));
+        assert!(app_state.transactions.read().unwrap().is_empty());
+    }
+
+    /// Scenario:
+    /// Execute create_transfer when both client ids are the same
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_transfer_to_the_same_client_should_return_common_error() {
+        let mut new_transfer = create_new_transfer_stub();
+        new_transfer.to_client_id = new_transfer.from_client_id;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.create_transfer(new_transfer).await;
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "to_client_id"
+        ));
+    }
+
+    /// Create the debited and the credited clients of a transfer, both without balance
+    fn create_transfer_clients() -> HashMap<i32, Client> {
+        let mut hashmap = HashMap::new();
+        for client_id in [MOCK_CLIENT_ID, TO_CLIENT_ID] {
+            let client_stub = create_new_client_stub();
+            hashmap.insert(
+                client_id,
+                Client {
+                    client_id,
+                    client_name: client_stub.client_name,
+                    birth_date: client_stub.birth_date,
+                    document_number: format!("{}-{}", client_stub.document_number, client_id),
+                    country: client_stub.country,
+                    balance: Decimal::zero(),
+                    version: INITIAL_CLIENT_VERSION,
+                    status: ClientStatus::Active,
+                    held_balance: Decimal::ZERO,
+                },
+            );
+        }
+        hashmap
+    }
+
+    /// Scenario:
+    /// Execute create_new_credit_transaction with a negative amount
+    /// Expectation:
+    /// A [CommonError] should be returned and the balance should not change
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_credit_transaction_with_negative_amount_should_return_common_error() {
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.credit_amount = Decimal::new(-100, 2);
+        let client_stub = create_new_client_stub();
+
+        let client = Client {
+            client_id: new_credit.client_id,
+            client_name: client_stub.client_name,
+            birth_date: client_stub.birth_date,
+            document_number: client_stub.document_number,
+            country: client_stub.country,
+            balance: Decimal::zero(),
+            version: INITIAL_CLIENT_VERSION,
+            status: ClientStatus::Active,
+            held_balance: Decimal::ZERO,
+        };
+        let mut hashmap = HashMap::new();
+        hashmap.insert(new_credit.client_id, client);
+
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(hashmap)),
+            client_id_unique: AtomicI32::new(new_credit.client_id),
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service
+            .create_new_credit_transaction(new_credit)
+            .await;
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "credit_amount"
+        ));
+        let clients_map = app_state.clients.read().unwrap();
+        assert_eq!(
+            Decimal::zero(),
+            clients_map.get(&MOCK_CLIENT_ID).unwrap().balance
+        );
+    }
+
+    /// Scenario:
+    /// Execute create_new_debit_transaction with an amount greater than the configured max amount
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_debit_transaction_greater_than_max_amount_should_return_common_error()
+    {
+        let new_debit = create_new_debit_transaction_stub();
+
+        let app_state = Arc::new(AppState {
+            transaction_limits: TransactionLimits {
+                max_scale: 2,
+                max_amount: Decimal::new(50, 2),
+            },
+            ..Default::default()
+        });
+
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service.create_new_debit_transaction(new_debit).await;
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "debit_amount"
+        ));
+    }
+
+    /// Scenario:
+    /// Execute create_new_client, create_new_credit_transaction, create_transfer and a failed debit with the event log enabled,
+    /// then replay the event log on an empty AppState
+    /// Expectation:
+    /// Clients, balances, ledger and next client id should be restored and the failed debit should not be logged
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_should_rebuild_the_state() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let app_state = Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+
+        let from_client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let mut other_client = create_new_client_stub();
+        other_client.document_number = "some-other-document-number".to_string();
+        let to_client_id = client_service
+            .create_new_client(other_client)
+            .await
+            .unwrap();
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.client_id = from_client_id;
+        client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+        let mut new_transfer = create_new_transfer_stub();
+        new_transfer.from_client_id = from_client_id;
+        new_transfer.to_client_id = to_client_id;
+        new_transfer.transfer_amount = Decimal::new(40, 2);
+        client_service.create_transfer(new_transfer).await.unwrap();
+        let mut new_debit = create_new_debit_transaction_stub();
+        new_debit.client_id = 3;
+        assert!(client_service
+            .create_new_debit_transaction(new_debit)
+            .await
+            .is_err());
+
+        let events = EventLog::read_all(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let restored_state = Arc::new(AppState::default());
+        let restored_service = ClientService::new(restored_state.clone());
+
+        let expected_result = restored_service.restore_from_event_log(events).unwrap();
+
+        assert_eq!(4, expected_result);
+        let from_client = restored_service
+            .get_client_balance(from_client_id)
+            .await
+            .unwrap();
+        let to_client = restored_service
+            .get_client_balance(to_client_id)
+            .await
+            .unwrap();
+        assert_eq!(Decimal::new(60, 2), from_client.balance);
+        assert_eq!(Decimal::new(40, 2), to_client.balance);
+        assert_eq!(
+            app_state.client_id_unique.load(Ordering::SeqCst),
+            restored_state.client_id_unique.load(Ordering::SeqCst)
+        );
+        let transactions = restored_service
+            .get_client_transactions(from_client_id, TransactionFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(2, transactions.total_items);
+    }
+
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances several times while credits are applied concurrently
+    /// Expectation:
+    /// Every credited amount should be either in a balance file or in the client balance
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_during_credits_should_not_lose_any_amount(
+    ) {
+        const CREDITS_PER_CLIENT: i64 = 2000;
+        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            ..Default::default()
+        });
+        let client_service = Arc::new(ClientService::new(app_state));
+
+        let mut tasks = Vec::new();
+        for client_id in [MOCK_CLIENT_ID, TO_CLIENT_ID] {
+            let client_service = Arc::clone(&client_service);
+            tasks.push(tokio::spawn(async move {
+                for _ in 0..CREDITS_PER_CLIENT {
+                    let mut new_credit = create_new_credit_transaction_stub();
+                    new_credit.client_id = client_id;
+                    client_service
+                        .create_new_credit_transaction(new_credit)
+                        .await
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+        let balance_file_service = Arc::clone(&client_service);
+        tasks.push(tokio::spawn(async move {
+            for _ in 0..20 {
+                balance_file_service
+                    .generate_file_with_all_clients_balances()
+                    .await
+                    .unwrap();
+                tokio::task::yield_now().await;
+            }
+        }));
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let mut stored_total = Decimal::zero();
+        for file in std::fs::read_dir(CLIENT_BALANCE_FOLDER).unwrap() {
+            let content = std::fs::read_to_string(file.unwrap().path()).unwrap();
+            for (_, balance) in parse_balance_file(&content).unwrap().balances {
+                stored_total += balance;
+            }
+        }
+        let mut in_memory_total = Decimal::zero();
+        for client_id in [MOCK_CLIENT_ID, TO_CLIENT_ID] {
+            in_memory_total += client_service
+                .get_client_balance(client_id)
+                .await
+                .unwrap()
+                .balance;
+        }
+        let credited_total = create_new_credit_transaction_stub().credit_amount
+            * Decimal::from(2 * CREDITS_PER_CLIENT);
+        assert_eq!(credited_total, stored_total + in_memory_total);
+    }
+
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances with exit
+    /// Expectation:
+    /// Only the final balance file should be left in the folder, without temporary files
+    #[tokio::test]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_should_not_leave_temporary_files() {
+        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .generate_file_with_all_clients_balances()
+            .await;
+
+        assert!(expected_result.is_ok());
+        let file_names = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
+            .unwrap()
+            .map(|file| file.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(1, file_names.len());
+        assert!(file_names[0].ends_with(PREFIX_FILE));
+    }
+
+    /// Scenario:
+    /// Execute write_in_the_file_the_balance_of_the_clients when the temporary file cannot be renamed
+    /// Expectation:
+    /// A [CommonError] should be returned, the temporary file removed and the balances kept
+    #[tokio::test]
+    #[serial]
+    async fn when_write_in_the_file_the_balance_of_the_clients_and_rename_failed_should_keep_the_balances(
+    ) {
+        let folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+        // a folder with content cannot be replaced by the balance file
+        let format_file_name = folder.join("01012025_1.DAT");
+        std::fs::create_dir_all(format_file_name.join("content")).unwrap();
+        let format_file_name = format_file_name.display().to_string();
+        let mut clients = create_transfer_clients();
+        clients.get_mut(&MOCK_CLIENT_ID).unwrap().balance = Decimal::new(100, 2);
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(clients)),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .write_in_the_file_the_balance_of_the_clients(format_file_name.clone())
+            .await;
+
+        let temporary_file_exists =
+            Path::new(&format!("{format_file_name}{TEMPORARY_FILE_SUFFIX}")).exists();
+        let _ = std::fs::remove_dir_all(&folder);
+        assert_eq!(
+            CommonError::FileWriteFailed(format_file_name),
+            expected_result.unwrap_err()
+        );
+        assert!(!temporary_file_exists);
+        assert_eq!(
+            Decimal::new(100, 2),
+            client_service
+                .get_client_balance(MOCK_CLIENT_ID)
+                .await
+                .unwrap()
+                .balance
+        );
+    }
+
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances with [BalanceFileFormat::Legacy]
+    /// Expectation:
+    /// The file should only have the `ID BALANCE` lines
+    #[tokio::test]
+    #[serial]
+    async fn when_generate_file_with_all_clients_balances_with_legacy_format_should_write_only_balances(
+    ) {
+        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+        let mut clients = create_transfer_clients();
+        clients.get_mut(&MOCK_CLIENT_ID).unwrap().balance = Decimal::new(1050, 2);
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(clients)),
+            balance_file_format: BalanceFileFormat::Legacy,
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .generate_file_with_all_clients_balances()
+            .await;
+
+        assert!(expected_result.is_ok());
+        let file = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!("01 10.50\n02 0.00\n", content);
+    }
+
+    /// Scenario:
+    /// Execute restore_from_latest_balance_file with several balance files in the folder,
+    /// one of them naming a client missing from the current state
+    /// Expectation:
+    /// The most recent file should be added to the balances, the missing client reported, and the file not loaded twice
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_latest_balance_file_should_load_the_most_recent_file() {
+        let balance_folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&balance_folder).unwrap();
+        let generated_at = Utc::now();
+        for (file_name, balance) in [
+            ("31012025_2.DAT", Decimal::new(1000, 2)),
+            ("01022025_1.DAT", Decimal::new(2500, 2)),
+            ("31012025_10.DAT", Decimal::new(9900, 2)),
+        ] {
+            let content = render_balance_file(
+                &[(MOCK_CLIENT_ID, balance), (3, Decimal::ONE)],
+                BalanceFileFormat::Control,
+                generated_at,
+            );
+            std::fs::write(balance_folder.join(file_name), content).unwrap();
+        }
+        let mut clients = create_transfer_clients();
+        clients.get_mut(&MOCK_CLIENT_ID).unwrap().balance = Decimal::new(100, 2);
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(clients)),
+            balance_folder: balance_folder.display().to_string(),
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state);
+
+        let expected_result = client_service
+            .restore_from_latest_balance_file(&HashSet::new())
+            .await
+            .unwrap();
+        let loaded_again = client_service
+            .restore_from_latest_balance_file(&HashSet::from(["01022025_1.DAT".to_string()]))
+            .await
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&balance_folder);
+
+        assert_eq!(
+            Some(OpeningBalancesReport {
+                file_name: "01022025_1.DAT".to_string(),
+                restored_client_ids: vec![MOCK_CLIENT_ID],
+                missing_client_ids: vec![3],
+            }),
+            expected_result
+        );
+        assert_eq!(None, loaded_again);
+        assert_eq!(
+            Decimal::new(2600, 2),
+            client_service
+                .get_client_balance(MOCK_CLIENT_ID)
+                .await
+                .unwrap()
+                .balance
+        );
+    }
+
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances with the event log enabled, then replay the event log
+    /// on an empty AppState with the same balance folder and execute restore_from_latest_balance_file
+    /// Expectation:
+    /// The file should be skipped, since its balances were already settled, and the balance left unchanged
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_latest_balance_file_already_settled_should_skip_the_file() {
+        let folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+        let path = folder.join("events.jsonl");
+        let balance_folder = folder.join("balances").display().to_string();
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            balance_folder: balance_folder.clone(),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.client_id = client_id;
+        client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+        client_service
+            .generate_file_with_all_clients_balances()
+            .await
+            .unwrap();
+
+        let restored_service = ClientService::new(Arc::new(AppState {
+            balance_folder,
+            ..Default::default()
+        }));
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let expected_result = restored_service
+            .restore_from_latest_balance_file(&HashSet::new())
+            .await
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&folder);
+
+        assert_eq!(None, expected_result);
+        assert_eq!(
+            Decimal::ZERO,
+            restored_service
+                .get_client_balance(client_id)
+                .await
+                .unwrap()
+                .balance
+        );
+    }
+
+    /// Create a [ClientService] with the clients named [str] client_names, ids starting at 1
+    fn create_client_service_with_clients(client_names: &[&str]) -> ClientService {
+        let clients = client_names
+            .iter()
+            .enumerate()
+            .map(|(index, client_name)| {
+                let client_id = index as i32 + 1;
+                let client = Client {
+                    client_id,
+                    client_name: client_name.to_string(),
+                    birth_date: NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+                    document_number: format!("DOC-{}", client_id),
+                    country: if client_id % 2 == 0 { "UY" } else { "AR" }.to_string(),
+                    balance: Decimal::new(client_id as i64 * 100, 2),
+                    version: INITIAL_CLIENT_VERSION,
+                    status: ClientStatus::Active,
+                    held_balance: Decimal::ZERO,
+                };
+                (client_id, client)
+            })
+            .collect();
+
+        ClientService::new(Arc::new(AppState {
+            clients: Arc::new(RwLock::new(clients)),
+            client_id_unique: AtomicI32::new(client_names.len() as i32 + 1),
+            ..Default::default()
+        }))
+    }
+
+    /// Scenario:
+    /// Execute list_clients sorted by name, following next_cursor until the last page
+    /// Expectation:
+    /// Every client should be returned once, in name order, and the last page should have no cursor
+    #[tokio::test]
+    #[serial]
+    async fn when_list_clients_sorted_by_name_should_page_through_every_client() {
+        let client_service =
+            create_client_service_with_clients(&["Diego", "Ana", "Carla", "Ana", "Bruno"]);
+
+        let mut client_ids = Vec::new();
+        let mut cursor = None;
+        loop {
+            let client_page = client_service
+                .list_clients(ClientFilter {
+                    sort_by: Some(ClientSortField::Name),
+                    page_size: Some(2),
+                    cursor: cursor.take(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            client_ids.extend(client_page.items.iter().map(|client| client.client_id));
+            match client_page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(vec![2, 4, 5, 3, 1], client_ids);
+    }
+
+    /// Scenario:
+    /// Execute list_clients with country, balance range and name filters
+    /// Expectation:
+    /// Only the matching clients should be returned, ordered by id
+    #[tokio::test]
+    #[serial]
+    async fn when_list_clients_with_filters_should_return_matching_clients() {
+        let client_service =
+            create_client_service_with_clients(&["Ana", "Bruno", "Mariana", "Juana", "Diana"]);
+
+        let expected_result = client_service
+            .list_clients(ClientFilter {
+                country: Some("ar".to_string()),
+                min_balance: Some(Decimal::new(300, 2)),
+                name: Some("ANA".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let client_ids: Vec<i32> = expected_result
+            .items
+            .iter()
+            .map(|client| client.client_id)
+            .collect();
+        assert_eq!(vec![3, 5], client_ids);
+        assert!(expected_result.next_cursor.is_none());
+    }
+
+    /// Scenario:
+    /// Execute list_clients with min_balance greater than max_balance or a cursor of another order
+    /// Expectation:
+    /// A [CommonError::InvalidField] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_list_clients_with_invalid_filter_should_return_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana", "Bruno"]);
+        let id_cursor = client_service
+            .list_clients(ClientFilter {
+                page_size: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .next_cursor;
+
+        let balance_range_result = client_service
+            .list_clients(ClientFilter {
+                min_balance: Some(Decimal::TEN),
+                max_balance: Some(Decimal::ONE),
+                ..Default::default()
+            })
+            .await;
+        let cursor_result = client_service
+            .list_clients(ClientFilter {
+                sort_by: Some(ClientSortField::Name),
+                cursor: id_cursor,
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(
+            balance_range_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "min_balance"
+        ));
+        assert!(matches!(
+            cursor_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "cursor"
+        ));
+    }
+
+    /// Scenario:
+    /// Execute get_client_by_document with a registered and an unknown document number
+    /// Expectation:
+    /// The client info should be returned for the registered one and a [CommonError] for the unknown one
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_by_document_should_return_client_info_or_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana", "Bruno"]);
+
+        let expected_result = client_service
+            .get_client_by_document("DOC-2".to_string())
+            .await
+            .unwrap();
+        let unknown_result = client_service
+            .get_client_by_document("DOC-3".to_string())
+            .await;
+
+        assert_eq!(2, expected_result.client_id);
+        assert_eq!("Bruno", expected_result.client_name);
+        assert_eq!(
+            CommonError::DocumentNotFound("DOC-3".to_string()),
+            unknown_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_client with the current version, then again with the same, now stale, version
+    /// Expectation:
+    /// The first update should change the profile and the version, the second should return a [CommonError]
+    #[tokio::test]
+    #[serial]
+    async fn when_update_client_with_stale_version_should_return_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana", "Bruno"]);
+
+        let expected_result = client_service
+            .update_client(
+                1,
+                INITIAL_CLIENT_VERSION,
+                UpdateClient {
+                    client_name: Some("Ana Maria".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let stale_result = client_service
+            .update_client(
+                1,
+                INITIAL_CLIENT_VERSION,
+                UpdateClient {
+                    country: Some("UY".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert_eq!("Ana Maria", expected_result.client_name);
+        assert_eq!("AR", expected_result.country);
+        assert_eq!(Decimal::new(100, 2), expected_result.balance);
+        assert_eq!(INITIAL_CLIENT_VERSION + 1, expected_result.version);
+        assert_eq!(
+            CommonError::VersionMismatch {
+                client_id: 1,
+                version: INITIAL_CLIENT_VERSION + 1
+            },
+            stale_result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_client with the document number of another client
+    /// Expectation:
+    /// A [CommonError::DocumentAlreadyExists] should be returned and the version should not change
+    #[tokio::test]
+    #[serial]
+    async fn when_update_client_with_existing_document_number_should_return_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana", "Bruno"]);
+
+        let expected_result = client_service
+            .update_client(
+                1,
+                INITIAL_CLIENT_VERSION,
+                UpdateClient {
+                    document_number: Some("DOC-2".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert_eq!(
+            CommonError::DocumentAlreadyExists("DOC-2".to_string()),
+            expected_result.unwrap_err()
+        );
+        assert_eq!(
+            INITIAL_CLIENT_VERSION,
+            client_service.get_client_balance(1).await.unwrap().version
+        );
+    }
+
+    /// Scenario:
+    /// Execute update_client with the event log enabled, then replay the event log on an empty AppState
+    /// Expectation:
+    /// The updated profile and version should be restored
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_with_client_updated_should_restore_the_profile() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service
+            .update_client(
+                client_id,
+                INITIAL_CLIENT_VERSION,
+                UpdateClient {
+                    document_number: Some("some-new-document-number".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let restored_service = ClientService::new(Arc::new(AppState::default()));
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let expected_result = restored_service
+            .get_client_by_document("some-new-document-number".to_string())
+            .await
+            .unwrap();
+        assert_eq!(client_id, expected_result.client_id);
+        assert_eq!(INITIAL_CLIENT_VERSION + 1, expected_result.version);
+    }
+
+    /// Scenario:
+    /// Execute block_client, then a credit, then unblock_client and the same credit again
+    /// Expectation:
+    /// The credit on the blocked account should be rejected and accepted once it is unblocked
+    #[tokio::test]
+    #[serial]
+    async fn when_block_client_should_reject_credits_until_unblocked() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+
+        let blocked_client = client_service.block_client(1).await.unwrap();
+        assert_eq!(ClientStatus::Blocked, blocked_client.status);
+
+        let expected_result = client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await;
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::AccountNotActive {
+                client_id: 1,
+                status: ClientStatus::Blocked
+            })
+        ));
+        assert!(matches!(
+            client_service.block_client(1).await,
+            Err(CommonError::InvalidStatusTransition { .. })
+        ));
+
+        let active_client = client_service.unblock_client(1).await.unwrap();
+        assert_eq!(ClientStatus::Active, active_client.status);
+        assert!(client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .is_ok());
+    }
+
+    /// Scenario:
+    /// Execute close_client with balance, then without balance, then unblock_client on the closed account
+    /// Expectation:
+    /// The first closing should be rejected, the second one accepted and the closed account cannot be reactivated
+    #[tokio::test]
+    #[serial]
+    async fn when_close_client_should_require_zero_balance_and_be_final() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+        let close_client = CloseClient {
+            reason_code: ClosureReason::CustomerRequest,
+        };
+
+        let expected_result = client_service.close_client(1, close_client.clone()).await;
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::BalanceNotZero { client_id: 1, .. })
+        ));
+
+        let mut new_debit_transaction = create_new_debit_transaction_stub();
+        new_debit_transaction.debit_amount = Decimal::new(100, 2);
+        client_service
+            .create_new_debit_transaction(new_debit_transaction)
+            .await
+            .unwrap();
+
+        let closed_client = client_service.close_client(1, close_client).await.unwrap();
+        assert_eq!(ClientStatus::Closed, closed_client.status);
+        assert!(matches!(
+            client_service.unblock_client(1).await,
+            Err(CommonError::InvalidStatusTransition {
+                from: ClientStatus::Closed,
+                to: ClientStatus::Active,
+                ..
+            })
+        ));
+        assert!(matches!(
+            client_service
+                .create_new_debit_transaction(create_new_debit_transaction_stub())
+                .await,
+            Err(CommonError::AccountNotActive { .. })
+        ));
+    }
+
+    /// Scenario:
+    /// Execute block_client with the event log enabled, then replay the event log on an empty AppState
+    /// Expectation:
+    /// The account should be restored as blocked
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_with_client_status_changed_should_restore_the_status() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service.block_client(client_id).await.unwrap();
+
+        let restored_service = ClientService::new(Arc::new(AppState::default()));
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let expected_result = restored_service
+            .get_client_balance(client_id)
+            .await
+            .unwrap();
+        assert_eq!(ClientStatus::Blocked, expected_result.status);
+    }
+
+    /// Scenario:
+    /// Execute create_new_debit_transaction over the default overdraft limit and on a client allowed to go unlimited negative
+    /// Expectation:
+    /// The first debit should be rejected with the available balance, the second one accepted
+    #[tokio::test]
+    #[serial]
+    async fn when_create_new_debit_transaction_over_overdraft_limit_should_return_common_error() {
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::new(5000, 2)),
+                client_limits: HashMap::from([(TO_CLIENT_ID, OverdraftLimit::Unlimited)]),
+            },
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+
+        let mut new_debit_transaction = create_new_debit_transaction_stub();
+        new_debit_transaction.client_id = MOCK_CLIENT_ID;
+        new_debit_transaction.debit_amount = Decimal::new(5001, 2);
+        let expected_result = client_service
+            .create_new_debit_transaction(new_debit_transaction)
+            .await;
+
+        assert_eq!(
+            Err(CommonError::InsufficientFunds {
+                client_id: MOCK_CLIENT_ID,
+                available_balance: Decimal::new(5000, 2),
+                amount: Decimal::new(5001, 2),
+            }),
+            expected_result
+        );
+        assert!(app_state.transactions.read().unwrap().is_empty());
+
+        let mut new_debit_transaction = create_new_debit_transaction_stub();
+        new_debit_transaction.client_id = TO_CLIENT_ID;
+        new_debit_transaction.debit_amount = Decimal::new(100_000, 2);
+        let expected_result = client_service
+            .create_new_debit_transaction(new_debit_transaction)
+            .await;
+
+        assert_eq!(Ok(Decimal::new(-100_000, 2)), expected_result);
+    }
+
+    /// Scenario:
+    /// Execute create_transfer from a prepaid client without balance
+    /// Expectation:
+    /// A [CommonError] should be returned and no balance should change
+    #[tokio::test]
+    #[serial]
+    async fn when_create_transfer_over_overdraft_limit_should_return_common_error() {
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+
+        let expected_result = client_service
+            .create_transfer(create_new_transfer_stub())
+            .await;
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InsufficientFunds {
+                client_id: MOCK_CLIENT_ID,
+                available_balance,
+                ..
+            }) if available_balance.is_zero()
+        ));
+        let clients_map = app_state.clients.read().unwrap();
+        assert!(clients_map.values().all(|client| client.balance.is_zero()));
+    }
+
+    /// Scenario:
+    /// Execute create_hold on a prepaid client, a debit over the available balance and a partial capture_hold
+    /// Expectation:
+    /// The hold should reduce only the available balance, the debit should be rejected
+    /// and the capture should debit the captured amount and release the rest
+    #[tokio::test]
+    #[serial]
+    async fn when_create_hold_and_capture_hold_should_split_available_and_ledger_balances() {
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+
+        let hold_info = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+
+        assert_eq!(HoldStatus::Active, hold_info.status);
+        assert_eq!(Decimal::new(100, 2), hold_info.balance);
+        assert_eq!(Decimal::new(60, 2), hold_info.available_balance);
+
+        let mut new_debit_transaction = create_new_debit_transaction_stub();
+        new_debit_transaction.debit_amount = Decimal::new(61, 2);
+        assert!(matches!(
+            client_service
+                .create_new_debit_transaction(new_debit_transaction)
+                .await,
+            Err(CommonError::InsufficientFunds { available_balance, .. })
+                if available_balance == Decimal::new(60, 2)
+        ));
+
+        let expected_result = client_service
+            .capture_hold(
+                hold_info.hold_id,
+                CaptureHold {
+                    capture_amount: Some(Decimal::new(25, 2)),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(HoldStatus::Captured, expected_result.status);
+        assert_eq!(Decimal::new(25, 2), expected_result.captured_amount);
+        assert_eq!(Decimal::new(75, 2), expected_result.balance);
+        assert_eq!(Decimal::new(75, 2), expected_result.available_balance);
+        let last_transaction = app_state.transactions.read().unwrap()[&MOCK_CLIENT_ID]
+            .last()
+            .cloned()
+            .unwrap();
+        assert_eq!(TransactionKind::Debit, last_transaction.kind);
+        assert_eq!(Decimal::new(25, 2), last_transaction.amount);
+
+        assert_eq!(
+            Err(CommonError::HoldNotActive {
+                hold_id: hold_info.hold_id,
+                status: HoldStatus::Captured
+            }),
+            client_service.release_hold(hold_info.hold_id).await
+        );
+    }
+
+    /// Scenario:
+    /// Execute capture_hold for more than the hold, release_hold and capture_hold on an unknown hold
+    /// Expectation:
+    /// The capture should be rejected, the release should restore the available balance
+    /// and the unknown hold should return a [CommonError]
+    #[tokio::test]
+    #[serial]
+    async fn when_release_hold_should_restore_available_balance() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+        let hold_info = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+
+        let expected_result = client_service
+            .capture_hold(
+                hold_info.hold_id,
+                CaptureHold {
+                    capture_amount: Some(*HOLD_AMOUNT + Decimal::new(1, 2)),
+                },
+            )
+            .await;
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "capture_amount"
+        ));
+
+        let expected_result = client_service
+            .release_hold(hold_info.hold_id)
+            .await
+            .unwrap();
+        assert_eq!(HoldStatus::Released, expected_result.status);
+        assert_eq!(Decimal::new(100, 2), expected_result.available_balance);
+        assert_eq!(Decimal::new(100, 2), expected_result.balance);
 
-    const MOCK_CLIENT_ID: i32 = 1;
+        assert_eq!(
+            Err(CommonError::HoldNotFound(99)),
+            client_service.release_hold(99).await
+        );
+    }
 
     /// Scenario:
-    /// Execute create_new_client when [NewClient] is valid
+    /// Execute close_client on an account with zero balance and an active hold, then release the hold and close it again
     /// Expectation:
-    /// A client id should be returned
+    /// The close should be rejected while the hold is active and accepted once it is released
     #[tokio::test]
     #[serial]
-    async fn when_create_new_client_with_valid_values_should_return_client_id() {
-        let new_client = create_new_client_stub();
-
-        let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(HashMap::new())),
-            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
-        });
-        let client_service = ClientService::new(app_state);
+    async fn when_close_client_with_active_hold_should_return_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+        let mut new_debit_transaction = create_new_debit_transaction_stub();
+        new_debit_transaction.debit_amount = Decimal::new(100, 2);
+        client_service
+            .create_new_debit_transaction(new_debit_transaction)
+            .await
+            .unwrap();
+        let hold_info = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+        let close_client = CloseClient {
+            reason_code: ClosureReason::CustomerRequest,
+        };
 
-        let expected_result = client_service.create_new_client(new_client).await.unwrap();
+        let expected_result = client_service.close_client(1, close_client.clone()).await;
+        assert_eq!(
+            Err(CommonError::HeldBalanceNotZero {
+                client_id: 1,
+                held_balance: *HOLD_AMOUNT,
+            }),
+            expected_result
+        );
 
-        assert_eq!(MOCK_CLIENT_ID, expected_result);
+        client_service
+            .release_hold(hold_info.hold_id)
+            .await
+            .unwrap();
+        let closed_client = client_service.close_client(1, close_client).await.unwrap();
+        assert_eq!(ClientStatus::Closed, closed_client.status);
+        assert_eq!(Decimal::ZERO, closed_client.balance);
     }
 
     /// Scenario:
-    /// Execute create_new_client when [NewClient] is valid but write RwLock failed
+    /// Execute capture_hold on a hold of a blocked account, then unblock the account and capture it again
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// The capture should be rejected without changing the balances while the account is blocked
+    /// and accepted once it is active again
     #[tokio::test]
     #[serial]
-    async fn when_create_new_client_with_valid_values_and_rwlock_failed_should_return_common_error()
-    {
-        let new_client = create_new_client_stub();
+    async fn when_capture_hold_on_blocked_account_should_return_common_error() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+        let hold_info = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+        client_service.block_client(1).await.unwrap();
 
-        let client = Arc::new(RwLock::new(HashMap::<i32, Client>::new()));
+        let expected_result = client_service
+            .capture_hold(hold_info.hold_id, CaptureHold::default())
+            .await;
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::AccountNotActive {
+                client_id: 1,
+                status: ClientStatus::Blocked,
+            })
+        ));
+        let client_info = client_service.get_client_balance(1).await.unwrap();
+        assert_eq!(Decimal::new(100, 2), client_info.balance);
+        assert_eq!(
+            Decimal::new(100, 2) - *HOLD_AMOUNT,
+            client_info.available_balance
+        );
 
-        let clients_ref = Arc::clone(&client);
+        client_service.unblock_client(1).await.unwrap();
+        let expected_result = client_service
+            .capture_hold(hold_info.hold_id, CaptureHold::default())
+            .await
+            .unwrap();
+        assert_eq!(HoldStatus::Captured, expected_result.status);
+        assert_eq!(Decimal::new(100, 2) - *HOLD_AMOUNT, expected_result.balance);
+    }
 
-        {
-            let _ = std::thread::spawn(move || {
-                let _guard = clients_ref.write().unwrap();
-                panic!("error trying write");
-            })
-            .join();
-        }
+    /// Scenario:
+    /// Execute release_expired_holds after the expiry of one hold and capture it afterwards
+    /// Expectation:
+    /// Only the expired hold should be released and it cannot be captured anymore
+    #[tokio::test]
+    #[serial]
+    async fn when_release_expired_holds_should_release_only_expired_holds() {
+        let client_service = create_client_service_with_clients(&["Ana"]);
+        let mut new_hold = create_new_hold_stub();
+        new_hold.expires_in_seconds = Some(60);
+        let expiring_hold = client_service.create_hold(new_hold).await.unwrap();
+        let lasting_hold = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
 
-        let app_state = Arc::new(AppState {
-            clients: client,
-            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
-        });
+        let expected_result = client_service
+            .release_expired_holds(Utc::now() + chrono::Duration::minutes(2))
+            .unwrap();
 
-        let client_service = ClientService::new(app_state);
+        assert_eq!(vec![expiring_hold.hold_id], expected_result);
+        let client_info = client_service.get_client_balance(1).await.unwrap();
+        assert_eq!(
+            Decimal::new(100, 2) - *HOLD_AMOUNT,
+            client_info.available_balance
+        );
+        assert_eq!(
+            Err(CommonError::HoldNotActive {
+                hold_id: expiring_hold.hold_id,
+                status: HoldStatus::Expired
+            }),
+            client_service
+                .capture_hold(expiring_hold.hold_id, CaptureHold::default())
+                .await
+        );
+        assert!(client_service
+            .capture_hold(lasting_hold.hold_id, CaptureHold::default())
+            .await
+            .is_ok());
+    }
 
-        let expected_result = client_service.create_new_client(new_client).await;
+    /// Scenario:
+    /// Execute spawn_hold_expiry_task with a hold that expires in one second
+    /// Expectation:
+    /// The task should release the hold once it expires
+    #[tokio::test]
+    #[serial]
+    async fn when_spawn_hold_expiry_task_should_release_expired_holds() {
+        let client_service = Arc::new(create_client_service_with_clients(&["Ana"]));
+        let mut new_hold = create_new_hold_stub();
+        new_hold.expires_in_seconds = Some(1);
+        let hold_info = client_service.create_hold(new_hold).await.unwrap();
 
-        assert_eq!(CommonError::LockReadFailed, expected_result.unwrap_err());
+        let task = Arc::clone(&client_service).spawn_hold_expiry_task(Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(1300)).await;
+        task.abort();
+
+        let hold = client_service.get_existing_hold(hold_info.hold_id).unwrap();
+        assert_eq!(HoldStatus::Expired, hold.status);
+        let client_info = client_service.get_client_balance(1).await.unwrap();
+        assert_eq!(client_info.balance, client_info.available_balance);
     }
 
     /// Scenario:
-    /// Execute create_new_credit_transaction when [NewCreditTransaction] is valid
+    /// Execute create_hold, capture_hold and release_hold with the event log enabled, then replay it on an empty AppState
     /// Expectation:
-    /// A client id should be returned
+    /// The balances, the holds and the next hold id should be restored
     #[tokio::test]
     #[serial]
-    async fn when_create_new_credit_transaction_with_valid_values_should_return_client_id() {
-        let new_credit = create_new_credit_transaction_stub();
-        let client_stub = create_new_client_stub();
-        let balance = Decimal::new(100, 2);
+    async fn when_restore_from_event_log_with_holds_should_restore_balances_and_holds() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+        let captured_hold = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+        client_service
+            .capture_hold(
+                captured_hold.hold_id,
+                CaptureHold {
+                    capture_amount: Some(Decimal::new(10, 2)),
+                },
+            )
+            .await
+            .unwrap();
+        let active_hold = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
 
-        let client = Client {
-            client_id: new_credit.client_id,
-            client_name: client_stub.client_name,
-            birth_date: client_stub.birth_date,
-            document_number: client_stub.document_number,
-            country: client_stub.country,
-            balance: Decimal::zero(),
-        };
+        let restored_state = Arc::new(AppState::default());
+        let restored_service = ClientService::new(restored_state.clone());
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
 
-        let mut hashmap = HashMap::new();
-        hashmap.insert(new_credit.client_id, client);
+        let expected_result = restored_service
+            .get_client_balance(client_id)
+            .await
+            .unwrap();
+        assert_eq!(Decimal::new(90, 2), expected_result.balance);
+        assert_eq!(
+            Decimal::new(90, 2) - *HOLD_AMOUNT,
+            expected_result.available_balance
+        );
+        assert_eq!(
+            HoldStatus::Captured,
+            restored_service
+                .get_existing_hold(captured_hold.hold_id)
+                .unwrap()
+                .status
+        );
+        assert!(restored_service
+            .release_hold(active_hold.hold_id)
+            .await
+            .is_ok());
+        assert_eq!(
+            active_hold.hold_id + 1,
+            restored_state.hold_id_unique.load(Ordering::SeqCst)
+        );
+    }
 
+    /// Scenario:
+    /// Execute credits on two clients in turns, then reverse_transaction on the second credit of the second client
+    /// Expectation:
+    /// The transaction should be found at its position in the ledger of its client and only it reversed
+    #[tokio::test]
+    #[serial]
+    async fn when_reverse_transaction_with_several_ledgers_should_find_it_by_its_position() {
         let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(new_credit.client_id),
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            ..Default::default()
         });
-
-        let client_service = ClientService::new(app_state);
+        let client_service = ClientService::new(app_state.clone());
+        for client_id in [MOCK_CLIENT_ID, TO_CLIENT_ID, MOCK_CLIENT_ID, TO_CLIENT_ID] {
+            let mut new_credit = create_new_credit_transaction_stub();
+            new_credit.client_id = client_id;
+            client_service
+                .create_new_credit_transaction(new_credit)
+                .await
+                .unwrap();
+        }
+        let credit_id = app_state.transactions.read().unwrap()[&TO_CLIENT_ID][1].transaction_id;
 
         let expected_result = client_service
-            .create_new_credit_transaction(new_credit)
+            .reverse_transaction(credit_id, ReverseTransaction::default())
             .await
             .unwrap();
 
-        assert_eq!(balance, expected_result);
+        assert_eq!(
+            Some(&(TO_CLIENT_ID, 1)),
+            app_state
+                .transaction_positions
+                .read()
+                .unwrap()
+                .get(&credit_id)
+        );
+        assert_eq!(TO_CLIENT_ID, expected_result.client_id);
+        assert_eq!(Some(credit_id), expected_result.reversal_of);
+        let transactions_map = app_state.transactions.read().unwrap();
+        assert!(transactions_map[&TO_CLIENT_ID][0].reversed_amount.is_zero());
+        assert_eq!(
+            transactions_map[&TO_CLIENT_ID][1].amount,
+            transactions_map[&TO_CLIENT_ID][1].reversed_amount
+        );
+        assert!(transactions_map[&MOCK_CLIENT_ID]
+            .iter()
+            .all(|transaction| transaction.reversed_amount.is_zero()));
     }
 
     /// Scenario:
-    /// Execute create_new_credit_transaction when [NewCreditTransaction] is invalid client id
+    /// Execute reverse_transaction on a debit with a partial amount, an amount over the rest, the rest of it and again,
+    /// then on the reversal itself and on an unknown transaction
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// Each reversal should credit a linked entry until the whole debit is refunded,
+    /// then a [CommonError] should be returned for every other request
     #[tokio::test]
     #[serial]
-    async fn when_create_new_credit_transaction_with_invalid_values_should_return_common_error() {
-        let new_credit = create_new_credit_transaction_stub();
-        let client_stub = create_new_client_stub();
-
-        let client = Client {
-            client_id: new_credit.client_id,
-            client_name: client_stub.client_name,
-            birth_date: client_stub.birth_date,
-            document_number: client_stub.document_number,
-            country: client_stub.country,
-            balance: Decimal::zero(),
-        };
-
-        let mut hashmap = HashMap::new();
-        hashmap.insert(new_credit.client_id, client);
-
+    async fn when_reverse_transaction_should_allow_partial_refunds_up_to_the_original_amount() {
         let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(new_credit.client_id),
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            ..Default::default()
         });
+        let client_service = ClientService::new(app_state.clone());
+        client_service
+            .create_new_debit_transaction(create_new_debit_transaction_stub())
+            .await
+            .unwrap();
+        let debit_id = app_state.transactions.read().unwrap()[&MOCK_CLIENT_ID][0].transaction_id;
 
-        {
-            let read_lock = app_state.clients.read().unwrap();
-            assert!(read_lock.contains_key(&new_credit.client_id));
-        }
-        {
-            let mut write_lock = app_state.clients.write().unwrap();
-            write_lock.remove(&new_credit.client_id);
-            write_lock.get(&new_credit.client_id);
-            assert!(write_lock.get(&new_credit.client_id).is_none());
-        }
-
-        let client_service = ClientService::new(app_state);
+        let partial_refund = client_service
+            .reverse_transaction(
+                debit_id,
+                ReverseTransaction {
+                    amount: Some(Decimal::new(40, 2)),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(TransactionKind::Credit, partial_refund.kind);
+        assert_eq!(Some(debit_id), partial_refund.reversal_of);
+        assert_eq!(Decimal::new(-60, 2), partial_refund.balance);
 
         let expected_result = client_service
-            .create_new_credit_transaction(new_credit)
+            .reverse_transaction(
+                debit_id,
+                ReverseTransaction {
+                    amount: Some(Decimal::new(70, 2)),
+                },
+            )
             .await;
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { ref field, .. }) if field == "amount"
+        ));
+
+        let full_refund = client_service
+            .reverse_transaction(debit_id, ReverseTransaction::default())
+            .await
+            .unwrap();
+        assert_eq!(Decimal::new(60, 2), full_refund.amount);
+        assert!(full_refund.balance.is_zero());
+
+        assert_eq!(
+            Err(CommonError::TransactionAlreadyReversed(debit_id)),
+            client_service
+                .reverse_transaction(debit_id, ReverseTransaction::default())
+                .await
+        );
+        assert!(matches!(
+            client_service
+                .reverse_transaction(full_refund.transaction_id, ReverseTransaction::default())
+                .await,
+            Err(CommonError::InvalidField { ref field, .. }) if field == "transaction_id"
+        ));
+        assert_eq!(
+            Err(CommonError::TransactionNotFound(99)),
+            client_service
+                .reverse_transaction(99, ReverseTransaction::default())
+                .await
+        );
 
-        assert_eq!(CommonError::NotFound, expected_result.unwrap_err());
+        let transactions_map = app_state.transactions.read().unwrap();
+        assert_eq!(3, transactions_map[&MOCK_CLIENT_ID].len());
+        assert_eq!(
+            Decimal::new(100, 2),
+            transactions_map[&MOCK_CLIENT_ID][0].reversed_amount
+        );
     }
 
     /// Scenario:
-    /// Execute create_new_debit_transaction when [NewDebitTransaction] is valid
+    /// Execute reverse_transaction on a credit of a prepaid client that already spent the money
     /// Expectation:
-    /// A client id should be returned
+    /// A [CommonError] should be returned and the credit should still be reversible
     #[tokio::test]
     #[serial]
-    async fn when_create_new_debit_transaction_with_valid_values_should_return_client_id() {
-        let new_debit = create_new_debit_transaction_stub();
-        let client_stub = create_new_client_stub();
-        let balance = Decimal::new(100, 2);
-        let updated_balance = Decimal::new(0, 2);
-
-        let client = Client {
-            client_id: new_debit.client_id,
-            client_name: client_stub.client_name,
-            birth_date: client_stub.birth_date,
-            document_number: client_stub.document_number,
-            country: client_stub.country,
-            balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(new_debit.client_id, client);
-
+    async fn when_reverse_transaction_over_overdraft_limit_should_return_common_error() {
         let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(new_debit.client_id),
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
         });
-
-        let client_service = ClientService::new(app_state);
+        let client_service = ClientService::new(app_state.clone());
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+        client_service
+            .create_new_debit_transaction(create_new_debit_transaction_stub())
+            .await
+            .unwrap();
+        let credit_id = app_state.transactions.read().unwrap()[&MOCK_CLIENT_ID][0].transaction_id;
 
         let expected_result = client_service
-            .create_new_debit_transaction(new_debit)
+            .reverse_transaction(credit_id, ReverseTransaction::default())
+            .await;
+
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InsufficientFunds {
+                client_id: MOCK_CLIENT_ID,
+                ..
+            })
+        ));
+        assert!(app_state.transactions.read().unwrap()[&MOCK_CLIENT_ID][0]
+            .reversed_amount
+            .is_zero());
+    }
+
+    /// Scenario:
+    /// Execute a credit and a partial reverse_transaction with the event log enabled, then replay it on an empty AppState
+    /// Expectation:
+    /// The balance and the reversed amount should be restored, so only the rest of the credit can be reversed
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_with_reversals_should_restore_reversed_amount() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+        let reversal = client_service
+            .reverse_transaction(
+                1,
+                ReverseTransaction {
+                    amount: Some(Decimal::new(30, 2)),
+                },
+            )
             .await
             .unwrap();
 
-        assert_eq!(updated_balance, expected_result);
+        let restored_state = Arc::new(AppState::default());
+        let restored_service = ClientService::new(restored_state.clone());
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            Decimal::new(70, 2),
+            restored_service
+                .get_client_balance(client_id)
+                .await
+                .unwrap()
+                .balance
+        );
+        {
+            let transactions_map = restored_state.transactions.read().unwrap();
+            assert_eq!(
+                Decimal::new(30, 2),
+                transactions_map[&client_id][0].reversed_amount
+            );
+            assert_eq!(
+                Some(1),
+                transactions_map[&client_id][reversal.transaction_id as usize - 1].reversal_of
+            );
+        }
+        assert!(restored_service
+            .reverse_transaction(
+                1,
+                ReverseTransaction {
+                    amount: Some(Decimal::new(100, 2)),
+                },
+            )
+            .await
+            .is_err());
+        assert!(restored_service
+            .reverse_transaction(1, ReverseTransaction::default())
+            .await
+            .is_ok());
     }
 
     /// Scenario:
-    /// Execute create_new_debit_transaction when [NewDebitTransaction] is valid but write RwLock failed
+    /// Execute create_transaction_batch in all or nothing mode on a prepaid client,
+    /// with a credit and two debits that together exceed the credited amount
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// The batch should be rejected naming the last item and no balance should change
     #[tokio::test]
     #[serial]
-    async fn when_create_new_debit_transaction_with_valid_values_and_rwlock_failed_should_return_common_error(
-    ) {
-        let new_debit = create_new_debit_transaction_stub();
+    async fn when_create_transaction_batch_all_or_nothing_with_rejected_item_should_apply_nothing()
+    {
+        let app_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let client_service = ClientService::new(app_state.clone());
+        let mut last_debit = create_new_debit_transaction_stub();
+        last_debit.debit_amount = Decimal::new(1, 2);
 
-        let client = Arc::new(RwLock::new(HashMap::<i32, Client>::new()));
+        let expected_result = client_service
+            .create_transaction_batch(NewTransactionBatch {
+                mode: BatchMode::AllOrNothing,
+                items: vec![
+                    BatchTransaction::Credit(create_new_credit_transaction_stub()),
+                    BatchTransaction::Debit(create_new_debit_transaction_stub()),
+                    BatchTransaction::Debit(last_debit),
+                ],
+            })
+            .await;
 
-        let _ = std::panic::catch_unwind(|| {
-            let _readlock = client.read().unwrap();
-            panic!("error trying read");
-        });
+        assert_eq!(
+            Err(CommonError::BatchItemRejected {
+                index: 2,
+                error: Box::new(CommonError::InsufficientFunds {
+                    client_id: MOCK_CLIENT_ID,
+                    available_balance: Decimal::ZERO,
+                    amount: Decimal::new(1, 2),
+                }),
+            }),
+            expected_result
+        );
+        assert!(app_state.transactions.read().unwrap().is_empty());
+        let clients_map = app_state.clients.read().unwrap();
+        assert!(clients_map.values().all(|client| client.balance.is_zero()));
+    }
 
+    /// Scenario:
+    /// Execute create_transaction_batch in best effort mode with valid items, an unknown client,
+    /// a debit over the overdraft limit and an item with an idempotency key
+    /// Expectation:
+    /// The valid items should be applied with their balances and every other item reported as rejected
+    #[tokio::test]
+    #[serial]
+    async fn when_create_transaction_batch_best_effort_should_report_every_item() {
         let app_state = Arc::new(AppState {
-            clients: client,
-            client_id_unique: AtomicI32::new(MOCK_CLIENT_ID),
+            clients: Arc::new(RwLock::new(create_transfer_clients())),
+            client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+            overdraft_limits: OverdraftLimits {
+                default_limit: OverdraftLimit::Limited(Decimal::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
         });
+        let client_service = ClientService::new(app_state.clone());
+        let mut unknown_client_debit = create_new_debit_transaction_stub();
+        unknown_client_debit.client_id = 99;
+        let mut prepaid_debit = create_new_debit_transaction_stub();
+        prepaid_debit.client_id = TO_CLIENT_ID;
+        let mut idempotent_debit = create_new_debit_transaction_stub();
+        idempotent_debit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let mut partial_debit = create_new_debit_transaction_stub();
+        partial_debit.debit_amount = Decimal::new(40, 2);
 
-        let client_service = ClientService::new(app_state);
-
-        let expected_result = client_service.create_new_debit_transaction(new_debit).await;
+        let report = client_service
+            .create_transaction_batch(NewTransactionBatch {
+                mode: BatchMode::BestEffort,
+                items: vec![
+                    BatchTransaction::Credit(create_new_credit_transaction_stub()),
+                    BatchTransaction::Debit(unknown_client_debit),
+                    BatchTransaction::Debit(prepaid_debit),
+                    BatchTransaction::Debit(idempotent_debit),
+                    BatchTransaction::Debit(partial_debit),
+                ],
+            })
+            .await
+            .unwrap();
 
-        assert_eq!(CommonError::NotFound, expected_result.unwrap_err());
+        assert_eq!(2, report.applied_items);
+        assert_eq!(3, report.rejected_items);
+        let statuses: Vec<_> = report
+            .items
+            .iter()
+            .map(|result| (result.status, result.error_code.as_deref()))
+            .collect();
+        assert_eq!(
+            vec![
+                (BatchItemStatus::Applied, None),
+                (BatchItemStatus::Rejected, Some("CLIENT_NOT_FOUND")),
+                (BatchItemStatus::Rejected, Some("INSUFFICIENT_FUNDS")),
+                (BatchItemStatus::Rejected, Some("INVALID_FIELD")),
+                (BatchItemStatus::Applied, None),
+            ],
+            statuses
+        );
+        assert_eq!(Some(Decimal::new(100, 2)), report.items[0].balance);
+        assert_eq!(Some(Decimal::new(60, 2)), report.items[4].balance);
+        assert_eq!(
+            2,
+            app_state.transactions.read().unwrap()[&MOCK_CLIENT_ID].len()
+        );
     }
 
     /// Scenario:
-    /// Execute get_client_balance when [client_id] is valid
+    /// Execute create_transaction_batch with the event log enabled, then replay it on an empty AppState
     /// Expectation:
-    /// A [ClientInfo] should be returned
+    /// The balances and the ledger of the applied items should be restored
     #[tokio::test]
     #[serial]
-    async fn when_get_client_balance_with_valid_values_and_rwlock_failed_should_return_common_error(
-    ) {
-        let client = create_client_info_stub();
-        let result_client_info = create_client_info_stub();
-        let client_id = client.client_id;
+    async fn when_restore_from_event_log_with_transaction_batch_should_restore_the_balances() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let client_service = ClientService::new(Arc::new(AppState {
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let mut debit_transaction = create_new_debit_transaction_stub();
+        debit_transaction.debit_amount = Decimal::new(30, 2);
+        client_service
+            .create_transaction_batch(NewTransactionBatch {
+                mode: BatchMode::AllOrNothing,
+                items: vec![
+                    BatchTransaction::Credit(create_new_credit_transaction_stub()),
+                    BatchTransaction::Debit(debit_transaction),
+                ],
+            })
+            .await
+            .unwrap();
 
-        let client = Client {
-            client_id: client.client_id,
-            client_name: client.client_name,
-            birth_date: client.birth_date,
-            document_number: client.document_number,
-            country: client.country,
-            balance: client.balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(client.client_id, client);
+        let restored_state = Arc::new(AppState::default());
+        let restored_service = ClientService::new(restored_state.clone());
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
 
-        let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(client_id),
-        });
+        assert_eq!(
+            Decimal::new(70, 2),
+            restored_service
+                .get_client_balance(client_id)
+                .await
+                .unwrap()
+                .balance
+        );
+        assert_eq!(
+            2,
+            restored_state.transactions.read().unwrap()[&client_id].len()
+        );
+    }
 
-        let client_service = ClientService::new(app_state);
+    /// Scenario:
+    /// Execute import_clients with a CSV file holding a new client, a document repeated in the file,
+    /// an already registered document, a bad birth date and a birth date in the future
+    /// Expectation:
+    /// Only the new client should be created and every other row reported with its line and reason
+    #[tokio::test]
+    #[serial]
+    async fn when_import_clients_should_create_valid_rows_and_report_rejected_rows() {
+        let app_state = Arc::new(AppState::default());
+        let client_service = ClientService::new(app_state.clone());
+        let existing_client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let content = format!(
+            "client_name,birth_date,document_number,country\n\
+             Jane Doe,1990-05-01,123,AR\n\
+             Jane Copy,1990-05-01,123,AR\n\
+             John Doe,1985-01-31,{CLIENT_DOCUMENT_NUMBER},AR\n\
+             Jim Doe,31/01/1985,456,AR\n\
+             Baby Doe,2999-01-01,789,AR\n"
+        );
 
-        let expected_result = client_service.get_client_balance(client_id).await.unwrap();
+        let report = client_service
+            .import_clients(content, ClientImportFormat::Csv)
+            .await
+            .unwrap();
 
-        assert_eq!(result_client_info, expected_result);
+        assert_eq!(1, report.created_count);
+        assert_eq!(
+            ImportedClient {
+                line: 2,
+                client_id: existing_client_id + 1,
+                document_number: "123".to_string(),
+            },
+            report.created[0]
+        );
+        assert_eq!(4, report.rejected_count);
+        let rejected: Vec<_> = report
+            .rejected
+            .iter()
+            .map(|row| (row.line, row.error_code.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (3, "INVALID_FIELD"),
+                (4, "DOCUMENT_ALREADY_EXISTS"),
+                (5, "INVALID_FIELD"),
+                (6, "INVALID_FIELD"),
+            ],
+            rejected
+        );
+        assert!(report.rejected[0].reason.contains("line 2"));
+        assert!(report.rejected[2].reason.contains("birth_date"));
+        assert_eq!(2, app_state.clients.read().unwrap().len());
     }
+
     /// Scenario:
-    /// Execute get_client_balance when [client_id] is invalid by client id
+    /// Execute get_client_statement for the two middle days of a ledger with one credit per day
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// The opening balance should be the balance before the first day and only the movements of the period returned
     #[tokio::test]
     #[serial]
-    async fn when_get_client_balance_with_invalid_client_id_and_rwlock_failed_should_return_common_error(
-    ) {
-        let client = create_client_info_stub();
-        let client_id = client.client_id;
-
-        let client = Client {
-            client_id: client.client_id,
-            client_name: client.client_name,
-            birth_date: client.birth_date,
-            document_number: client.document_number,
-            country: client.country,
-            balance: client.balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(client.client_id, client);
-
-        let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(client_id),
-        });
+    async fn when_get_client_statement_should_return_balances_and_movements_of_the_period() {
+        let client_service = ClientService::new(Arc::new(AppState::default()));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
 
-        let client_service = ClientService::new(app_state);
+        // one credit per day, from 2025-07-01 to 2025-07-04
+        let transactions = (1..=4)
+            .map(|day| Transaction {
+                transaction_id: day,
+                client_id,
+                kind: TransactionKind::Credit,
+                amount: Decimal::new(100, 2),
+                balance: Decimal::new(100 * day as i64, 2),
+                created_at: NaiveDate::from_ymd_opt(2025, 7, day as u32)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+                    .and_utc(),
+                reversal_of: None,
+                reversed_amount: Decimal::ZERO,
+            })
+            .collect::<Vec<_>>();
+        client_service
+            .app_state
+            .transactions
+            .write()
+            .unwrap()
+            .insert(client_id, transactions);
 
-        let expected_result = client_service.get_client_balance(3).await;
+        let expected_result = client_service
+            .get_client_statement(
+                client_id,
+                NaiveDate::from_ymd_opt(2025, 7, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 7, 3).unwrap(),
+            )
+            .await
+            .unwrap();
 
-        assert_eq!(CommonError::NotFound, expected_result.unwrap_err());
+        assert_eq!(client_id, expected_result.client.client_id);
+        assert_eq!(Decimal::new(100, 2), expected_result.opening_balance);
+        assert_eq!(Decimal::new(300, 2), expected_result.closing_balance);
+        assert_eq!(
+            vec![2, 3],
+            expected_result
+                .movements
+                .iter()
+                .map(|movement| movement.transaction_id)
+                .collect::<Vec<_>>()
+        );
     }
+
     /// Scenario:
-    /// Execute generate_file_with_all_clients_balances with exit
+    /// Execute get_client_statement after a credit, a balances reset and a debit
     /// Expectation:
-    /// A [Ok()] should be returned
+    /// The reset should be a settlement line taking the stored balance out, which cannot be reversed,
+    /// and the movements should add up to the closing balance
     #[tokio::test]
     #[serial]
-    async fn when_generate_file_with_all_clients_balances_with_exit_should_return_ok() {
-        let client = create_client_info_stub();
-        let client_id = client.client_id;
-
-        let client = Client {
-            client_id: client.client_id,
-            client_name: client.client_name,
-            birth_date: client.birth_date,
-            document_number: client.document_number,
-            country: client.country,
-            balance: client.balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(client.client_id, client);
+    async fn when_get_client_statement_after_balances_reset_should_show_a_settlement_line() {
+        let client_service = ClientService::new(Arc::new(AppState::default()));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let credit = create_new_credit_transaction_stub();
+        let credit_amount = credit.credit_amount;
+        client_service
+            .create_new_credit_transaction(credit)
+            .await
+            .unwrap();
+        let (balances, ledger_cut) = client_service.snapshot_balances().unwrap();
+        client_service
+            .reset_balances(balances, "01072025_1.DAT".to_string(), ledger_cut)
+            .unwrap();
+        let debit_amount = Decimal::new(10, 2);
+        let closing_balance = client_service
+            .create_new_debit_transaction(NewDebitTransaction {
+                client_id,
+                debit_amount,
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
 
-        let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(client_id),
-        });
+        let today = Utc::now().date_naive();
+        let expected_result = client_service
+            .get_client_statement(client_id, today, today)
+            .await
+            .unwrap();
 
-        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+        let movements = &expected_result.movements;
+        assert_eq!(3, movements.len());
+        assert_eq!(TransactionKind::Credit, movements[0].kind);
+        assert_eq!(credit_amount, movements[0].amount);
+        assert_eq!(TransactionKind::Settlement, movements[1].kind);
+        assert_eq!(-credit_amount, movements[1].amount);
+        assert_eq!(Decimal::ZERO, movements[1].balance);
+        assert_eq!(TransactionKind::Debit, movements[2].kind);
+        assert_eq!(-debit_amount, movements[2].amount);
+        assert_eq!(Decimal::ZERO, expected_result.opening_balance);
+        assert_eq!(closing_balance, expected_result.closing_balance);
+        assert_eq!(
+            expected_result.closing_balance,
+            expected_result.opening_balance
+                + movements
+                    .iter()
+                    .map(|movement| movement.amount)
+                    .sum::<Decimal>()
+        );
+        assert!(matches!(
+            client_service
+                .reverse_transaction(movements[1].transaction_id, ReverseTransaction::default())
+                .await,
+            Err(CommonError::InvalidField { field, .. }) if field == "transaction_id"
+        ));
+    }
 
-        let client_service = ClientService::new(app_state.clone());
+    /// Scenario:
+    /// Execute get_client_statement with the first day after the last day
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[tokio::test]
+    #[serial]
+    async fn when_get_client_statement_with_from_after_to_should_return_common_error() {
+        let client_service = ClientService::new(Arc::new(AppState::default()));
 
         let expected_result = client_service
-            .generate_file_with_all_clients_balances()
+            .get_client_statement(
+                MOCK_CLIENT_ID,
+                NaiveDate::from_ymd_opt(2025, 7, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            )
             .await;
-        assert!(expected_result.is_ok());
 
-        let folder = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
+        assert!(matches!(
+            expected_result,
+            Err(CommonError::InvalidField { field, .. }) if field == "from"
+        ));
+    }
+
+    /// Read the names of the balance files in [str] balance_folder, oldest first
+    fn read_balance_file_names(balance_folder: &str) -> Vec<String> {
+        let mut file_names: Vec<String> = std::fs::read_dir(balance_folder)
             .unwrap()
             .filter_map(|file| file.ok())
-            .collect::<Vec<_>>();
-        assert!(!folder.is_empty());
-
-        let read_lock = app_state.clients.read().unwrap();
-        let client = read_lock.get(&client_id).unwrap();
-        assert_eq!(client.balance, Decimal::zero());
+            .map(|file| file.file_name().to_string_lossy().to_string())
+            .collect();
+        file_names
+            .sort_by_key(|file_name| BalanceFileNamePattern::default().parse_file_name(file_name));
+        file_names
     }
+
     /// Scenario:
-    /// Execute generate_file_with_all_clients_balances and create folder failed
+    /// Execute generate_file_with_all_clients_balances twice with credits and debits before each file,
+    /// then reconcile both files, a changed copy of the last one, an unknown file and an invalid file name
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// Both files should match the movements, the changed balance should be reported
+    /// and a [CommonError] should be returned for the unknown and the invalid file
     #[tokio::test]
     #[serial]
-    async fn when_generate_file_with_all_clients_balances_and_create_folder_failed_should_return_common_error(
-    ) {
-        let client = create_client_info_stub();
-        let client_id = client.client_id;
-
-        let _ = std::fs::remove_file(CLIENT_BALANCE_FOLDER);
-        let _ = std::fs::remove_dir_all(CLIENT_BALANCE_FOLDER);
-
-        File::create(CLIENT_BALANCE_FOLDER).expect("Cannot create file");
+    async fn when_reconcile_balance_file_after_store_balance_should_report_only_changed_balances() {
+        let balance_folder = std::env::temp_dir()
+            .join(format!("prex-{}", uuid::Uuid::new_v4()))
+            .display()
+            .to_string();
+        let client_service = ClientService::new(Arc::new(AppState {
+            balance_folder: balance_folder.clone(),
+            ..Default::default()
+        }));
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+        client_service
+            .generate_file_with_all_clients_balances()
+            .await
+            .unwrap();
+        client_service
+            .create_new_debit_transaction(NewDebitTransaction {
+                client_id,
+                debit_amount: Decimal::new(25, 2),
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
+        client_service
+            .generate_file_with_all_clients_balances()
+            .await
+            .unwrap();
 
-        let client = Client {
-            client_id: client.client_id,
-            client_name: client.client_name,
-            birth_date: client.birth_date,
-            document_number: client.document_number,
-            country: client.country,
-            balance: client.balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(client.client_id, client);
+        let file_names = read_balance_file_names(&balance_folder);
+        assert_eq!(2, file_names.len());
+        for file_name in &file_names {
+            let report = client_service
+                .reconcile_balance_file(file_name.clone())
+                .await
+                .unwrap();
+            assert_eq!(1, report.checked_clients);
+            assert!(report.is_balanced());
+        }
 
-        let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(client_id),
-        });
+        let changed_content = render_balance_file(
+            &[(client_id, Decimal::new(-20, 2))],
+            BalanceFileFormat::Control,
+            Utc::now(),
+        );
+        let expected_result = client_service
+            .reconcile_balance_content(&file_names[1], &changed_content)
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&balance_folder);
 
-        let client_service = ClientService::new(app_state.clone());
+        assert_eq!(
+            vec![BalanceMismatch {
+                client_id,
+                file_balance: Some(Decimal::new(-20, 2)),
+                expected_balance: Decimal::new(-25, 2),
+                difference: Decimal::new(5, 2),
+            }],
+            expected_result.mismatches
+        );
+        assert_eq!(
+            CommonError::BalanceFileNotFound("01012020_1.DAT".to_string()),
+            client_service
+                .reconcile_balance_content("01012020_1.DAT", &changed_content)
+                .unwrap_err()
+        );
+        assert!(matches!(
+            client_service.reconcile_balance_file("../events.jsonl".to_string()).await,
+            Err(CommonError::InvalidField { field, .. }) if field == "file_name"
+        ));
+    }
 
-        let expected_result = client_service
+    /// Scenario:
+    /// Execute generate_file_with_all_clients_balances with the event log enabled, then replay it on an empty AppState
+    /// Expectation:
+    /// The balance file should still be reconciled against the replayed movements
+    #[tokio::test]
+    #[serial]
+    async fn when_restore_from_event_log_after_store_balance_should_reconcile_the_balance_file() {
+        let folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+        let balance_folder = folder.join("store_balances").display().to_string();
+        let path = folder.join("events.jsonl");
+        let client_service = ClientService::new(Arc::new(AppState {
+            balance_folder: balance_folder.clone(),
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
+        }));
+        client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        client_service
+            .create_new_credit_transaction(create_new_credit_transaction_stub())
+            .await
+            .unwrap();
+        client_service
             .generate_file_with_all_clients_balances()
-            .await;
+            .await
+            .unwrap();
 
-        assert_eq!(CommonError::FolderReadFailed, expected_result.unwrap_err());
+        let restored_service = ClientService::new(Arc::new(AppState {
+            balance_folder: balance_folder.clone(),
+            ..Default::default()
+        }));
+        restored_service
+            .restore_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let file_name = read_balance_file_names(&balance_folder).remove(0);
+        let expected_result = restored_service
+            .reconcile_balance_file(file_name.clone())
+            .await
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&folder);
 
-        let _ = std::fs::remove_file(CLIENT_BALANCE_FOLDER);
+        assert_eq!(file_name, expected_result.file_name);
+        assert_eq!(1, expected_result.checked_clients);
+        assert!(expected_result.is_balanced());
     }
 
     /// Scenario:
-    /// Execute generate_file_with_all_clients_balances and failed when try write in lock
+    /// Execute a credit, a capture and a hold with the event log enabled,
+    /// then replay the event log on a repository that already keeps the clients
     /// Expectation:
-    /// A [CommonError] should be returned
+    /// The balances of the repository should not change, and the ledger, the holds, the idempotency keys
+    /// and the ids should be restored
     #[tokio::test]
     #[serial]
-    async fn when_generate_file_with_all_clients_balances_and_failed_try_write_in_lock_should_return_common_error(
-    ) {
-        let client = create_client_info_stub();
-        let client_id = client.client_id;
-
-        let client = Client {
-            client_id: client.client_id,
-            client_name: client.client_name,
-            birth_date: client.birth_date,
-            document_number: client.document_number,
-            country: client.country,
-            balance: client.balance,
-        };
-        let mut hashmap = HashMap::new();
-        hashmap.insert(client.client_id, client);
-
-        let _ = tokio::fs::remove_dir_all(CLIENT_BALANCE_FOLDER).await;
+    async fn when_restore_ledger_from_event_log_should_keep_the_repository_clients() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
         let app_state = Arc::new(AppState {
-            clients: Arc::new(RwLock::new(hashmap)),
-            client_id_unique: AtomicI32::new(client_id),
+            event_log: Some(EventLog::open(&path).unwrap()),
+            ..Default::default()
         });
-        let app_state_clone = app_state.clone();
-        let _ = std::thread::spawn(move || {
-            let _write_lock = app_state_clone.clients.write().unwrap();
-            panic!("error");
-        })
-        .join();
-
         let client_service = ClientService::new(app_state.clone());
+        let client_id = client_service
+            .create_new_client(create_new_client_stub())
+            .await
+            .unwrap();
+        let mut new_credit = create_new_credit_transaction_stub();
+        new_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        let credit_balance = client_service
+            .create_new_credit_transaction(new_credit)
+            .await
+            .unwrap();
+        let captured_hold = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+        client_service
+            .capture_hold(captured_hold.hold_id, CaptureHold::default())
+            .await
+            .unwrap();
+        let active_hold = client_service
+            .create_hold(create_new_hold_stub())
+            .await
+            .unwrap();
+        let saved_clients = app_state.clients.read().unwrap().clone();
 
-        let expected_result = client_service
-            .generate_file_with_all_clients_balances()
-            .await;
-
-        let folder = std::fs::read_dir(CLIENT_BALANCE_FOLDER)
-            .unwrap()
-            .filter_map(|file| file.ok())
-            .collect::<Vec<_>>();
-        assert!(folder.is_empty());
+        let restored_state = Arc::new(AppState {
+            clients: Arc::new(RwLock::new(saved_clients.clone())),
+            ..Default::default()
+        });
+        let restored_service = ClientService::new(restored_state.clone());
+        let expected_result = restored_service
+            .restore_ledger_from_event_log(EventLog::read_all(&path).unwrap())
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
 
-        assert_eq!(CommonError::LockReadFailed, expected_result.unwrap_err());
+        let saved_client = &saved_clients[&client_id];
+        let restored_client = restored_service.get_existing_client(client_id).unwrap();
+        assert_eq!(saved_client.balance, restored_client.balance);
+        assert_eq!(saved_client.held_balance, restored_client.held_balance);
+        assert_eq!(1, expected_result.len());
+        assert_eq!(saved_client.balance, expected_result[0].balance);
+        assert_eq!(saved_client.held_balance, expected_result[0].held_balance);
+        assert_eq!(
+            app_state.transaction_id_unique.load(Ordering::SeqCst),
+            restored_state.transaction_id_unique.load(Ordering::SeqCst)
+        );
+        assert_eq!(
+            client_id + 1,
+            restored_state.client_id_unique.load(Ordering::SeqCst)
+        );
+        let transactions = restored_service
+            .get_client_transactions(client_id, TransactionFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(2, transactions.total_items);
+        assert_eq!(
+            HoldStatus::Active,
+            restored_service
+                .get_existing_hold(active_hold.hold_id)
+                .unwrap()
+                .status
+        );
+        assert_eq!(
+            active_hold.hold_id + 1,
+            restored_state.hold_id_unique.load(Ordering::SeqCst)
+        );
+        let mut retried_credit = create_new_credit_transaction_stub();
+        retried_credit.idempotency_key = Some(IDEMPOTENCY_KEY.to_string());
+        assert_eq!(
+            credit_balance,
+            restored_service
+                .create_new_credit_transaction(retried_credit)
+                .await
+                .unwrap()
+        );
     }
 }
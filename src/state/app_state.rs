@@ -1,7 +1,16 @@
+use crate::balance_file::balance_file_format::BalanceFileFormat;
+use crate::balance_file::balance_file_name_pattern::BalanceFileNamePattern;
+use crate::constants::constants::CLIENT_BALANCE_FOLDER;
 use crate::model::client_model::Client;
+use crate::model::hold_model::Hold;
+use crate::model::overdraft_model::OverdraftLimits;
+use crate::model::transaction_model::Transaction;
+use crate::state::event_log::EventLog;
+use crate::state::idempotency_store::IdempotencyStore;
+use crate::validator::transaction_validator::TransactionLimits;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicI32;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 /// AppState for save clients data
 pub struct AppState {
@@ -9,4 +18,59 @@ pub struct AppState {
     pub clients: Arc<RwLock<HashMap<i32, Client>>>,
     // client id unique
     pub client_id_unique: AtomicI32,
+    // transactions ledger grouped by client id
+    pub transactions: Arc<RwLock<HashMap<i32, Vec<Transaction>>>>,
+    // transaction id unique
+    pub transaction_id_unique: AtomicI32,
+    // (client id, index) of each transaction in the ledger by transaction id, changed under the ledger lock
+    pub transaction_positions: Arc<RwLock<HashMap<i32, (i32, usize)>>>,
+    // holds by hold id, released and captured ones included
+    pub holds: Arc<RwLock<HashMap<i32, Hold>>>,
+    // hold id unique
+    pub hold_id_unique: AtomicI32,
+    // idempotency keys of credit and debit transactions
+    pub idempotency_store: IdempotencyStore,
+    // limits applied to every transaction amount
+    pub transaction_limits: TransactionLimits,
+    // how far below zero each balance can go
+    pub overdraft_limits: OverdraftLimits,
+    // only one client is registered at a time, so the document check and the insert are one step
+    pub client_registration_lock: Mutex<()>,
+    // write-ahead log of every change, disabled when None
+    pub event_log: Option<EventLog>,
+    // only one balance file is generated at a time
+    pub balance_file_lock: tokio::sync::Mutex<()>,
+    // layout of the balance files
+    pub balance_file_format: BalanceFileFormat,
+    // folder for save the client's balances
+    pub balance_folder: String,
+    // name of the balance files
+    pub balance_file_name_pattern: BalanceFileNamePattern,
+    // first transaction id left out of each balance file written, by file name
+    pub balance_file_ledger_cuts: Arc<RwLock<HashMap<String, i32>>>,
+}
+
+/// Empty AppState without event log, ids start at 1
+impl Default for AppState {
+    fn default() -> Self {
+        AppState {
+            clients: Arc::new(RwLock::new(HashMap::new())),
+            client_id_unique: AtomicI32::new(1),
+            transactions: Arc::new(RwLock::new(HashMap::new())),
+            transaction_id_unique: AtomicI32::new(1),
+            transaction_positions: Arc::new(RwLock::new(HashMap::new())),
+            holds: Arc::new(RwLock::new(HashMap::new())),
+            hold_id_unique: AtomicI32::new(1),
+            idempotency_store: IdempotencyStore::default(),
+            transaction_limits: TransactionLimits::default(),
+            overdraft_limits: OverdraftLimits::default(),
+            client_registration_lock: Mutex::new(()),
+            event_log: None,
+            balance_file_lock: tokio::sync::Mutex::new(()),
+            balance_file_format: BalanceFileFormat::default(),
+            balance_folder: CLIENT_BALANCE_FOLDER.to_string(),
+            balance_file_name_pattern: BalanceFileNamePattern::default(),
+            balance_file_ledger_cuts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
 }
@@ -0,0 +1,261 @@
+use crate::errors::common_error::CommonError;
+use crate::model::event_model::EventRecord;
+use log::{error, warn};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Append only file with one JSON [EventRecord] per line
+pub struct EventLog {
+    // file path
+    pub path: PathBuf,
+    // file opened in append mode
+    file: Mutex<File>,
+}
+
+/// Event log logic
+impl EventLog {
+    /// Open or create the event log in [Path] path, creating its folder if needed
+    /// An incomplete last line, left by a crash while appending, is cut off so new records start on their own line
+    /// Returns a [CommonError] if the folder or the file cannot be created
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CommonError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(folder) = path
+            .parent()
+            .filter(|folder| !folder.as_os_str().is_empty())
+        {
+            fs::create_dir_all(folder).map_err(|error| {
+                error!("open - error when creating folder error: {:?}", error);
+                CommonError::FolderCreationFailed(folder.display().to_string())
+            })?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|error| {
+                error!("open - error when opening file error: {:?}", error);
+                CommonError::FileCreationFailed(path.display().to_string())
+            })?;
+        Self::truncate_incomplete_line(&mut file).map_err(|error| {
+            error!(
+                "open - error when truncating incomplete line error: {:?}",
+                error
+            );
+            CommonError::FileWriteFailed(path.display().to_string())
+        })?;
+
+        Ok(EventLog {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Cut [File] file back to the end of its last complete line
+    fn truncate_incomplete_line(file: &mut File) -> std::io::Result<()> {
+        let length = file.metadata()?.len();
+        let mut end = length;
+        let mut buffer = [0u8; 4096];
+        // search the last new line backwards, one block at a time
+        while end > 0 {
+            let start = end.saturating_sub(buffer.len() as u64);
+            let block = &mut buffer[..(end - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(block)?;
+            if let Some(position) = block.iter().rposition(|byte| *byte == b'\n') {
+                end = start + position as u64 + 1;
+                break;
+            }
+            end = start;
+        }
+        if end < length {
+            warn!(
+                "truncate_incomplete_line - removing {} bytes of an incomplete last line",
+                length - end
+            );
+            file.set_len(end)?;
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Append [EventRecord] record and fsync, so it is on disk once this returns
+    /// Returns a [CommonError] if the record cannot be written
+    pub fn append(&self, record: &EventRecord) -> Result<(), CommonError> {
+        let file_name = self.path.display().to_string();
+        let mut line = serde_json::to_string(record).map_err(|error| {
+            error!(
+                "append - error when serializing the record - error: {:?}",
+                error
+            );
+            CommonError::FileWriteFailed(file_name.clone())
+        })?;
+        line.push('\n');
+
+        let mut file = self.file.lock().map_err(|error| {
+            error!("append - error when locking the file - error: {:?}", error);
+            CommonError::LockWriteFailed
+        })?;
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.sync_data())
+            .map_err(|error| {
+                error!("append - error when writing to the file - file name: {file_name} - error: {:?}", error);
+                CommonError::FileWriteFailed(file_name)
+            })
+    }
+
+    /// Read every [EventRecord] saved in [Path] path, in the order they were appended
+    /// A missing file has no records and an incomplete last line, left by a crash while appending, is ignored
+    /// Returns a [CommonError] if the file cannot be read or any other line is invalid
+    pub fn read_all(path: impl AsRef<Path>) -> Result<Vec<EventRecord>, CommonError> {
+        let path = path.as_ref();
+        let file_name = path.display().to_string();
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => {
+                error!("read_all - error when opening file error: {:?}", error);
+                return Err(CommonError::FileReadFailed(file_name));
+            }
+        };
+
+        let lines = BufReader::new(file)
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|error| {
+                error!("read_all - error when reading file error: {:?}", error);
+                CommonError::FileReadFailed(file_name.clone())
+            })?;
+
+        let last_line = lines.len().saturating_sub(1);
+        let mut records = Vec::with_capacity(lines.len());
+        for (line_number, line) in lines.iter().enumerate() {
+            match serde_json::from_str::<EventRecord>(line) {
+                Ok(record) => records.push(record),
+                Err(error) if line_number == last_line => {
+                    warn!("read_all - ignoring incomplete last line - file name: {file_name} - error: {:?}", error);
+                }
+                Err(error) => {
+                    error!(
+                        "read_all - error - invalid line {} - file name: {file_name} - error: {:?}",
+                        line_number + 1,
+                        error
+                    );
+                    return Err(CommonError::FileReadFailed(file_name));
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::event_model::Event;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    /// Create an [EventRecord] resetting [i32] client_id
+    fn create_record(client_id: i32) -> EventRecord {
+        EventRecord {
+            created_at: Utc::now(),
+            event: Event::BalancesReset {
+                stored_balances: vec![(client_id, Decimal::ONE)],
+                file_name: None,
+                ledger_cut: None,
+            },
+        }
+    }
+
+    /// Scenario:
+    /// Execute append twice and then read_all, with an incomplete line at the end of the file
+    /// Expectation:
+    /// Both records should be returned in order and the incomplete line ignored
+    #[test]
+    fn when_read_all_after_append_should_return_the_records_in_order() {
+        let path = std::env::temp_dir()
+            .join(format!("prex-{}", uuid::Uuid::new_v4()))
+            .join("events.jsonl");
+        let records = vec![create_record(1), create_record(2)];
+        {
+            let event_log = EventLog::open(&path).unwrap();
+            for record in &records {
+                event_log.append(record).unwrap();
+            }
+        }
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"created_at":"2025-"#)
+            .unwrap();
+
+        let expected_result = EventLog::read_all(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(records, expected_result);
+    }
+
+    /// Scenario:
+    /// Execute open on an event log with an incomplete last line, then append and read_all
+    /// Expectation:
+    /// The incomplete line should be cut off and every complete record returned in order
+    #[test]
+    fn when_append_after_incomplete_line_should_return_the_records_in_order() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let records = vec![create_record(1), create_record(2)];
+        EventLog::open(&path).unwrap().append(&records[0]).unwrap();
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"created_at":"2025-"#)
+            .unwrap();
+
+        EventLog::open(&path).unwrap().append(&records[1]).unwrap();
+        let expected_result = EventLog::read_all(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(records, expected_result.unwrap());
+    }
+
+    /// Scenario:
+    /// Execute open on an event log with only an incomplete line
+    /// Expectation:
+    /// The file should be left empty
+    #[test]
+    fn when_open_with_only_incomplete_line_should_leave_the_file_empty() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        fs::write(&path, br#"{"created_at":"2025-"#).unwrap();
+
+        EventLog::open(&path).unwrap();
+        let expected_result = fs::read(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(expected_result.unwrap().is_empty());
+    }
+
+    /// Scenario:
+    /// Execute read_all when a line in the middle of the file is invalid
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[test]
+    fn when_read_all_with_invalid_line_should_return_common_error() {
+        let path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+        let valid_line = serde_json::to_string(&create_record(1)).unwrap();
+        fs::write(&path, format!("not json\n{valid_line}\n")).unwrap();
+
+        let expected_result = EventLog::read_all(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            CommonError::FileReadFailed(path.display().to_string()),
+            expected_result.unwrap_err()
+        );
+        assert!(EventLog::read_all(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::constants::constants::IDEMPOTENCY_KEY_TTL_SECONDS;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Response saved for an idempotency key
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    // kind, client id and amount of the first request that used the key
+    pub fingerprint: String,
+    // balance returned to the first request
+    pub balance: Decimal,
+    // when the key was saved
+    pub created_at: Instant,
+}
+
+/// Idempotency keys received on credit and debit transactions
+pub struct IdempotencyStore {
+    // records by idempotency key
+    pub records: Mutex<HashMap<String, IdempotencyRecord>>,
+    // how long a key is remembered
+    pub ttl: Duration,
+}
+
+/// Idempotency store logic
+impl IdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        IdempotencyStore {
+            records: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Remove from [HashMap] records the keys older than the store ttl
+    pub fn remove_expired_records(&self, records: &mut HashMap<String, IdempotencyRecord>) {
+        let now = Instant::now();
+        records.retain(|_, record| now.duration_since(record.created_at) < self.ttl);
+    }
+
+    /// Build the [IdempotencyRecord] of a key saved [Duration] age ago with [String] fingerprint and [Decimal] balance,
+    /// as replayed from the event log, [None] if the key is already older than the store ttl
+    pub fn restore_record(
+        &self,
+        fingerprint: String,
+        balance: Decimal,
+        age: Duration,
+    ) -> Option<IdempotencyRecord> {
+        if age >= self.ttl {
+            return None;
+        }
+        let now = Instant::now();
+        Some(IdempotencyRecord {
+            fingerprint,
+            balance,
+            // the monotonic clock may not reach that far back, then the key is kept for a whole ttl
+            created_at: now.checked_sub(age).unwrap_or(now),
+        })
+    }
+}
+
+/// Keys are remembered for [IDEMPOTENCY_KEY_TTL_SECONDS]
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        IdempotencyStore::new(Duration::from_secs(IDEMPOTENCY_KEY_TTL_SECONDS))
+    }
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDEMPOTENCY_KEY: &str = "some-idempotency-key";
+
+    /// Scenario:
+    /// Execute remove_expired_records when a record is older than the ttl
+    /// Expectation:
+    /// The record should be removed
+    #[test]
+    fn when_remove_expired_records_with_expired_record_should_remove_it() {
+        let store = IdempotencyStore::new(Duration::ZERO);
+        let mut records = HashMap::new();
+        records.insert(
+            IDEMPOTENCY_KEY.to_string(),
+            IdempotencyRecord {
+                fingerprint: String::new(),
+                balance: Decimal::new(100, 2),
+                created_at: Instant::now(),
+            },
+        );
+
+        store.remove_expired_records(&mut records);
+
+        assert!(records.is_empty());
+    }
+
+    /// Scenario:
+    /// Execute remove_expired_records when a record is younger than the ttl
+    /// Expectation:
+    /// The record should be kept
+    #[test]
+    fn when_remove_expired_records_with_valid_record_should_keep_it() {
+        let store = IdempotencyStore::default();
+        let mut records = HashMap::new();
+        records.insert(
+            IDEMPOTENCY_KEY.to_string(),
+            IdempotencyRecord {
+                fingerprint: String::new(),
+                balance: Decimal::new(100, 2),
+                created_at: Instant::now(),
+            },
+        );
+
+        store.remove_expired_records(&mut records);
+
+        assert!(records.contains_key(IDEMPOTENCY_KEY));
+    }
+
+    /// Scenario:
+    /// Execute restore_record with an age younger and older than the ttl
+    /// Expectation:
+    /// Only the younger one should be restored, dated back by its age
+    #[test]
+    fn when_restore_record_should_only_restore_keys_younger_than_the_ttl() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+
+        let expected_result = store
+            .restore_record(String::new(), Decimal::new(100, 2), Duration::from_secs(30))
+            .unwrap();
+        let expired = store.restore_record(String::new(), Decimal::ONE, Duration::from_secs(60));
+
+        assert_eq!(Decimal::new(100, 2), expected_result.balance);
+        assert!(expected_result.created_at.elapsed() >= Duration::from_secs(30));
+        assert!(expired.is_none());
+    }
+}
@@ -1 +1,3 @@
 pub mod app_state;
+pub mod event_log;
+pub mod idempotency_store;
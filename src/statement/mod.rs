@@ -0,0 +1 @@
+pub mod statement_format;
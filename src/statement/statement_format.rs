@@ -0,0 +1,203 @@
+use crate::dto::client_statement_dto::ClientStatement;
+use crate::errors::common_error::CommonError;
+use chrono::NaiveDate;
+use csv::Writer;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Layout of the client statements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum StatementFormat {
+    // the statement as a JSON object
+    #[default]
+    Json,
+    // the client and the period on top, then one movement per line
+    Csv,
+}
+
+/// Implementation Statement format
+impl StatementFormat {
+    /// Name of the file with the statement of [ClientStatement] statement in this format
+    pub fn file_name(&self, statement: &ClientStatement) -> String {
+        let extension = match self {
+            StatementFormat::Json => "json",
+            StatementFormat::Csv => "csv",
+        };
+        format!(
+            "statement_{}_{}_{}.{extension}",
+            statement.client.client_id, statement.from, statement.to
+        )
+    }
+}
+
+/// Statement format from its name, `json` or `csv`
+impl std::str::FromStr for StatementFormat {
+    type Err = CommonError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "json" => Ok(StatementFormat::Json),
+            "csv" => Ok(StatementFormat::Csv),
+            _ => Err(CommonError::InvalidField {
+                field: "format".to_string(),
+                reason: "must be json or csv".to_string(),
+            }),
+        }
+    }
+}
+
+/// Read a [StatementFormat] from a query param
+impl TryFrom<String> for StatementFormat {
+    type Error = CommonError;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        format.parse()
+    }
+}
+
+/// Header of a CSV statement, the client data and the balances of the period
+#[derive(Serialize)]
+struct StatementHeaderRecord<'a> {
+    client_id: i32,
+    client_name: &'a str,
+    birth_date: NaiveDate,
+    document_number: &'a str,
+    country: &'a str,
+    from: NaiveDate,
+    to: NaiveDate,
+    opening_balance: Decimal,
+    closing_balance: Decimal,
+}
+
+/// Render [ClientStatement] statement as CSV, a header table with the client and the balances,
+/// a blank line, and a table with the movements
+/// Returns a [CommonError] if a record cannot be written
+pub fn render_statement_csv(statement: &ClientStatement) -> Result<String, CommonError> {
+    let client = &statement.client;
+    let header = write_csv(&[StatementHeaderRecord {
+        client_id: client.client_id,
+        client_name: &client.client_name,
+        birth_date: client.birth_date,
+        document_number: &client.document_number,
+        country: &client.country,
+        from: statement.from,
+        to: statement.to,
+        opening_balance: statement.opening_balance,
+        closing_balance: statement.closing_balance,
+    }])?;
+    let movements = write_csv(&statement.movements)?;
+    Ok(format!("{header}\n{movements}"))
+}
+
+/// Write [Serialize] records as CSV, with a header line named after their fields
+/// Returns a [CommonError] if a record cannot be written
+fn write_csv<T: Serialize>(records: &[T]) -> Result<String, CommonError> {
+    let mut writer = Writer::from_writer(Vec::new());
+    for record in records {
+        writer.serialize(record).map_err(to_common_error)?;
+    }
+    let content = writer
+        .into_inner()
+        .map_err(|error| to_common_error(error.into_error().into()))?;
+    String::from_utf8(content).map_err(|error| CommonError::StorageFailed(error.to_string()))
+}
+
+/// Map a [csv::Error] raised while rendering a statement to [CommonError]
+fn to_common_error(error: csv::Error) -> CommonError {
+    CommonError::StorageFailed(error.to_string())
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::client_statement_dto::StatementLine;
+    use crate::model::transaction_model::TransactionKind;
+    use crate::stub::client_info_stub::stub::create_client_info_stub;
+    use crate::stub::transaction_info_stub::stub::TRANSACTION_CREATED_AT;
+
+    /// Create a [ClientStatement] of July 2025 with a credit and a settlement
+    fn create_client_statement() -> ClientStatement {
+        ClientStatement {
+            client: create_client_info_stub(),
+            from: NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2025, 7, 31).unwrap(),
+            opening_balance: Decimal::new(500, 2),
+            closing_balance: Decimal::ZERO,
+            movements: vec![
+                StatementLine {
+                    transaction_id: 1,
+                    created_at: *TRANSACTION_CREATED_AT,
+                    kind: TransactionKind::Credit,
+                    amount: Decimal::new(100, 2),
+                    balance: Decimal::new(600, 2),
+                    reversal_of: None,
+                },
+                StatementLine {
+                    transaction_id: 2,
+                    created_at: *TRANSACTION_CREATED_AT,
+                    kind: TransactionKind::Settlement,
+                    amount: Decimal::new(-600, 2),
+                    balance: Decimal::ZERO,
+                    reversal_of: None,
+                },
+            ],
+        }
+    }
+
+    /// Scenario:
+    /// Parse the statement formats from their names
+    /// Expectation:
+    /// json and csv should be accepted in any case, any other name should be rejected
+    #[test]
+    fn when_parse_statement_format_should_accept_json_and_csv() {
+        assert_eq!(StatementFormat::Json, "json".parse().unwrap());
+        assert_eq!(StatementFormat::Csv, "CSV".parse().unwrap());
+        assert!(matches!(
+            "pdf".parse::<StatementFormat>(),
+            Err(CommonError::InvalidField { field, .. }) if field == "format"
+        ));
+    }
+
+    /// Scenario:
+    /// Render a statement with a credit and a settlement as CSV
+    /// Expectation:
+    /// The client and balances table, a blank line and the movements table should be written
+    #[test]
+    fn when_render_statement_csv_should_write_the_header_and_the_movements() {
+        let statement = create_client_statement();
+        let client = &statement.client;
+
+        let result = render_statement_csv(&statement).unwrap();
+
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(
+            "client_id,client_name,birth_date,document_number,country,from,to,opening_balance,closing_balance",
+            lines[0]
+        );
+        assert_eq!(
+            format!(
+                "{},{},{},{},{},2025-07-01,2025-07-31,5.00,0",
+                client.client_id,
+                client.client_name,
+                client.birth_date,
+                client.document_number,
+                client.country
+            ),
+            lines[1]
+        );
+        assert_eq!("", lines[2]);
+        assert_eq!(
+            "transaction_id,created_at,kind,amount,balance,reversal_of",
+            lines[3]
+        );
+        assert_eq!("1,2025-07-03T10:00:00Z,CREDIT,1.00,6.00,", lines[4]);
+        assert_eq!("2,2025-07-03T10:00:00Z,SETTLEMENT,-6.00,0,", lines[5]);
+        assert_eq!(6, lines.len());
+        assert_eq!(
+            "statement_1_2025-07-01_2025-07-31.csv",
+            StatementFormat::Csv.file_name(&statement)
+        );
+    }
+}
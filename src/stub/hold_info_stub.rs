@@ -0,0 +1,30 @@
+pub mod stub {
+    use crate::dto::hold_info_dto::HoldInfo;
+    use crate::model::hold_model::HoldStatus;
+    use crate::stub::client_info_stub::stub::{CLIENT_BALANCE, CLIENT_ID};
+    use crate::stub::new_hold_stub::stub::HOLD_AMOUNT;
+    use chrono::{DateTime, Utc};
+    use once_cell::sync::Lazy;
+    use rust_decimal::Decimal;
+
+    pub const HOLD_ID: i32 = 1;
+    pub static HOLD_EXPIRES_AT: Lazy<DateTime<Utc>> = Lazy::new(|| {
+        DateTime::parse_from_rfc3339("2025-07-10T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    });
+
+    /// Create a [HoldInfo] populated with basic stub data
+    pub fn create_hold_info_stub() -> HoldInfo {
+        HoldInfo {
+            hold_id: HOLD_ID,
+            client_id: CLIENT_ID,
+            amount: *HOLD_AMOUNT,
+            captured_amount: Decimal::ZERO,
+            status: HoldStatus::Active,
+            expires_at: *HOLD_EXPIRES_AT,
+            balance: *CLIENT_BALANCE,
+            available_balance: *CLIENT_BALANCE - *HOLD_AMOUNT,
+        }
+    }
+}
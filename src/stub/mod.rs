@@ -1,4 +1,8 @@
 pub mod client_info_stub;
+pub mod hold_info_stub;
 pub mod new_client_stub;
 pub mod new_credit_transaction_stub;
 pub mod new_debit_transaction_stub;
+pub mod new_hold_stub;
+pub mod new_transfer_stub;
+pub mod transaction_info_stub;
@@ -13,7 +13,7 @@ pub mod stub {
     pub fn create_new_client_stub() -> NewClient {
         NewClient {
             client_name: String::from(CLIENT_NAME),
-            birth_date: CLIENT_BIRTH_DATE.clone(),
+            birth_date: *CLIENT_BIRTH_DATE,
             document_number: String::from(CLIENT_DOCUMENT_NUMBER),
             country: String::from(CLIENT_COUNTRY),
         }
@@ -10,7 +10,8 @@ pub mod stub {
     pub fn create_new_credit_transaction_stub() -> NewCreditTransaction {
         NewCreditTransaction {
             client_id: CLIENT_ID,
-            credit_amount: CLIENT_CREDIT_AMOUNT.clone(),
+            credit_amount: *CLIENT_CREDIT_AMOUNT,
+            idempotency_key: None,
         }
     }
 }
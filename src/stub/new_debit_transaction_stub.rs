@@ -10,7 +10,8 @@ pub mod stub {
     pub fn create_new_debit_transaction_stub() -> NewDebitTransaction {
         NewDebitTransaction {
             client_id: CLIENT_ID,
-            debit_amount: CLIENT_DEBIT_AMOUNT.clone(),
+            debit_amount: *CLIENT_DEBIT_AMOUNT,
+            idempotency_key: None,
         }
     }
 }
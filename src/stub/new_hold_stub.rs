@@ -0,0 +1,17 @@
+pub mod stub {
+    use crate::dto::new_hold_dto::NewHold;
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use once_cell::sync::Lazy;
+    use rust_decimal::Decimal;
+
+    pub static HOLD_AMOUNT: Lazy<Decimal> = Lazy::new(|| Decimal::new(40, 2));
+
+    /// Create a [NewHold] populated with basic stub data
+    pub fn create_new_hold_stub() -> NewHold {
+        NewHold {
+            client_id: CLIENT_ID,
+            hold_amount: *HOLD_AMOUNT,
+            expires_in_seconds: None,
+        }
+    }
+}
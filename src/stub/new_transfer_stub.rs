@@ -0,0 +1,18 @@
+pub mod stub {
+    use crate::dto::new_transfer_dto::NewTransfer;
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use once_cell::sync::Lazy;
+    use rust_decimal::Decimal;
+
+    pub const TO_CLIENT_ID: i32 = 2;
+    pub static TRANSFER_AMOUNT: Lazy<Decimal> = Lazy::new(|| Decimal::new(100, 2));
+
+    /// Create a [NewTransfer] populated with basic stub data
+    pub fn create_new_transfer_stub() -> NewTransfer {
+        NewTransfer {
+            from_client_id: CLIENT_ID,
+            to_client_id: TO_CLIENT_ID,
+            transfer_amount: *TRANSFER_AMOUNT,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+pub mod stub {
+    use crate::dto::transaction_info_dto::TransactionInfo;
+    use crate::model::transaction_model::TransactionKind;
+    use crate::stub::client_info_stub::stub::CLIENT_ID;
+    use chrono::{DateTime, Utc};
+    use once_cell::sync::Lazy;
+    use rust_decimal::Decimal;
+
+    pub const TRANSACTION_ID: i32 = 1;
+    pub static TRANSACTION_AMOUNT: Lazy<Decimal> = Lazy::new(|| Decimal::new(100, 2));
+    pub static TRANSACTION_CREATED_AT: Lazy<DateTime<Utc>> = Lazy::new(|| {
+        DateTime::parse_from_rfc3339("2025-07-03T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    });
+
+    /// Create a [TransactionInfo] populated with basic stub data
+    pub fn create_transaction_info_stub() -> TransactionInfo {
+        TransactionInfo {
+            transaction_id: TRANSACTION_ID,
+            client_id: CLIENT_ID,
+            kind: TransactionKind::Credit,
+            amount: *TRANSACTION_AMOUNT,
+            balance: *TRANSACTION_AMOUNT,
+            created_at: *TRANSACTION_CREATED_AT,
+            reversal_of: None,
+            reversed_amount: Decimal::ZERO,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::dto::new_client_dto::NewClient;
+use crate::dto::update_client_dto::UpdateClient;
+use crate::errors::common_error::CommonError;
+use chrono::NaiveDate;
+
+/// Validate the informed fields of [UpdateClient] update_client, [NaiveDate] today is the last valid birth date
+/// Returns a [CommonError] naming the offending field if the update is invalid
+pub fn validate_update_client(
+    update_client: &UpdateClient,
+    today: NaiveDate,
+) -> Result<(), CommonError> {
+    if update_client.client_name.is_none()
+        && update_client.birth_date.is_none()
+        && update_client.document_number.is_none()
+        && update_client.country.is_none()
+    {
+        return Err(CommonError::invalid_field(
+            "body",
+            "must inform at least one field".to_string(),
+        ));
+    }
+    for (field, value) in [
+        ("client_name", &update_client.client_name),
+        ("document_number", &update_client.document_number),
+        ("country", &update_client.country),
+    ] {
+        if value
+            .as_deref()
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            return Err(CommonError::invalid_field(
+                field,
+                "must not be empty".to_string(),
+            ));
+        }
+    }
+    if update_client
+        .birth_date
+        .is_some_and(|birth_date| birth_date > today)
+    {
+        return Err(CommonError::invalid_field(
+            "birth_date",
+            "must not be in the future".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate every field of [NewClient] new_client, [NaiveDate] today is the last valid birth date
+/// Returns a [CommonError] naming the offending field if the client is invalid
+pub fn validate_new_client(new_client: &NewClient, today: NaiveDate) -> Result<(), CommonError> {
+    validate_update_client(
+        &UpdateClient {
+            client_name: Some(new_client.client_name.clone()),
+            birth_date: Some(new_client.birth_date),
+            document_number: Some(new_client.document_number.clone()),
+            country: Some(new_client.country.clone()),
+        },
+        today,
+    )
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::new_client_stub::stub::{create_new_client_stub, CLIENT_BIRTH_DATE};
+
+    /// Get the date used as today
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+    }
+
+    /// Scenario:
+    /// Execute validate_update_client with valid fields
+    /// Expectation:
+    /// No error should be returned
+    #[test]
+    fn when_validate_update_client_with_valid_fields_should_return_ok() {
+        let update_client = UpdateClient {
+            client_name: Some("some-name".to_string()),
+            birth_date: Some(today()),
+            ..Default::default()
+        };
+
+        assert!(validate_update_client(&update_client, today()).is_ok());
+    }
+
+    /// Scenario:
+    /// Execute validate_update_client without fields, with a blank country and with a future birth date
+    /// Expectation:
+    /// A [CommonError] naming the offending field should be returned
+    #[test]
+    fn when_validate_update_client_with_invalid_fields_should_return_common_error() {
+        let blank_country = UpdateClient {
+            country: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let future_birth_date = UpdateClient {
+            birth_date: today().succ_opt(),
+            ..Default::default()
+        };
+
+        for (update_client, expected_field) in [
+            (UpdateClient::default(), "body"),
+            (blank_country, "country"),
+            (future_birth_date, "birth_date"),
+        ] {
+            assert!(matches!(
+                validate_update_client(&update_client, today()),
+                Err(CommonError::InvalidField { field, .. }) if field == expected_field
+            ));
+        }
+    }
+
+    /// Scenario:
+    /// Execute validate_new_client with a valid client, a blank country and a birth date in the future
+    /// Expectation:
+    /// Only the valid client should be accepted, the others rejected naming their field
+    #[test]
+    fn when_validate_new_client_should_check_every_field() {
+        // the stub client is born today
+        let today = *CLIENT_BIRTH_DATE;
+        let mut blank_country = create_new_client_stub();
+        blank_country.country = " ".to_string();
+        let mut future_birth_date = create_new_client_stub();
+        future_birth_date.birth_date = today.succ_opt().unwrap();
+
+        assert!(validate_new_client(&create_new_client_stub(), today).is_ok());
+        assert!(matches!(
+            validate_new_client(&blank_country, today),
+            Err(CommonError::InvalidField { ref field, .. }) if field == "country"
+        ));
+        assert!(matches!(
+            validate_new_client(&future_birth_date, today),
+            Err(CommonError::InvalidField { ref field, .. }) if field == "birth_date"
+        ));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod client_validator;
+pub mod transaction_validator;
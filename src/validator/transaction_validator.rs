@@ -0,0 +1,316 @@
+use crate::constants::constants::{
+    MAX_AMOUNT_SCALE, MAX_BATCH_ITEMS, MAX_HOLD_EXPIRY_SECONDS, MAX_TRANSACTION_AMOUNT,
+};
+use crate::dto::new_credit_transaction::NewCreditTransaction;
+use crate::dto::new_debit_transaction::NewDebitTransaction;
+use crate::dto::new_hold_dto::NewHold;
+use crate::dto::new_transfer_dto::NewTransfer;
+use crate::dto::transaction_batch_dto::{BatchTransaction, NewTransactionBatch};
+use crate::errors::common_error::CommonError;
+use rust_decimal::Decimal;
+
+/// Limits applied to every transaction amount
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionLimits {
+    // max decimal places of an amount
+    pub max_scale: u32,
+    // max amount of a single transaction
+    pub max_amount: Decimal,
+}
+
+/// Limits from [MAX_AMOUNT_SCALE] and [MAX_TRANSACTION_AMOUNT]
+impl Default for TransactionLimits {
+    fn default() -> Self {
+        TransactionLimits {
+            max_scale: MAX_AMOUNT_SCALE,
+            max_amount: Decimal::from(MAX_TRANSACTION_AMOUNT),
+        }
+    }
+}
+
+/// Validate [NewCreditTransaction] credit_transaction against [TransactionLimits] limits
+/// Returns a [CommonError] naming the offending field if the transaction is invalid
+pub fn validate_new_credit_transaction(
+    credit_transaction: &NewCreditTransaction,
+    limits: &TransactionLimits,
+) -> Result<(), CommonError> {
+    validate_amount("credit_amount", credit_transaction.credit_amount, limits)
+}
+
+/// Validate [NewDebitTransaction] debit_transaction against [TransactionLimits] limits
+/// Returns a [CommonError] naming the offending field if the transaction is invalid
+pub fn validate_new_debit_transaction(
+    debit_transaction: &NewDebitTransaction,
+    limits: &TransactionLimits,
+) -> Result<(), CommonError> {
+    validate_amount("debit_amount", debit_transaction.debit_amount, limits)
+}
+
+/// Validate that [NewTransactionBatch] batch has between 1 and [MAX_BATCH_ITEMS] items
+/// Returns a [CommonError] naming the items field if the batch is empty or too big
+pub fn validate_new_transaction_batch(batch: &NewTransactionBatch) -> Result<(), CommonError> {
+    if !(1..=MAX_BATCH_ITEMS).contains(&batch.items.len()) {
+        return Err(CommonError::invalid_field(
+            "items",
+            format!("must have between 1 and {MAX_BATCH_ITEMS} items"),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate [BatchTransaction] item of a batch against [TransactionLimits] limits
+/// Returns a [CommonError] naming the offending field if the item is invalid or has an idempotency key
+pub fn validate_batch_transaction(
+    item: &BatchTransaction,
+    limits: &TransactionLimits,
+) -> Result<(), CommonError> {
+    // a batch is applied at once, its items cannot be retried one by one
+    if item.idempotency_key().is_some() {
+        return Err(CommonError::invalid_field(
+            "idempotency_key",
+            "is not supported on batch items".to_string(),
+        ));
+    }
+    match item {
+        BatchTransaction::Credit(credit_transaction) => {
+            validate_new_credit_transaction(credit_transaction, limits)
+        }
+        BatchTransaction::Debit(debit_transaction) => {
+            validate_new_debit_transaction(debit_transaction, limits)
+        }
+    }
+}
+
+/// Validate [NewHold] hold against [TransactionLimits] limits
+/// Returns a [CommonError] naming the offending field if the hold is invalid
+pub fn validate_new_hold(hold: &NewHold, limits: &TransactionLimits) -> Result<(), CommonError> {
+    if let Some(expires_in_seconds) = hold.expires_in_seconds {
+        if !(1..=MAX_HOLD_EXPIRY_SECONDS).contains(&expires_in_seconds) {
+            return Err(CommonError::invalid_field(
+                "expires_in_seconds",
+                format!("must be between 1 and {MAX_HOLD_EXPIRY_SECONDS}"),
+            ));
+        }
+    }
+    validate_amount("hold_amount", hold.hold_amount, limits)
+}
+
+/// Validate [NewTransfer] transfer against [TransactionLimits] limits
+/// Returns a [CommonError] naming the offending field if the transfer is invalid
+pub fn validate_new_transfer(
+    transfer: &NewTransfer,
+    limits: &TransactionLimits,
+) -> Result<(), CommonError> {
+    if transfer.from_client_id == transfer.to_client_id {
+        return Err(CommonError::invalid_field(
+            "to_client_id",
+            "must be different from from_client_id".to_string(),
+        ));
+    }
+    validate_amount("transfer_amount", transfer.transfer_amount, limits)
+}
+
+/// Validate that [Decimal] amount is positive, well scaled and below the max amount
+/// Returns a [CommonError] naming [str] field if the amount is invalid
+pub fn validate_amount(
+    field: &str,
+    amount: Decimal,
+    limits: &TransactionLimits,
+) -> Result<(), CommonError> {
+    if amount <= Decimal::ZERO {
+        return Err(CommonError::invalid_field(
+            field,
+            "must be greater than zero".to_string(),
+        ));
+    }
+    // trailing zeros do not count, 10.500 has 1 decimal place
+    if amount.normalize().scale() > limits.max_scale {
+        return Err(CommonError::invalid_field(
+            field,
+            format!("must have at most {} decimal places", limits.max_scale),
+        ));
+    }
+    if amount > limits.max_amount {
+        return Err(CommonError::invalid_field(
+            field,
+            format!("must not be greater than {}", limits.max_amount),
+        ));
+    }
+    Ok(())
+}
+
+/// Unit tests cases
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::new_credit_transaction_stub::stub::create_new_credit_transaction_stub;
+    use crate::stub::new_debit_transaction_stub::stub::create_new_debit_transaction_stub;
+    use crate::stub::new_hold_stub::stub::create_new_hold_stub;
+    use crate::stub::new_transfer_stub::stub::create_new_transfer_stub;
+
+    /// Scenario:
+    /// Execute validate_new_credit_transaction with a valid amount
+    /// Expectation:
+    /// Ok should be returned
+    #[test]
+    fn when_validate_new_credit_transaction_with_valid_amount_should_return_ok() {
+        let credit_transaction = create_new_credit_transaction_stub();
+
+        let result =
+            validate_new_credit_transaction(&credit_transaction, &TransactionLimits::default());
+
+        assert!(result.is_ok());
+    }
+
+    /// Scenario:
+    /// Execute validate_new_credit_transaction with a negative amount
+    /// Expectation:
+    /// A [CommonError] naming credit_amount should be returned
+    #[test]
+    fn when_validate_new_credit_transaction_with_negative_amount_should_return_common_error() {
+        let mut credit_transaction = create_new_credit_transaction_stub();
+        credit_transaction.credit_amount = Decimal::new(-100, 2);
+
+        let result =
+            validate_new_credit_transaction(&credit_transaction, &TransactionLimits::default());
+
+        assert_eq!(
+            CommonError::InvalidField {
+                field: "credit_amount".to_string(),
+                reason: "must be greater than zero".to_string(),
+            },
+            result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute validate_new_debit_transaction with a zero amount
+    /// Expectation:
+    /// A [CommonError] naming debit_amount should be returned
+    #[test]
+    fn when_validate_new_debit_transaction_with_zero_amount_should_return_common_error() {
+        let mut debit_transaction = create_new_debit_transaction_stub();
+        debit_transaction.debit_amount = Decimal::ZERO;
+
+        let result =
+            validate_new_debit_transaction(&debit_transaction, &TransactionLimits::default());
+
+        assert!(matches!(
+            result,
+            Err(CommonError::InvalidField { field, .. }) if field == "debit_amount"
+        ));
+    }
+
+    /// Scenario:
+    /// Execute validate_amount with more decimal places than allowed
+    /// Expectation:
+    /// A [CommonError] should be returned, trailing zeros should be accepted
+    #[test]
+    fn when_validate_amount_with_too_many_decimal_places_should_return_common_error() {
+        let limits = TransactionLimits::default();
+
+        let result = validate_amount("credit_amount", Decimal::new(1001, 3), &limits);
+        let trailing_zeros_result =
+            validate_amount("credit_amount", Decimal::new(1000, 3), &limits);
+
+        assert_eq!(
+            CommonError::InvalidField {
+                field: "credit_amount".to_string(),
+                reason: "must have at most 2 decimal places".to_string(),
+            },
+            result.unwrap_err()
+        );
+        assert!(trailing_zeros_result.is_ok());
+    }
+
+    /// Scenario:
+    /// Execute validate_amount with an amount greater than the configured max amount
+    /// Expectation:
+    /// A [CommonError] should be returned
+    #[test]
+    fn when_validate_amount_greater_than_max_amount_should_return_common_error() {
+        let limits = TransactionLimits {
+            max_scale: 2,
+            max_amount: Decimal::new(100, 0),
+        };
+
+        let result = validate_amount("debit_amount", Decimal::new(10001, 2), &limits);
+
+        assert_eq!(
+            CommonError::InvalidField {
+                field: "debit_amount".to_string(),
+                reason: "must not be greater than 100".to_string(),
+            },
+            result.unwrap_err()
+        );
+    }
+
+    /// Scenario:
+    /// Execute validate_new_transfer when both client ids are the same
+    /// Expectation:
+    /// A [CommonError] naming to_client_id should be returned
+    #[test]
+    fn when_validate_new_transfer_to_the_same_client_should_return_common_error() {
+        let mut transfer = create_new_transfer_stub();
+        transfer.to_client_id = transfer.from_client_id;
+
+        let result = validate_new_transfer(&transfer, &TransactionLimits::default());
+
+        assert!(matches!(
+            result,
+            Err(CommonError::InvalidField { field, .. }) if field == "to_client_id"
+        ));
+    }
+
+    /// Scenario:
+    /// Execute validate_new_hold with an expiry out of range and with a valid one
+    /// Expectation:
+    /// A [CommonError] naming expires_in_seconds should be returned only for the expiry out of range
+    #[test]
+    fn when_validate_new_hold_with_expiry_out_of_range_should_return_common_error() {
+        let mut hold = create_new_hold_stub();
+        hold.expires_in_seconds = Some(0);
+
+        let result = validate_new_hold(&hold, &TransactionLimits::default());
+
+        assert!(matches!(
+            result,
+            Err(CommonError::InvalidField { field, .. }) if field == "expires_in_seconds"
+        ));
+
+        hold.expires_in_seconds = Some(MAX_HOLD_EXPIRY_SECONDS);
+        assert!(validate_new_hold(&hold, &TransactionLimits::default()).is_ok());
+    }
+
+    /// Scenario:
+    /// Execute validate_new_transaction_batch without items and validate_batch_transaction on an item with an idempotency key
+    /// Expectation:
+    /// A [CommonError] naming the items and the idempotency_key fields should be returned
+    #[test]
+    fn when_validate_transaction_batch_without_items_or_with_idempotency_key_should_return_common_error(
+    ) {
+        let batch = NewTransactionBatch {
+            mode: Default::default(),
+            items: Vec::new(),
+        };
+        let mut debit_transaction = create_new_debit_transaction_stub();
+        debit_transaction.idempotency_key = Some("some-idempotency-key".to_string());
+
+        assert!(matches!(
+            validate_new_transaction_batch(&batch),
+            Err(CommonError::InvalidField { ref field, .. }) if field == "items"
+        ));
+        assert!(matches!(
+            validate_batch_transaction(
+                &BatchTransaction::Debit(debit_transaction),
+                &TransactionLimits::default()
+            ),
+            Err(CommonError::InvalidField { ref field, .. }) if field == "idempotency_key"
+        ));
+        assert!(validate_batch_transaction(
+            &BatchTransaction::Credit(create_new_credit_transaction_stub()),
+            &TransactionLimits::default()
+        )
+        .is_ok());
+    }
+}
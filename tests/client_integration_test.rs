@@ -1,17 +1,35 @@
 use actix_web::{test, web, App};
 use num_traits::Zero;
 use prex_core_challenge::constants::constants::{
-    MAIN_PATH, NEW_CLIENT_PATH, NEW_CREDIT_TRANSACTION_PATH, NEW_DEBIT_TRANSACTION_PATH,
+    CLIENTS_PATH, IDEMPOTENCY_KEY_HEADER, INITIAL_CLIENT_VERSION, MAIN_PATH, NEW_CLIENT_PATH,
+    NEW_CREDIT_TRANSACTION_PATH, NEW_DEBIT_TRANSACTION_PATH, STORE_BALANCE_PATH, TRANSFER_PATH,
 };
 use prex_core_challenge::controller::client_controller::*;
+use prex_core_challenge::dto::client_import_report_dto::ClientImportReport;
+use prex_core_challenge::dto::client_info_dto::ClientInfo;
+use prex_core_challenge::dto::client_page_dto::ClientPage;
+use prex_core_challenge::dto::client_statement_dto::ClientStatement;
+use prex_core_challenge::dto::hold_info_dto::HoldInfo;
 use prex_core_challenge::dto::new_client_dto::NewClient;
-use prex_core_challenge::model::client_model::Client;
+use prex_core_challenge::dto::reconciliation_report_dto::ReconciliationReport;
+use prex_core_challenge::dto::transaction_batch_report_dto::{
+    BatchItemStatus, TransactionBatchReport,
+};
+use prex_core_challenge::dto::transaction_info_dto::TransactionInfo;
+use prex_core_challenge::dto::transaction_page_dto::TransactionPage;
+use prex_core_challenge::dto::transfer_info_dto::TransferInfo;
+use prex_core_challenge::errors::common_error::CommonError;
+use prex_core_challenge::model::client_model::{Client, ClientStatus};
+use prex_core_challenge::model::hold_model::HoldStatus;
+use prex_core_challenge::model::transaction_model::TransactionKind;
 use prex_core_challenge::service::client_service::{ClientService, DynClientService};
 use prex_core_challenge::state::app_state::AppState;
+use prex_core_challenge::state::event_log::EventLog;
 use prex_core_challenge::stub::client_info_stub::stub::{create_client_info_stub, CLIENT_ID};
 use prex_core_challenge::stub::new_client_stub::stub::create_new_client_stub;
 use prex_core_challenge::stub::new_credit_transaction_stub::stub::create_new_credit_transaction_stub;
 use prex_core_challenge::stub::new_debit_transaction_stub::stub::create_new_debit_transaction_stub;
+use prex_core_challenge::stub::new_transfer_stub::stub::{create_new_transfer_stub, TO_CLIENT_ID};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicI32;
@@ -19,6 +37,7 @@ use std::sync::{Arc, RwLock};
 
 const MOCK_CLIENT_ID: i32 = 3;
 const MOCK_CLIENT_BALANCE_PATH: &str = "/client_balance/";
+const MOCK_CLIENT_TRANSACTIONS_PATH: &str = "/transactions";
 
 /// Scenario:
 /// Execute map_create_new_client when [NewClient] is valid
@@ -38,11 +57,10 @@ async fn when_map_create_new_client_should_insert_into_app_state() {
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(HashMap::new())),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
 
@@ -89,6 +107,9 @@ async fn when_map_create_new_client_and_this_all_ready_exists_should_return_comm
         document_number: client.document_number,
         country: client.country,
         balance: client.balance,
+        version: client.version,
+        status: client.status,
+        held_balance: Decimal::ZERO,
     };
 
     let new_client = NewClient {
@@ -104,11 +125,10 @@ async fn when_map_create_new_client_and_this_all_ready_exists_should_return_comm
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH);
@@ -130,6 +150,46 @@ async fn when_map_create_new_client_and_this_all_ready_exists_should_return_comm
     assert_eq!(resp.status(), http::StatusCode::FORBIDDEN);
 }
 
+/// Scenario:
+/// Execute map_create_new_client when [NewClient] has a blank name and a birth date in the future
+/// Expectation:
+/// A [StatusCode::BAD_REQUEST] should be returned and no client should be inserted into AppState
+#[actix_web::test]
+async fn when_map_create_new_client_with_invalid_fields_should_return_bad_request() {
+    let mut new_client = create_new_client_stub();
+    new_client.client_name = " ".to_string();
+    new_client.birth_date = chrono::Local::now().date_naive() + chrono::Days::new(1);
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(HashMap::new())),
+        client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let path = format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH);
+
+    let req = test::TestRequest::post()
+        .uri(&path)
+        .set_json(&new_client)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+    assert!(app_state.clients.read().unwrap().is_empty());
+}
+
 /// Scenario:
 /// Execute map_create_new_client when [NewClient] is valid and read AppState failed
 /// Expectation:
@@ -159,11 +219,10 @@ async fn when_map_create_new_client_and_read_app_state_failed_should_return_comm
     let app_state = Arc::new(AppState {
         clients: client,
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH);
@@ -201,6 +260,9 @@ async fn when_map_create_new_credit_transaction_is_valid_should_update_app_state
         document_number: client_stub.document_number,
         country: client_stub.country,
         balance: Decimal::zero(),
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(new_credit.client_id, client);
@@ -208,11 +270,10 @@ async fn when_map_create_new_credit_transaction_is_valid_should_update_app_state
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(new_credit.client_id),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
@@ -264,11 +325,10 @@ async fn when_map_create_new_credit_transaction_and_read_app_state_failed_should
     let app_state = Arc::new(AppState {
         clients: client,
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
@@ -307,6 +367,9 @@ async fn when_map_create_new_credit_transaction_and_client_id_does_not_exist_sho
         document_number: client.document_number,
         country: client.country,
         balance: client.balance,
+        version: client.version,
+        status: client.status,
+        held_balance: Decimal::ZERO,
     };
 
     let mut hashmap = HashMap::new();
@@ -315,11 +378,10 @@ async fn when_map_create_new_credit_transaction_and_client_id_does_not_exist_sho
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
@@ -360,6 +422,9 @@ async fn when_map_create_new_debit_transaction_is_valid_should_update_app_state(
         document_number: client_stub.document_number,
         country: client_stub.country,
         balance: new_debit.debit_amount,
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(new_debit.client_id, client);
@@ -367,11 +432,10 @@ async fn when_map_create_new_debit_transaction_is_valid_should_update_app_state(
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(new_debit.client_id),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH);
@@ -422,11 +486,10 @@ async fn when_map_create_new_debit_transaction_and_read_app_state_failed_should_
     let app_state = Arc::new(AppState {
         clients: client,
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH);
@@ -465,6 +528,9 @@ async fn when_map_create_new_debit_transaction_and_client_id_does_not_exist_shou
         document_number: client.document_number,
         country: client.country,
         balance: client.balance,
+        version: client.version,
+        status: client.status,
+        held_balance: Decimal::ZERO,
     };
 
     let mut hashmap = HashMap::new();
@@ -473,11 +539,10 @@ async fn when_map_create_new_debit_transaction_and_client_id_does_not_exist_shou
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let path = format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH);
@@ -517,6 +582,9 @@ async fn when_map_get_client_balance_should_get_client() {
         document_number: client_info.document_number.clone(),
         country: client_info.country,
         balance: client_info.balance,
+        version: client_info.version,
+        status: client_info.status,
+        held_balance: Decimal::ZERO,
     };
 
     let mut hashmap = HashMap::new();
@@ -525,11 +593,10 @@ async fn when_map_get_client_balance_should_get_client() {
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let route_pattern = format!("{}{}{{id}}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH);
@@ -571,6 +638,9 @@ async fn when_map_get_client_balance_and_client_id_is_invalid_should_common_erro
         document_number: client_info.document_number.clone(),
         country: client_info.country,
         balance: client_info.balance,
+        version: client_info.version,
+        status: client_info.status,
+        held_balance: Decimal::ZERO,
     };
 
     let mut hashmap = HashMap::new();
@@ -579,11 +649,10 @@ async fn when_map_get_client_balance_and_client_id_is_invalid_should_common_erro
     let app_state = Arc::new(AppState {
         clients: Arc::new(RwLock::new(hashmap)),
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let route_pattern = format!("{}{}{{id}}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH);
@@ -627,11 +696,10 @@ async fn when_map_get_client_balance_and_read_app_state_failed_should_common_err
     let app_state = Arc::new(AppState {
         clients: client,
         client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
     });
 
-    let client_service = ClientService {
-        app_state: Arc::clone(&app_state),
-    };
+    let client_service = ClientService::new(Arc::clone(&app_state));
 
     let dyn_client_service: DynClientService = Arc::new(client_service);
     let route_pattern = format!("{}{}{{id}}", MAIN_PATH, MOCK_CLIENT_BALANCE_PATH);
@@ -650,3 +718,955 @@ async fn when_map_get_client_balance_and_read_app_state_failed_should_common_err
     let resp = test::call_service(&app, req).await;
     assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+/// Scenario:
+/// Execute map_get_client_transactions after a credit and a debit on the same client
+/// Expectation:
+/// Both transactions should be returned with their resulting balance
+#[actix_web::test]
+async fn when_map_get_client_transactions_should_return_client_history() {
+    let new_credit = create_new_credit_transaction_stub();
+    let new_debit = create_new_debit_transaction_stub();
+    let client_stub = create_new_client_stub();
+
+    let client = Client {
+        client_id: CLIENT_ID,
+        client_name: client_stub.client_name,
+        birth_date: client_stub.birth_date,
+        document_number: client_stub.document_number,
+        country: client_stub.country,
+        balance: Decimal::zero(),
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
+    };
+    let mut hashmap = HashMap::new();
+    hashmap.insert(CLIENT_ID, client);
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(hashmap)),
+        client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH))
+        .set_json(&new_credit)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH))
+        .set_json(&new_debit)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let path = format!(
+        "{}/{}{}",
+        MAIN_PATH, CLIENT_ID, MOCK_CLIENT_TRANSACTIONS_PATH
+    );
+    let req = test::TestRequest::get().uri(&path).to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: TransactionPage = test::read_body_json(resp).await;
+    assert_eq!(body.total_items, 2);
+    assert_eq!(body.items[0].kind, TransactionKind::Credit);
+    assert_eq!(body.items[0].balance, new_credit.credit_amount);
+    assert_eq!(body.items[1].kind, TransactionKind::Debit);
+    assert_eq!(
+        body.items[1].balance,
+        new_credit.credit_amount - new_debit.debit_amount
+    );
+}
+
+/// Scenario:
+/// Execute map_create_new_credit_transaction twice with the same `Idempotency-Key` header
+/// Expectation:
+/// The credit should be applied once and both responses should return the same balance
+#[actix_web::test]
+async fn when_map_create_new_credit_transaction_is_retried_with_idempotency_key_should_apply_it_once(
+) {
+    let new_credit = create_new_credit_transaction_stub();
+    let client_stub = create_new_client_stub();
+
+    let client = Client {
+        client_id: CLIENT_ID,
+        client_name: client_stub.client_name,
+        birth_date: client_stub.birth_date,
+        document_number: client_stub.document_number,
+        country: client_stub.country,
+        balance: Decimal::zero(),
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
+    };
+    let mut hashmap = HashMap::new();
+    hashmap.insert(CLIENT_ID, client);
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(hashmap)),
+        client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+    let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let mut balances = Vec::new();
+    for _ in 0..2 {
+        let req = test::TestRequest::post()
+            .uri(&path)
+            .insert_header((IDEMPOTENCY_KEY_HEADER, "retried-credit"))
+            .set_json(&new_credit)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let body: Decimal = test::read_body_json(resp).await;
+        balances.push(body);
+    }
+
+    assert_eq!(balances[0], new_credit.credit_amount);
+    assert_eq!(balances[0], balances[1]);
+
+    let client_in_app_state = app_state.clients.read().unwrap();
+    let client = client_in_app_state
+        .get(&CLIENT_ID)
+        .expect("error searching client");
+    assert_eq!(client.balance, new_credit.credit_amount);
+}
+
+/// Scenario:
+/// Execute map_create_transfer when both clients exist
+/// Expectation:
+/// The amount should move between both clients in AppState
+#[actix_web::test]
+async fn when_map_create_transfer_is_valid_should_update_app_state() {
+    let new_transfer = create_new_transfer_stub();
+    let client_stub = create_new_client_stub();
+
+    let mut hashmap = HashMap::new();
+    for client_id in [CLIENT_ID, TO_CLIENT_ID] {
+        hashmap.insert(
+            client_id,
+            Client {
+                client_id,
+                client_name: client_stub.client_name.clone(),
+                birth_date: client_stub.birth_date,
+                document_number: format!("{}-{}", client_stub.document_number, client_id),
+                country: client_stub.country.clone(),
+                balance: new_transfer.transfer_amount,
+                version: INITIAL_CLIENT_VERSION,
+                status: ClientStatus::Active,
+                held_balance: Decimal::ZERO,
+            },
+        );
+    }
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(hashmap)),
+        client_id_unique: AtomicI32::new(TO_CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+    let path = format!("{}{}", MAIN_PATH, TRANSFER_PATH);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&path)
+        .set_json(&new_transfer)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: TransferInfo = test::read_body_json(resp).await;
+    assert_eq!(body.from_balance, Decimal::zero());
+    assert_eq!(
+        body.to_balance,
+        new_transfer.transfer_amount + new_transfer.transfer_amount
+    );
+
+    let client_in_app_state = app_state.clients.read().unwrap();
+    assert_eq!(
+        client_in_app_state.get(&CLIENT_ID).unwrap().balance,
+        Decimal::zero()
+    );
+}
+
+/// Scenario:
+/// Execute map_create_transfer when the credited client does not exist
+/// Expectation:
+/// A [StatusCode::NOT_FOUND] should be returned and the debited balance should not change
+#[actix_web::test]
+async fn when_map_create_transfer_and_client_id_does_not_exist_should_return_common_error() {
+    let new_transfer = create_new_transfer_stub();
+    let client = create_client_info_stub();
+
+    let client_exists = Client {
+        client_id: CLIENT_ID,
+        client_name: client.client_name,
+        birth_date: client.birth_date,
+        document_number: client.document_number,
+        country: client.country,
+        balance: client.balance,
+        version: client.version,
+        status: client.status,
+        held_balance: Decimal::ZERO,
+    };
+
+    let mut hashmap = HashMap::new();
+    hashmap.insert(CLIENT_ID, client_exists);
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(hashmap)),
+        client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+    let path = format!("{}{}", MAIN_PATH, TRANSFER_PATH);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&path)
+        .set_json(&new_transfer)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+    let client_in_app_state = app_state.clients.read().unwrap();
+    assert_eq!(
+        client_in_app_state.get(&CLIENT_ID).unwrap().balance,
+        client.balance
+    );
+}
+
+/// Scenario:
+/// Execute map_create_new_credit_transaction when [NewCreditTransaction] has a negative amount
+/// Expectation:
+/// A [StatusCode::BAD_REQUEST] should be returned and the balance should not change
+#[actix_web::test]
+async fn when_map_create_new_credit_transaction_with_negative_amount_should_return_bad_request() {
+    let mut new_credit = create_new_credit_transaction_stub();
+    new_credit.credit_amount = Decimal::new(-100, 2);
+    let client_stub = create_new_client_stub();
+
+    let client = Client {
+        client_id: CLIENT_ID,
+        client_name: client_stub.client_name,
+        birth_date: client_stub.birth_date,
+        document_number: client_stub.document_number,
+        country: client_stub.country,
+        balance: Decimal::zero(),
+        version: INITIAL_CLIENT_VERSION,
+        status: ClientStatus::Active,
+        held_balance: Decimal::ZERO,
+    };
+    let mut hashmap = HashMap::new();
+    hashmap.insert(CLIENT_ID, client);
+
+    let app_state = Arc::new(AppState {
+        clients: Arc::new(RwLock::new(hashmap)),
+        client_id_unique: AtomicI32::new(CLIENT_ID),
+        ..Default::default()
+    });
+
+    let client_service = ClientService::new(Arc::clone(&app_state));
+
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+    let path = format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&path)
+        .set_json(&new_credit)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+    let client_in_app_state = app_state.clients.read().unwrap();
+    let client = client_in_app_state
+        .get(&CLIENT_ID)
+        .expect("error searching client");
+    assert_eq!(client.balance, Decimal::zero());
+}
+
+/// Scenario:
+/// Execute map_list_clients with both client scopes registered, following next_cursor
+/// Expectation:
+/// Every client should be listed once through the pages
+#[actix_web::test]
+async fn when_map_list_clients_should_page_through_every_client() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_clients_routes())
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    for document_number in ["1001", "1002", "1003"] {
+        let new_client = NewClient {
+            document_number: document_number.to_string(),
+            ..create_new_client_stub()
+        };
+        let req = test::TestRequest::post()
+            .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+            .set_json(&new_client)
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+
+    let mut document_numbers = Vec::new();
+    let mut path = format!("{}?page_size=2", CLIENTS_PATH);
+    loop {
+        let req = test::TestRequest::get().uri(&path).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let body: ClientPage = test::read_body_json(resp).await;
+        document_numbers.extend(body.items.into_iter().map(|client| client.document_number));
+        match body.next_cursor {
+            Some(next_cursor) => {
+                path = format!("{}?page_size=2&cursor={}", CLIENTS_PATH, next_cursor)
+            }
+            None => break,
+        }
+    }
+
+    assert_eq!(vec!["1001", "1002", "1003"], document_numbers);
+}
+
+/// Scenario:
+/// Execute map_get_client_by_document through the client routes after creating a client
+/// Expectation:
+/// The client should be found by its document number and an unknown one should return 404
+#[actix_web::test]
+async fn when_map_get_client_by_document_should_return_created_client() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let new_client = create_new_client_stub();
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(&new_client)
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/by_document/{}",
+            MAIN_PATH, new_client.document_number
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: ClientInfo = test::read_body_json(resp).await;
+    assert_eq!(client_id, body.client_id);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("{}/by_document/transactions", MAIN_PATH))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+}
+
+/// Scenario:
+/// Execute create_new_client from several threads at once with the same document number, for many documents
+/// Expectation:
+/// Exactly one registration per document should succeed, the others should return a document already exists error
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn when_create_new_client_concurrently_with_same_document_should_register_one_client() {
+    const DOCUMENTS: usize = 50;
+    const REGISTRATIONS_PER_DOCUMENT: usize = 8;
+
+    // the fsync of the event log widens the gap between the document check and the insert
+    let event_log_path = std::env::temp_dir().join(format!("prex-{}.jsonl", uuid::Uuid::new_v4()));
+    let app_state = Arc::new(AppState {
+        event_log: Some(EventLog::open(&event_log_path).unwrap()),
+        ..Default::default()
+    });
+    let dyn_client_service: DynClientService = Arc::new(ClientService::new(Arc::clone(&app_state)));
+
+    for document in 0..DOCUMENTS {
+        let document_number = format!("DOC-{document}");
+        // every registration starts at the same time
+        let barrier = Arc::new(tokio::sync::Barrier::new(REGISTRATIONS_PER_DOCUMENT));
+        let mut handles = Vec::new();
+        for _ in 0..REGISTRATIONS_PER_DOCUMENT {
+            let client_service = dyn_client_service.clone();
+            let barrier = Arc::clone(&barrier);
+            let new_client = NewClient {
+                document_number: document_number.clone(),
+                ..create_new_client_stub()
+            };
+            handles.push(tokio::spawn(async move {
+                barrier.wait().await;
+                client_service.create_new_client(new_client).await
+            }));
+        }
+
+        let mut created = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(_) => created += 1,
+                Err(error) => assert_eq!(
+                    error,
+                    CommonError::DocumentAlreadyExists(document_number.clone())
+                ),
+            }
+        }
+        assert_eq!(
+            1, created,
+            "document {document_number} registered {created} times"
+        );
+    }
+
+    let _ = std::fs::remove_file(&event_log_path);
+    assert_eq!(DOCUMENTS, app_state.clients.read().unwrap().len());
+}
+
+/// Scenario:
+/// Create a client, read its ETag and update it twice with the same If-Match through the client routes
+/// Expectation:
+/// The first update should succeed with a new ETag and the second should return 412
+#[actix_web::test]
+async fn when_map_update_client_with_stale_if_match_should_return_precondition_failed() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}{}{}",
+            MAIN_PATH, MOCK_CLIENT_BALANCE_PATH, client_id
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let update_path = format!("{}/{}", MAIN_PATH, client_id);
+    let req = test::TestRequest::patch()
+        .uri(&update_path)
+        .insert_header(("If-Match", etag.clone()))
+        .set_json(serde_json::json!({ "client_name": "some-new-name" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_ne!(etag, resp.headers().get("ETag").unwrap());
+    let body: ClientInfo = test::read_body_json(resp).await;
+    assert_eq!("some-new-name", body.client_name);
+
+    let req = test::TestRequest::patch()
+        .uri(&update_path)
+        .insert_header(("If-Match", etag))
+        .set_json(serde_json::json!({ "country": "UY" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::PRECONDITION_FAILED);
+}
+
+/// Scenario:
+/// Create a client, block it, try a credit, unblock it and close it through the client routes
+/// Expectation:
+/// The credit on the blocked account should return 409 and the account should end closed
+#[actix_web::test]
+async fn when_map_block_and_close_client_should_follow_the_status_lifecycle() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/{}/block", MAIN_PATH, client_id))
+        .to_request();
+    let body: ClientInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(ClientStatus::Blocked, body.status);
+
+    let mut new_credit_transaction = create_new_credit_transaction_stub();
+    new_credit_transaction.client_id = client_id;
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH))
+        .set_json(new_credit_transaction)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/{}/unblock", MAIN_PATH, client_id))
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/{}/close", MAIN_PATH, client_id))
+        .set_json(serde_json::json!({ "reason_code": "CUSTOMER_REQUEST" }))
+        .to_request();
+    let body: ClientInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(ClientStatus::Closed, body.status);
+    assert!(app_state.clients.read().unwrap()[&client_id]
+        .balance
+        .is_zero());
+}
+
+/// Scenario:
+/// Create a client with balance, place a hold, read the client balance and capture part of the hold through the client routes
+/// Expectation:
+/// The hold should reduce only the available balance until it is captured
+#[actix_web::test]
+async fn when_map_create_and_capture_hold_should_report_ledger_and_available_balances() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let mut new_credit_transaction = create_new_credit_transaction_stub();
+    new_credit_transaction.client_id = client_id;
+    new_credit_transaction.credit_amount = Decimal::new(1000, 2);
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH))
+        .set_json(new_credit_transaction)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/new_hold", MAIN_PATH))
+        .set_json(serde_json::json!({ "client_id": client_id, "hold_amount": "4.00" }))
+        .to_request();
+    let hold_info: HoldInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(HoldStatus::Active, hold_info.status);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}{}{}",
+            MAIN_PATH, MOCK_CLIENT_BALANCE_PATH, client_id
+        ))
+        .to_request();
+    let client_info: ClientInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(Decimal::new(1000, 2), client_info.balance);
+    assert_eq!(Decimal::new(600, 2), client_info.available_balance);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/hold/{}/capture", MAIN_PATH, hold_info.hold_id))
+        .set_json(serde_json::json!({ "capture_amount": "2.50" }))
+        .to_request();
+    let hold_info: HoldInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(HoldStatus::Captured, hold_info.status);
+    assert_eq!(Decimal::new(750, 2), hold_info.balance);
+    assert_eq!(Decimal::new(750, 2), hold_info.available_balance);
+}
+
+/// Scenario:
+/// Create a client, post a debit, read its id from the client history and reverse it twice through the client routes
+/// Expectation:
+/// The first reversal should credit a linked entry and the second one should be rejected with a conflict
+#[actix_web::test]
+async fn when_map_reverse_transaction_twice_should_reject_the_double_reversal() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let mut new_debit_transaction = create_new_debit_transaction_stub();
+    new_debit_transaction.client_id = client_id;
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_DEBIT_TRANSACTION_PATH))
+        .set_json(new_debit_transaction)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/{}{}",
+            MAIN_PATH, client_id, MOCK_CLIENT_TRANSACTIONS_PATH
+        ))
+        .to_request();
+    let transaction_page: TransactionPage =
+        test::read_body_json(test::call_service(&app, req).await).await;
+    let debit_id = transaction_page.items[0].transaction_id;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/transaction/{}/reversal", MAIN_PATH, debit_id))
+        .set_json(serde_json::json!({}))
+        .to_request();
+    let reversal: TransactionInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(TransactionKind::Credit, reversal.kind);
+    assert_eq!(Some(debit_id), reversal.reversal_of);
+    assert!(reversal.balance.is_zero());
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/transaction/{}/reversal", MAIN_PATH, debit_id))
+        .set_json(serde_json::json!({}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+}
+
+/// Scenario:
+/// Create a client and post a best effort batch with a credit, a debit and an item of an unknown client through the client routes
+/// Expectation:
+/// The known client items should be applied once and the unknown client item reported as rejected
+#[actix_web::test]
+async fn when_map_create_transaction_batch_best_effort_should_apply_valid_items() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/new_transaction_batch", MAIN_PATH))
+        .set_json(serde_json::json!({
+            "mode": "BEST_EFFORT",
+            "items": [
+                { "client_id": client_id, "credit_amount": "10.00" },
+                { "client_id": client_id + 1, "credit_amount": "5.00" },
+                { "client_id": client_id, "debit_amount": "2.50" },
+            ],
+        }))
+        .to_request();
+    let report: TransactionBatchReport =
+        test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(2, report.applied_items);
+    assert_eq!(BatchItemStatus::Rejected, report.items[1].status);
+    assert_eq!(Some(Decimal::new(750, 2)), report.items[2].balance);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}{}{}",
+            MAIN_PATH, MOCK_CLIENT_BALANCE_PATH, client_id
+        ))
+        .to_request();
+    let client_info: ClientInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(Decimal::new(750, 2), client_info.balance);
+}
+
+/// Scenario:
+/// Import a JSON Lines file with a new client and a repeated document through the client routes, then read the created client by document
+/// Expectation:
+/// The first row should be created and the repeated one reported as rejected
+#[actix_web::test]
+async fn when_map_import_clients_should_create_clients_and_report_rejected_rows() {
+    let app_state = Arc::new(AppState::default());
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let row = serde_json::to_string(&create_new_client_stub()).unwrap();
+    let req = test::TestRequest::post()
+        .uri(&format!("{}/import_clients?format=ndjson", MAIN_PATH))
+        .set_payload(format!("{row}\n{row}\n"))
+        .to_request();
+    let report: ClientImportReport =
+        test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(1, report.created_count);
+    assert_eq!(1, report.rejected_count);
+    assert_eq!(2, report.rejected[0].line);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/by_document/{}",
+            MAIN_PATH, report.created[0].document_number
+        ))
+        .to_request();
+    let client_info: ClientInfo = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(report.created[0].client_id, client_info.client_id);
+}
+
+/// Scenario:
+/// Create a client, post a credit, store the balances, post a credit and a debit, then request the statement of today as JSON and CSV
+/// Expectation:
+/// The stored balance should be a settlement line and the movements should add up to the closing balance
+#[actix_web::test]
+async fn when_map_get_client_statement_after_store_balance_should_show_the_settlement() {
+    let balance_folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+    let app_state = Arc::new(AppState {
+        balance_folder: balance_folder.display().to_string(),
+        ..Default::default()
+    });
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+
+    let requests = [
+        (
+            NEW_CREDIT_TRANSACTION_PATH,
+            serde_json::json!({ "client_id": client_id, "credit_amount": "10.00" }),
+        ),
+        (STORE_BALANCE_PATH, serde_json::json!(null)),
+        (
+            NEW_CREDIT_TRANSACTION_PATH,
+            serde_json::json!({ "client_id": client_id, "credit_amount": "4.00" }),
+        ),
+        (
+            NEW_DEBIT_TRANSACTION_PATH,
+            serde_json::json!({ "client_id": client_id, "debit_amount": "1.50" }),
+        ),
+    ];
+    for (path, body) in requests {
+        let req = test::TestRequest::post()
+            .uri(&format!("{}{}", MAIN_PATH, path))
+            .set_json(body)
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+    let _ = std::fs::remove_dir_all(&balance_folder);
+
+    let today = chrono::Utc::now().date_naive();
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/{}/statement?from={today}&to={today}",
+            MAIN_PATH, client_id
+        ))
+        .to_request();
+    let statement: ClientStatement =
+        test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(client_id, statement.client.client_id);
+    assert_eq!(
+        vec![
+            TransactionKind::Credit,
+            TransactionKind::Settlement,
+            TransactionKind::Credit,
+            TransactionKind::Debit,
+        ],
+        statement
+            .movements
+            .iter()
+            .map(|movement| movement.kind)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Decimal::new(-1000, 2), statement.movements[1].amount);
+    assert!(statement.opening_balance.is_zero());
+    assert_eq!(Decimal::new(250, 2), statement.closing_balance);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/{}/statement?from={today}&to={today}&format=csv",
+            MAIN_PATH, client_id
+        ))
+        .to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert_eq!(1, body.matches(",SETTLEMENT,-10.00,0.00,").count());
+}
+
+/// Scenario:
+/// Create a client, post a credit, store the balances and reconcile the balance file through the client routes,
+/// then reconcile a file name that was never written
+/// Expectation:
+/// The stored file should match the movements and the unknown file should return not found
+#[actix_web::test]
+async fn when_map_reconcile_balance_file_after_store_balance_should_match_the_movements() {
+    let balance_folder = std::env::temp_dir().join(format!("prex-{}", uuid::Uuid::new_v4()));
+    let app_state = Arc::new(AppState {
+        balance_folder: balance_folder.display().to_string(),
+        ..Default::default()
+    });
+    let client_service = ClientService::new(Arc::clone(&app_state));
+    let dyn_client_service: DynClientService = Arc::new(client_service);
+
+    let client_controller = ClientController::new(dyn_client_service.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(dyn_client_service))
+            .service(client_controller.create_routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CLIENT_PATH))
+        .set_json(create_new_client_stub())
+        .to_request();
+    let client_id: i32 = test::read_body_json(test::call_service(&app, req).await).await;
+    let mut new_credit_transaction = create_new_credit_transaction_stub();
+    new_credit_transaction.client_id = client_id;
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, NEW_CREDIT_TRANSACTION_PATH))
+        .set_json(new_credit_transaction)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+    let req = test::TestRequest::post()
+        .uri(&format!("{}{}", MAIN_PATH, STORE_BALANCE_PATH))
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let file_name = std::fs::read_dir(&balance_folder)
+        .unwrap()
+        .filter_map(|file| file.ok())
+        .map(|file| file.file_name().to_string_lossy().to_string())
+        .next()
+        .unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/balance_file/{}/reconciliation",
+            MAIN_PATH, file_name
+        ))
+        .to_request();
+    let report: ReconciliationReport =
+        test::read_body_json(test::call_service(&app, req).await).await;
+    let _ = std::fs::remove_dir_all(&balance_folder);
+    assert_eq!(file_name, report.file_name);
+    assert_eq!(1, report.checked_clients);
+    assert!(report.is_balanced());
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "{}/balance_file/01012020_1.DAT/reconciliation",
+            MAIN_PATH
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const OLD_FILE_CONTENT: &str = "01 10.00\n02 5.00\n03 1.00\n";
+const NEW_FILE_CONTENT: &str = "01 10.00\n02 7.50\n04 2.00\n";
+
+/// Write [str] content in a temporary file named [str] file_name
+fn write_balance_file(file_name: &str, content: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("prex_dat_test_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let path = folder.join(file_name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Run prex-dat with [str] args
+fn run_prex_dat(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_prex-dat"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Scenario:
+/// Run prex-dat diff with two balance files
+/// Expectation:
+/// The added and removed clients and the balance deltas should be printed
+#[test]
+fn when_prex_dat_diff_should_print_added_removed_and_changed_clients() {
+    let old_file = write_balance_file("old.DAT", OLD_FILE_CONTENT);
+    let new_file = write_balance_file("new.DAT", NEW_FILE_CONTENT);
+
+    let expected_result = run_prex_dat(&[
+        "diff",
+        old_file.to_str().unwrap(),
+        new_file.to_str().unwrap(),
+    ]);
+
+    assert!(expected_result.status.success());
+    assert_eq!(
+        "+ 04 2.00\n- 03 1.00\n~ 02 5.00 -> 7.50 (+2.50)\n",
+        String::from_utf8(expected_result.stdout).unwrap()
+    );
+}
+
+/// Scenario:
+/// Run prex-dat validate with a file with a wrong balance line
+/// Expectation:
+/// The file should be reported as invalid and the exit code should not be zero
+#[test]
+fn when_prex_dat_validate_with_invalid_file_should_fail() {
+    let invalid_file = write_balance_file("invalid.DAT", "01 abc\n");
+
+    let expected_result = run_prex_dat(&["validate", invalid_file.to_str().unwrap()]);
+
+    assert!(!expected_result.status.success());
+    assert!(String::from_utf8(expected_result.stdout)
+        .unwrap()
+        .contains("invalid balance line"));
+}
+
+/// Scenario:
+/// Run prex-dat balance with an existing client id
+/// Expectation:
+/// The balance of the client should be printed
+#[test]
+fn when_prex_dat_balance_should_print_the_client_balance() {
+    let old_file = write_balance_file("balance.DAT", OLD_FILE_CONTENT);
+
+    let expected_result = run_prex_dat(&["balance", old_file.to_str().unwrap(), "2"]);
+
+    assert!(expected_result.status.success());
+    assert_eq!(
+        "02 5.00\n",
+        String::from_utf8(expected_result.stdout).unwrap()
+    );
+}